@@ -0,0 +1,28 @@
+//! Deployment data for modules that model a container.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A deployable container — a Docker Compose service, a standalone
+/// Dockerfile — attached to the [`Module`](super::Module) that models it
+/// (the container level of the C4 model).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Container {
+    /// Image the container runs (`"postgres:16"`); for containers built
+    /// from source, the base image of the Dockerfile's final stage.
+    pub image: Option<String>,
+    /// Published or exposed ports as written (`"8080:80"`, `"5432"`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
+    /// Networks the container joins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<String>,
+    /// Build context directory, for containers built from source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_context: Option<PathBuf>,
+    /// Directories inside the build context that hold source files, so
+    /// layouts can place the modules of other drivers in the container.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<PathBuf>,
+}
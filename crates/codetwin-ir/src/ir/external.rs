@@ -0,0 +1,24 @@
+//! Imports that point outside the model.
+
+use serde::{Deserialize, Serialize};
+
+/// Where an [`ExternalImport`] points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ExternalKind {
+    /// The language's standard library (`fmt`, `net/http`, ...).
+    Std,
+    /// A third-party package or declared dependency.
+    ThirdParty,
+}
+
+/// An import that does not resolve to any [`Module`](super::Module) in the
+/// model, kept so layouts can still report what a module depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalImport {
+    /// Import path as written in the source (`"github.com/x/y"`).
+    pub path: String,
+    /// Standard library or third-party.
+    pub kind: ExternalKind,
+}
@@ -6,14 +6,18 @@
 //! The IR is `serde`-friendly so it can be cached on disk for snapshots and
 //! diffs (Phase 4).
 
+mod container;
 mod edge;
+mod external;
 mod model;
 mod module;
 mod symbol;
 mod visibility;
 
+pub use container::Container;
 pub use edge::{Edge, EdgeKind};
+pub use external::{ExternalImport, ExternalKind};
 pub use model::CodeModel;
 pub use module::{Module, ModuleId};
-pub use symbol::{Symbol, SymbolKind};
+pub use symbol::{Member, Symbol, SymbolKind};
 pub use visibility::Visibility;
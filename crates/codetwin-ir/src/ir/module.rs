@@ -4,7 +4,7 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-use super::Symbol;
+use super::{Container, ExternalImport, Symbol};
 
 /// Stable identifier for a module within a [`CodeModel`](super::CodeModel).
 ///
@@ -38,4 +38,16 @@ pub struct Module {
     pub symbols: Vec<Symbol>,
     /// Doc comment text extracted from the file header, if any.
     pub doc: Option<String>,
+    /// Imports that resolve to nothing in the model (standard library,
+    /// third-party packages), for drivers that can classify them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_imports: Vec<ExternalImport>,
+    /// Sub-project of a monorepo the module was parsed from
+    /// (`"services/api"`); `None` for the top-level project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Deployment data, for modules that model a container rather than
+    /// source code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<Container>,
 }
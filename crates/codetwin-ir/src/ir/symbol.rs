@@ -8,7 +8,7 @@ use super::Visibility;
 ///
 /// New variants are additive — old consumers should still compile against
 /// a newer IR thanks to `#[non_exhaustive]`.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum SymbolKind {
@@ -39,6 +39,10 @@ pub struct Symbol {
     pub visibility: Visibility,
     /// Source line (1-indexed) — handy for cross-linking to IDEs/GitHub.
     pub line: u32,
+    /// Notebook cell (1-indexed) for symbols declared in a Jupyter
+    /// notebook; [`Symbol::line`] then counts from the top of that cell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell: Option<u32>,
     /// Doc comment, if any.
     pub doc: Option<String>,
     /// Textual signature for display (e.g. `"fn foo(x: u32) -> bool"`).
@@ -46,4 +50,24 @@ pub struct Symbol {
     /// TODO(Phase 1.a): decide whether to also store a structured signature
     ///                  (parameters + return type) for richer layouts.
     pub signature: Option<String>,
+    /// Fields, columns or variants declared inside the symbol, for drivers
+    /// that model them (the columns of a SQL table).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Member>,
+}
+
+/// A field of a [`Symbol`] — a struct field, a table column, ...
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    /// Member name (`"email"`).
+    pub name: String,
+    /// Declared type as written in the source (`"VARCHAR(255)"`).
+    #[serde(rename = "type")]
+    pub ty: Option<String>,
+    /// Modifiers and constraints as written in the source (`"NOT NULL"`,
+    /// `"REFERENCES users(id)"`), in declaration order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+    /// Doc comment, if any.
+    pub doc: Option<String>,
 }
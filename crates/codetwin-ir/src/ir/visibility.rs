@@ -6,12 +6,27 @@ use serde::{Deserialize, Serialize};
 ///
 /// Languages map onto these as follows:
 ///
-/// | Language   | Public       | Private      | Protected | Internal       |
-/// | ---------- | ------------ | ------------ | --------- | -------------- |
-/// | Rust       | `pub`        | (none)       | —         | `pub(crate)`   |
-/// | Python     | no `_`       | `_prefix`    | —         | —              |
-/// | TypeScript | `export`     | (none)       | —         | —              |
-/// | Go         | Upper-case   | lower-case   | —         | —              |
+/// | Language   | Public                               | Private                                               | Protected                       | Internal                          |
+/// | ---------- | ------------------------------------ | ----------------------------------------------------- | ------------------------------- | --------------------------------- |
+/// | Rust       | `pub`                                | (none)                                                | —                               | `pub(crate)`                      |
+/// | Python     | no `_` / in `__all__`                | `_prefix` / not in `__all__`                          | —                               | —                                 |
+/// | TypeScript | `export`                             | (none)                                                | —                               | —                                 |
+/// | Go         | Upper-case                           | lower-case                                            | —                               | —                                 |
+/// | Java       | `public`                             | `private`                                             | `protected`                     | (none)                            |
+/// | Kotlin     | (none) / `public`                    | `private`                                             | `protected`                     | `internal`                        |
+/// | C#         | `public`                             | `private`                                             | `protected`                     | `internal`                        |
+/// | C / C++    | in a header / non-`static`           | `static` / anonymous namespace                        | —                               | —                                 |
+/// | Ruby       | (none) / `public` section            | `private` section / `private :name` / top-level `def` | `protected` section             | —                                 |
+/// | PHP        | every class, function and constant   | —                                                     | —                               | —                                 |
+/// | Swift      | `public` / `open`                    | `private` / `fileprivate`                             | —                               | (none) / `internal` / `package`   |
+/// | Elixir     | `def` / `defmacro` / `@type`         | `defp` / `defmacrop` / `@typep`                       | —                               | —                                 |
+/// | Scala      | (none)                               | `private` / `private[this]`                           | `protected` / `protected[this]` | `private[pkg]` / `protected[pkg]` |
+/// | Zig        | `pub`                                | (none)                                                | —                               | —                                 |
+/// | Protobuf   | every message, enum, service and rpc | —                                                     | —                               | —                                 |
+/// | OpenAPI    | every operation and schema           | —                                                     | —                               | —                                 |
+/// | SQL        | every table                          | —                                                     | —                               | —                                 |
+/// | Terraform  | `variable` / `output`                | `resource` / `data` / `module`                        | —                               | —                                 |
+/// | GraphQL    | every type and root field            | —                                                     | —                               | —                                 |
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Visibility {
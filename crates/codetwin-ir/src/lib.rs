@@ -1,4 +1,7 @@
 //! CodeTwin intermediate representation.
 
 pub mod ir;
-pub use ir::{CodeModel, Edge, EdgeKind, Module, ModuleId, Symbol, SymbolKind, Visibility};
+pub use ir::{
+    CodeModel, Container, Edge, EdgeKind, ExternalImport, ExternalKind, Member, Module, ModuleId,
+    Symbol, SymbolKind, Visibility,
+};
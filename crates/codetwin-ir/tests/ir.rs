@@ -13,10 +13,15 @@ fn sample_module() -> Module {
             kind: SymbolKind::Function,
             visibility: Visibility::Public,
             line: 42,
+            cell: None,
             doc: Some("Entry point.".to_string()),
             signature: Some("fn run() -> Result<()>".to_string()),
+            members: Vec::new(),
         }],
         doc: None,
+        external_imports: Vec::new(),
+        group: None,
+        container: None,
     }
 }
 
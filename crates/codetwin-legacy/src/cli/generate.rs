@@ -4,6 +4,7 @@ use anyhow::Result;
 
 use super::GenArgs;
 use crate::config::Config;
+use crate::drivers::DriverRegistry;
 use crate::pipeline::{self, GenOptions};
 
 /// Entry point for `codetwin gen`.
@@ -20,8 +21,10 @@ pub fn run(args: GenArgs, json: bool) -> Result<()> {
     if args.watch {
         // TODO(Phase 1.e): wire up the watcher via `crate::watch::run_loop`.
         //                  Should re-run the pipeline on debounced fs events.
+        let project_root = std::env::current_dir()?;
+        let registry = DriverRegistry::with_plugins(&config.plugins.drivers, &project_root);
         return crate::watch::run_loop(&config, move |cfg| {
-            pipeline::run(cfg, &args.clone().into(), json)
+            pipeline::run_with(&registry, cfg, &args.clone().into(), json)
         });
     }
 
@@ -1,10 +1,15 @@
 //! `codetwin list` — print the registered drivers and layouts.
 
+use std::collections::BTreeMap;
+use std::path::Path;
+
 use anyhow::Result;
 
 use super::ListArgs;
+use crate::config::Config;
 use crate::drivers::DriverRegistry;
 use crate::layouts::LayoutRegistry;
+use crate::pipeline::{discover, select_projects};
 
 /// Entry point for `codetwin list`.
 pub fn run(args: ListArgs, json: bool) -> Result<()> {
@@ -12,18 +17,35 @@ pub fn run(args: ListArgs, json: bool) -> Result<()> {
     let show_drivers = args.drivers || !args.layouts;
     let show_layouts = args.layouts || !args.drivers;
 
+    // The plain listing still works with a broken config, minus its plugins;
+    // the JSON file counts need the config, so they report the error.
+    let config = match Config::load_or_default() {
+        Ok(config) => config,
+        Err(err) if !json => {
+            tracing::warn!(error = %format!("{err:#}"), "ignoring the config; plugin drivers are not listed");
+            Config::default()
+        }
+        Err(err) => return Err(err),
+    };
+    let project_root = std::env::current_dir()?;
+    let registry = DriverRegistry::with_plugins(&config.plugins.drivers, &project_root);
+
     if json {
-        let payload = serde_json::json!({
-            "drivers": if show_drivers { DriverRegistry::default().names() } else { vec![] },
+        let mut payload = serde_json::json!({
+            "drivers": if show_drivers { registry.names() } else { vec![] },
             "layouts": if show_layouts { LayoutRegistry::default().names() } else { vec![] },
         });
+        if show_drivers {
+            payload["file_counts"] =
+                serde_json::to_value(file_counts(&registry, &config, &project_root)?)?;
+        }
         println!("{}", serde_json::to_string_pretty(&payload)?);
         return Ok(());
     }
 
     if show_drivers {
         println!("drivers:");
-        for name in DriverRegistry::default().names() {
+        for name in registry.names() {
             println!("  - {name}");
         }
     }
@@ -35,3 +57,26 @@ pub fn run(args: ListArgs, json: bool) -> Result<()> {
     }
     Ok(())
 }
+
+/// Number of discovered files each driver selected for the current project
+/// (or one of its nested projects) owns — the same selection `gen` makes,
+/// so a `drivers` override in the config applies.
+fn file_counts(
+    registry: &DriverRegistry,
+    config: &Config,
+    project_root: &Path,
+) -> Result<BTreeMap<&'static str, usize>> {
+    let mut counts = BTreeMap::new();
+    for project in select_projects(registry, project_root, config)? {
+        if project.drivers.is_empty() {
+            continue;
+        }
+        let files = discover(&project.config(config, project_root))?;
+        for driver in &project.drivers {
+            let claims = driver.claims();
+            *counts.entry(driver.name()).or_default() +=
+                files.iter().filter(|f| claims.owns(f)).count();
+        }
+    }
+    Ok(counts)
+}
@@ -5,9 +5,11 @@
 
 mod format;
 mod layer;
+mod plugins;
 
 pub use format::OutputFormat;
 pub use layer::LayerSpec;
+pub use plugins::{PluginDriverSpec, PluginsSpec};
 
 use std::path::{Path, PathBuf};
 
@@ -37,6 +39,9 @@ pub struct Config {
     pub layers: Vec<LayerSpec>,
     /// Optional explicit driver list, overriding auto-detection.
     pub drivers: Option<Vec<String>>,
+    /// Out-of-process plugins (`[[plugins.drivers]]`).
+    #[serde(skip_serializing_if = "PluginsSpec::is_empty")]
+    pub plugins: PluginsSpec,
 }
 
 impl Default for Config {
@@ -54,6 +59,7 @@ impl Default for Config {
             ],
             layers: Vec::new(),
             drivers: None,
+            plugins: PluginsSpec::default(),
         }
     }
 }
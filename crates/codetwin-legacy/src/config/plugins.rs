@@ -0,0 +1,52 @@
+//! Out-of-process plugins declared in `codetwin.toml`.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The `[plugins]` table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginsSpec {
+    /// External drivers, one `[[plugins.drivers]]` entry each.
+    pub drivers: Vec<PluginDriverSpec>,
+}
+
+impl PluginsSpec {
+    /// Whether no plugin is declared (the table is then left out of saved
+    /// configs).
+    pub fn is_empty(&self) -> bool {
+        self.drivers.is_empty()
+    }
+}
+
+/// A driver implemented by an executable speaking the external-driver
+/// protocol — see [`crate::drivers::ExternalDriver`].
+///
+/// ```toml
+/// [[plugins.drivers]]
+/// name       = "dsl"
+/// command    = "tools/dsl-codetwin"
+/// extensions = ["dsl"]
+/// detect     = ["dsl.yaml"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginDriverSpec {
+    /// Driver name, as used by `drivers = [...]` and `codetwin list`.
+    pub name: String,
+    /// Executable to run. Paths with a directory are relative to the
+    /// project root; bare names are looked up on `PATH`.
+    pub command: PathBuf,
+    /// Extra arguments passed to `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extensions (without the dot) of the files the driver parses; empty
+    /// hands it every discovered file.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Marker files whose presence in a project root activates the driver;
+    /// empty activates it for the root project.
+    #[serde(default)]
+    pub detect: Vec<String>,
+}
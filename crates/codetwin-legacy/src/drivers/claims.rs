@@ -0,0 +1,157 @@
+//! Which discovered files belong to a driver.
+
+use std::io::Read;
+use std::path::Path;
+
+use super::common::has_extension;
+
+/// The files a [`Driver`](super::Driver) parses, so the pipeline can hand
+/// each driver its own files instead of the whole tree.
+///
+/// File-name patterns support `*` and match case-insensitively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileClaims {
+    /// Extensions of the files the driver parses, without the dot.
+    pub extensions: &'static [&'static str],
+    /// File-name patterns of files it parses whatever their extension
+    /// (`"Dockerfile.*"`, `"compile_commands.json"`).
+    pub names: &'static [&'static str],
+    /// Interpreters marking an extension-less script as the driver's in its
+    /// `#!` line or editor modeline (`"python"` also covers `python3.12`).
+    pub interpreters: &'static [&'static str],
+    /// File-name patterns of files the driver reads without owning them,
+    /// e.g. the Kotlin sources Java resolves types against; `"*"` for every
+    /// file.
+    pub reads: &'static [&'static str],
+}
+
+impl FileClaims {
+    /// Every file, owning none — the default for drivers that declare
+    /// nothing.
+    pub const ALL: Self = Self {
+        extensions: &[],
+        names: &[],
+        interpreters: &[],
+        reads: &["*"],
+    };
+
+    /// Files with one of `extensions`, and nothing else.
+    pub const fn extensions(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            names: &[],
+            interpreters: &[],
+            reads: &[],
+        }
+    }
+
+    /// Whether the driver parses `path` as one of its own files.
+    pub fn owns(&self, path: &Path) -> bool {
+        has_extension(path, self.extensions)
+            || self.names.iter().any(|p| matches_name(p, path))
+            || self.owns_script(path)
+    }
+
+    /// Whether `path` is an extension-less script for one of
+    /// [`interpreters`](Self::interpreters).
+    pub fn owns_script(&self, path: &Path) -> bool {
+        !self.interpreters.is_empty()
+            && path.extension().is_none()
+            && script_interpreter(path).is_some_and(|i| self.interpreters.contains(&i.as_str()))
+    }
+
+    /// Whether the pipeline hands `path` to the driver: owned or read.
+    pub fn wants(&self, path: &Path) -> bool {
+        self.owns(path) || self.reads.iter().any(|p| matches_name(p, path))
+    }
+}
+
+/// `pattern` against the file name of `path`, ASCII case-insensitively.
+fn matches_name(pattern: &str, path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let name = name.to_ascii_lowercase();
+    let pattern = pattern.to_ascii_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Interpreter an extension-less file names in its `#!` line
+/// (`#!/usr/bin/env python3`) or in an editor modeline among its first
+/// lines (`# vim: ft=ruby`, `# -*- mode: python -*-`), without version
+/// suffix (`python3.12` → `python`).
+pub fn script_interpreter(path: &Path) -> Option<String> {
+    let mut head = Vec::new();
+    std::fs::File::open(path)
+        .ok()?
+        .take(1024)
+        .read_to_end(&mut head)
+        .ok()?;
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.lines();
+
+    let first = lines.next()?;
+    let name = if let Some(command) = first.strip_prefix("#!") {
+        let mut words = command.split_whitespace();
+        let program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            words.find(|w| !w.starts_with('-') && !w.contains('='))?
+        } else {
+            program
+        }
+        .to_string()
+    } else {
+        std::iter::once(first)
+            .chain(lines)
+            .take(5)
+            .find_map(modeline)?
+    };
+    let name = name
+        .to_ascii_lowercase()
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-')
+        .to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+/// File type named by a vim (`vim: set ft=python:`) or emacs
+/// (`-*- mode: ruby -*-`, `-*- ruby -*-`) modeline on `line`.
+fn modeline(line: &str) -> Option<String> {
+    if let Some(start) = line.find("-*-") {
+        let rest = &line[start + 3..];
+        let body = &rest[..rest.find("-*-")?];
+        return body
+            .split(';')
+            .find_map(|setting| match setting.split_once(':') {
+                Some((key, value)) if key.trim().eq_ignore_ascii_case("mode") => Some(value.trim()),
+                Some(_) => None,
+                None => Some(setting.trim()),
+            })
+            .filter(|mode| !mode.is_empty())
+            .map(str::to_string);
+    }
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .find_map(|marker| line.find(marker).map(|i| i + marker.len()))?;
+    line[start..]
+        .split([' ', ':'])
+        .find_map(|option| {
+            let (key, value) = option.split_once('=')?;
+            matches!(key, "ft" | "filetype" | "syntax").then_some(value)
+        })
+        .map(str::to_string)
+}
@@ -0,0 +1,200 @@
+//! Helpers shared by the tree-sitter drivers.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use rayon::prelude::*;
+use tree_sitter::{Language, Node, Parser, Tree};
+
+use crate::ir::{Edge, EdgeKind, ModuleId};
+
+/// Parse, in parallel, every path for which `language` returns a grammar and
+/// hand each `(path, source, tree)` to `extract`.
+///
+/// Output order matches the order of `paths`.
+pub(super) fn parse_each<T, L, F>(paths: &[PathBuf], language: L, extract: F) -> Result<Vec<T>>
+where
+    T: Send,
+    L: Fn(&Path) -> Option<Language> + Sync,
+    F: Fn(&Path, &str, &Tree) -> Result<T> + Sync,
+{
+    paths
+        .par_iter()
+        .filter_map(|p| language(p).map(|lang| (p, lang)))
+        .map_init(Parser::new, |parser, (path, lang)| {
+            parser.set_language(lang).map_err(|e| anyhow!("{e}"))?;
+            let source = read_source(path)?;
+            let tree = parse_source(parser, path, &source)?;
+            extract(path, &source, &tree)
+        })
+        .collect()
+}
+
+/// Read, in parallel, every path accepted by `filter` and hand each
+/// `(path, source)` to `extract`. The counterpart of [`parse_each`] for
+/// drivers built on the [`scanner`](super::scanner).
+///
+/// Output order matches the order of `paths`.
+pub(super) fn scan_each<T, P, F>(paths: &[PathBuf], filter: P, extract: F) -> Result<Vec<T>>
+where
+    T: Send,
+    P: Fn(&Path) -> bool + Sync,
+    F: Fn(&Path, &str) -> Result<T> + Sync,
+{
+    paths
+        .par_iter()
+        .filter(|p| filter(p))
+        .map(|path| extract(path, &read_source(path)?))
+        .collect()
+}
+
+/// Read a source file, attaching the path to any I/O error. Bytes that are
+/// not UTF-8 (a Latin-1 `©` in a comment) are replaced with U+FFFD rather
+/// than failing the file, so lines and offsets stay where they were.
+pub(super) fn read_source(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(String::from_utf8(bytes).unwrap_or_else(|e| {
+        tracing::debug!(path = %path.display(), "reading non-UTF-8 source lossily");
+        String::from_utf8_lossy(e.as_bytes()).into_owned()
+    }))
+}
+
+/// Run `parser` over `source`, surfacing a [`crate::Error::Parse`] if
+/// tree-sitter gives up.
+pub(super) fn parse_source(parser: &mut Parser, path: &Path, source: &str) -> Result<Tree> {
+    parser.parse(source, None).ok_or_else(|| {
+        crate::Error::Parse {
+            path: path.to_path_buf(),
+            reason: "tree-sitter returned no tree".to_string(),
+        }
+        .into()
+    })
+}
+
+/// `true` when `path` has one of `extensions` (without the leading dot).
+pub(super) fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.contains(&e))
+}
+
+/// Lexically resolve `.` and `..` components so paths built by joining
+/// relative specifiers compare equal to discovered paths.
+pub(super) fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if out.file_name().is_some() => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Deepest directory that contains every path.
+pub(super) fn common_root<'a>(paths: impl Iterator<Item = &'a Path>) -> PathBuf {
+    let mut root: Option<PathBuf> = None;
+    for path in paths {
+        let dir = normalize_path(path.parent().unwrap_or(Path::new("")));
+        root = Some(match root {
+            None => dir,
+            Some(r) => r
+                .ancestors()
+                .find(|a| dir.starts_with(a))
+                .unwrap_or(Path::new(""))
+                .to_path_buf(),
+        });
+    }
+    root.unwrap_or_default()
+}
+
+/// `src/app/service.ts` → `src/app/service`: the path relative to `root`,
+/// extension dropped, `/`-separated.
+pub(super) fn relative_module_id(path: &Path, root: &Path) -> String {
+    let path = normalize_path(path);
+    let rel = path.strip_prefix(root).unwrap_or(&path);
+    let mut segments: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if let Some(last) = segments.last_mut()
+        && let Some((stem, _)) = last.rsplit_once('.')
+    {
+        *last = stem.to_string();
+    }
+    segments.join("/")
+}
+
+/// Source text covered by `node`.
+pub(super) fn text<'a>(node: Node<'_>, src: &'a [u8]) -> &'a str {
+    node.utf8_text(src).unwrap_or_default()
+}
+
+/// 1-indexed start line of `node`.
+pub(super) fn line(node: Node<'_>) -> u32 {
+    node.start_position().row as u32 + 1
+}
+
+/// Source text from the start of `node` up to (not including) `end`,
+/// whitespace-collapsed — the usual shape of a display signature.
+pub(super) fn header(node: Node<'_>, end: Option<Node<'_>>, src: &[u8]) -> String {
+    let end = end.map_or(node.end_byte(), |n| n.start_byte());
+    collapse_whitespace(&String::from_utf8_lossy(&src[node.start_byte()..end]))
+}
+
+pub(super) fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Join doc lines, returning `None` when nothing but whitespace is left.
+pub(super) fn join_doc(lines: Vec<String>) -> Option<String> {
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Body of a `/** ... */` (or `/*! ... */`, `/* ... */`) doc block with the
+/// leading `*` gutter removed; `None` when it is empty, as in `/**/`.
+pub(super) fn clean_block_comment(comment: &str) -> Option<String> {
+    let body = comment.strip_prefix("/*").unwrap_or(comment);
+    let body = body.strip_suffix("*/").unwrap_or(body);
+    let body = body.strip_prefix(['*', '!']).unwrap_or(body);
+    let body = body
+        .lines()
+        .map(|l| {
+            let l = l.trim();
+            l.strip_prefix('*').unwrap_or(l).trim()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = body.trim();
+    (!body.is_empty()).then(|| body.to_string())
+}
+
+/// Edge accumulator that drops self-edges and duplicates while keeping
+/// first-seen order.
+#[derive(Default)]
+pub(super) struct EdgeSet {
+    seen: HashSet<(String, String, EdgeKind)>,
+    edges: Vec<Edge>,
+}
+
+impl EdgeSet {
+    pub(super) fn push(&mut self, from: &str, to: &str, kind: EdgeKind) {
+        if from != to && self.seen.insert((from.to_string(), to.to_string(), kind)) {
+            self.edges.push(Edge {
+                from: ModuleId(from.to_string()),
+                to: ModuleId(to.to_string()),
+                kind,
+            });
+        }
+    }
+
+    pub(super) fn into_edges(self) -> Vec<Edge> {
+        self.edges
+    }
+}
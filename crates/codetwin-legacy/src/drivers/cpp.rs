@@ -0,0 +1,697 @@
+//! C / C++ driver (tree-sitter-based).
+//!
+//! A header and the source file implementing it form one [`Module`]:
+//! `gpio.h` + `gpio.c` in the same directory, or — for the common
+//! `include/` + `src/` split — a lone header and a lone source file sharing
+//! a stem anywhere in the tree. The id is the path of the source (or of the
+//! header, for header-only modules) relative to the common root, extension
+//! dropped.
+//!
+//! `.c` files use the C grammar, C++ sources and headers the C++ grammar;
+//! `.h` headers use the C++ grammar only when the project has C++ sources.
+//!
+//! Quoted `#include`s are resolved relative to the including file, then
+//! against the `-I` / `-iquote` / `-isystem` directories recorded for it in
+//! `compile_commands.json` (when one is discovered or sits in the project or
+//! `build/` directory), then by unique path suffix. Resolved includes become
+//! [`EdgeKind::Import`] edges; `<...>` includes outside the project are kept
+//! as [`ExternalImport`]s.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use tree_sitter::{Language, Node};
+
+use super::common::{
+    EdgeSet, clean_block_comment, common_root, has_extension, header, join_doc, line,
+    normalize_path, parse_each, relative_module_id, text,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+const C_SOURCES: &[&str] = &["c"];
+const CPP_SOURCES: &[&str] = &["cc", "cpp", "cxx", "c++"];
+const HEADERS: &[&str] = &["h", "hh", "hpp", "hxx", "h++", "inl", "ipp"];
+
+/// C / C++ driver (detects `CMakeLists.txt`, `Makefile`, `meson.build` or
+/// `compile_commands.json`).
+#[derive(Default)]
+pub struct CppDriver;
+
+impl Driver for CppDriver {
+    fn name(&self) -> &'static str {
+        "cpp"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        [
+            "CMakeLists.txt",
+            "Makefile",
+            "meson.build",
+            "compile_commands.json",
+        ]
+        .iter()
+        .any(|manifest| project_root.join(manifest).is_file())
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            names: &["compile_commands.json"],
+            ..FileClaims::extensions(&[
+                "c", "cc", "cpp", "cxx", "c++", "h", "hh", "hpp", "hxx", "h++", "inl", "ipp",
+            ])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let has_cpp = paths.iter().any(|p| has_extension(p, CPP_SOURCES));
+        let files = parse_each(
+            paths,
+            |p| language_for(p, has_cpp),
+            |path, source, tree| Ok(extract_file(path, source.as_bytes(), tree.root_node())),
+        )?;
+
+        let groups = pair_files(&files);
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        let ids: Vec<String> = groups
+            .iter()
+            .map(|g| relative_module_id(&files[g.primary()].path, &root))
+            .collect();
+
+        let mut module_of: HashMap<PathBuf, usize> = HashMap::new();
+        for (m, group) in groups.iter().enumerate() {
+            for f in group.files() {
+                module_of.insert(canonical(&files[f].path), m);
+            }
+        }
+        let compile_db = CompileDb::locate(paths, &root);
+        let headers: Vec<PathBuf> = files
+            .iter()
+            .filter(|f| f.is_header)
+            .map(|f| normalize_path(&f.path))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (group, id) in groups.iter().zip(&ids) {
+            let primary = &files[group.primary()];
+            let mut module = Module {
+                id: ModuleId(id.clone()),
+                name: primary
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: primary.path.clone(),
+                symbols: Vec::new(),
+                doc: None,
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            };
+
+            // Header first, so declarations win over their definitions.
+            let mut seen_symbols = HashSet::new();
+            let mut seen_externals = HashSet::new();
+            for f in group.files() {
+                let file = &files[f];
+                for symbol in &file.symbols {
+                    if seen_symbols.insert((symbol.name.clone(), symbol.kind.clone())) {
+                        module.symbols.push(symbol.clone());
+                    }
+                }
+
+                let include_dirs = compile_db
+                    .as_ref()
+                    .map_or(&[][..], |db| db.dirs_for(&file.path));
+                for include in &file.includes {
+                    let resolved =
+                        resolve_include(include, &file.path, include_dirs, &headers, &module_of);
+                    match resolved {
+                        Some(target) => edges.push(id, &ids[target], EdgeKind::Import),
+                        None if include.system && seen_externals.insert(include.path.as_str()) => {
+                            module.external_imports.push(ExternalImport {
+                                path: include.path.clone(),
+                                kind: classify(&include.path),
+                            });
+                        }
+                        // Generated or missing header, or already recorded.
+                        None => {}
+                    }
+                }
+            }
+            model.modules.push(module);
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// `.c` → C; C++ sources and headers → C++; `.h` → C++ only when the
+/// project has C++ sources.
+fn language_for(path: &Path, has_cpp: bool) -> Option<Language> {
+    if has_extension(path, C_SOURCES) || (has_extension(path, &["h"]) && !has_cpp) {
+        Some(tree_sitter_c::language())
+    } else if has_extension(path, CPP_SOURCES) || has_extension(path, HEADERS) {
+        Some(tree_sitter_cpp::language())
+    } else {
+        None
+    }
+}
+
+/// Everything extracted from a single file.
+struct ParsedFile {
+    path: PathBuf,
+    is_header: bool,
+    symbols: Vec<Symbol>,
+    includes: Vec<Include>,
+}
+
+/// `#include "path"` or `#include <path>`.
+struct Include {
+    path: String,
+    /// Angle-bracket form.
+    system: bool,
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        is_header: has_extension(path, HEADERS),
+        symbols: Vec::new(),
+        includes: Vec::new(),
+    };
+    let scope = Scope {
+        prefix: String::new(),
+        internal: false,
+        is_header: file.is_header,
+    };
+    collect(root, &scope, src, &mut file);
+    file
+}
+
+/// Where a declaration sits: its `ns::` prefix, and whether it has internal
+/// linkage (anonymous namespace) regardless of `static`.
+struct Scope {
+    prefix: String,
+    internal: bool,
+    is_header: bool,
+}
+
+/// Walk a translation unit, namespace body or preprocessor block.
+fn collect(node: Node<'_>, scope: &Scope, src: &[u8], file: &mut ParsedFile) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "preproc_include" => {
+                if let Some(path) = child.child_by_field_name("path") {
+                    let raw = text(path, src);
+                    file.includes.push(Include {
+                        path: raw.trim_matches(['"', '<', '>']).to_string(),
+                        system: raw.starts_with('<'),
+                    });
+                }
+            }
+            "preproc_if" | "preproc_ifdef" | "preproc_else" | "preproc_elif"
+            | "preproc_elifdef" | "declaration_list" => collect(child, scope, src, file),
+            "linkage_specification" => {
+                if let Some(body) = child.child_by_field_name("body") {
+                    if body.kind() == "declaration_list" {
+                        collect(body, scope, src, file);
+                    } else {
+                        item(body, child, scope, src, &mut file.symbols);
+                    }
+                }
+            }
+            "namespace_definition" => {
+                let name = child.child_by_field_name("name").map(|n| text(n, src));
+                if let Some(name) = name {
+                    file.symbols.push(Symbol {
+                        name: format!("{}{name}", scope.prefix),
+                        kind: SymbolKind::Module,
+                        visibility: visibility(scope, false, scope.is_header),
+                        line: line(child),
+                        cell: None,
+                        doc: doc_comment(child, src),
+                        signature: Some(format!("namespace {name}")),
+                        members: Vec::new(),
+                    });
+                }
+                let inner = Scope {
+                    prefix: match name {
+                        Some(name) => format!("{}{name}::", scope.prefix),
+                        None => scope.prefix.clone(),
+                    },
+                    internal: scope.internal || name.is_none(),
+                    is_header: scope.is_header,
+                };
+                if let Some(body) = child.child_by_field_name("body") {
+                    collect(body, &inner, src, file);
+                }
+            }
+            "template_declaration" => {
+                let mut c = child.walk();
+                for templated in child.named_children(&mut c) {
+                    item(templated, child, scope, src, &mut file.symbols);
+                }
+            }
+            _ => item(child, child, scope, src, &mut file.symbols),
+        }
+    }
+}
+
+/// Extract a symbol from a top-level (or namespace-level) declaration.
+/// `anchor` is the node that carries the doc comment and starts the
+/// signature — the `template<...>` wrapper when there is one.
+fn item(node: Node<'_>, anchor: Node<'_>, scope: &Scope, src: &[u8], out: &mut Vec<Symbol>) {
+    let symbol = |name: &str, kind: SymbolKind, exported: bool, signature: String| Symbol {
+        name: format!("{}{name}", scope.prefix),
+        kind,
+        visibility: visibility(scope, is_static(node, src), exported),
+        line: line(anchor),
+        cell: None,
+        doc: doc_comment(anchor, src),
+        signature: Some(signature),
+        members: Vec::new(),
+    };
+
+    match node.kind() {
+        "function_definition" => {
+            let Some((name, qualified)) = node
+                .child_by_field_name("declarator")
+                .and_then(|d| declarator_name(d, src))
+            else {
+                return;
+            };
+            // `void Widget::draw() {}` defines a member declared elsewhere.
+            if qualified {
+                return;
+            }
+            let signature = header(anchor, node.child_by_field_name("body"), src);
+            out.push(symbol(name, SymbolKind::Function, true, signature));
+        }
+        "declaration" => {
+            let declarator = node.child_by_field_name("declarator");
+            if let Some(d) = declarator.filter(|d| is_function_declarator(*d)) {
+                let Some((name, false)) = declarator_name(d, src) else {
+                    return;
+                };
+                let signature = header(anchor, None, src);
+                out.push(symbol(
+                    name,
+                    SymbolKind::Function,
+                    true,
+                    signature.trim_end_matches(';').trim_end().to_string(),
+                ));
+            } else if let Some(ty) = node.child_by_field_name("type") {
+                // `struct point { ... } origin;`
+                item(ty, anchor, scope, src, out);
+            }
+        }
+        "struct_specifier" | "class_specifier" | "union_specifier" | "enum_specifier" => {
+            let (Some(name), Some(body)) = (
+                node.child_by_field_name("name"),
+                node.child_by_field_name("body"),
+            ) else {
+                return;
+            };
+            let kind = type_kind(node.kind());
+            let signature = header(anchor, Some(body), src);
+            out.push(symbol(text(name, src), kind, false, signature));
+        }
+        "type_definition" => {
+            let Some(name) = node
+                .child_by_field_name("declarator")
+                .and_then(|d| declarator_name(d, src))
+                .map(|(name, _)| name)
+            else {
+                return;
+            };
+            let ty = node.child_by_field_name("type");
+            // `typedef struct { ... } point;` is how C names a struct.
+            let (kind, signature) = match ty.filter(|t| t.child_by_field_name("body").is_some()) {
+                Some(ty) if ty.kind() != "type_identifier" => (
+                    type_kind(ty.kind()),
+                    format!(
+                        "typedef {} {name}",
+                        header(ty, ty.child_by_field_name("body"), src)
+                    ),
+                ),
+                _ => (
+                    SymbolKind::TypeAlias,
+                    header(anchor, None, src)
+                        .trim_end_matches(';')
+                        .trim_end()
+                        .to_string(),
+                ),
+            };
+            out.push(symbol(name, kind, false, signature));
+        }
+        "alias_declaration" => {
+            let Some(name) = node.child_by_field_name("name") else {
+                return;
+            };
+            let signature = header(anchor, None, src);
+            out.push(symbol(
+                text(name, src),
+                SymbolKind::TypeAlias,
+                false,
+                signature.trim_end_matches(';').trim_end().to_string(),
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn type_kind(specifier: &str) -> SymbolKind {
+    match specifier {
+        "enum_specifier" => SymbolKind::Enum,
+        _ => SymbolKind::Struct,
+    }
+}
+
+/// Names declared in a header, and functions without `static`, are visible
+/// to other translation units; everything in an anonymous namespace or
+/// marked `static` is private, as are types defined in a source file.
+fn visibility(scope: &Scope, is_static: bool, exported: bool) -> Visibility {
+    if scope.internal || is_static || !(scope.is_header || exported) {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    }
+}
+
+fn is_static(node: Node<'_>, src: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .any(|c| c.kind() == "storage_class_specifier" && text(c, src) == "static")
+}
+
+/// `true` when `declarator` (possibly behind pointers / references)
+/// declares a function.
+fn is_function_declarator(declarator: Node<'_>) -> bool {
+    let mut node = Some(declarator);
+    while let Some(n) = node {
+        if n.kind() == "function_declarator" {
+            return true;
+        }
+        node = n.child_by_field_name("declarator");
+    }
+    false
+}
+
+/// The declared name inside a (possibly nested) declarator, and whether it
+/// is qualified (`Widget::draw`).
+fn declarator_name<'a>(declarator: Node<'_>, src: &'a [u8]) -> Option<(&'a str, bool)> {
+    let mut node = declarator;
+    loop {
+        match node.kind() {
+            "identifier" | "field_identifier" | "type_identifier" | "destructor_name"
+            | "operator_name" => return Some((text(node, src), false)),
+            "qualified_identifier" => return Some((text(node, src), true)),
+            _ => {
+                node = node
+                    .child_by_field_name("declarator")
+                    .or_else(|| node.named_child(0))?;
+            }
+        }
+    }
+}
+
+/// Doxygen comments (`///`, `//!`, `/** */`, `/*! */`) directly above
+/// `node`.
+fn doc_comment(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut expected_row = node.start_position().row;
+    let mut prev = node.prev_sibling();
+    while let Some(comment) = prev.filter(|p| p.kind() == "comment") {
+        let t = text(comment, src);
+        if comment.end_position().row + 1 != expected_row {
+            break;
+        }
+        if let Some(rest) = t.strip_prefix("///").or_else(|| t.strip_prefix("//!")) {
+            lines.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+        } else if t.starts_with("/**") || t.starts_with("/*!") {
+            let body = clean_block_comment(t).unwrap_or_default();
+            lines.extend(body.lines().rev().map(String::from));
+        } else {
+            break;
+        }
+        expected_row = comment.start_position().row;
+        prev = comment.prev_sibling();
+    }
+    lines.reverse();
+    join_doc(lines)
+}
+
+/// Files that make up one module.
+struct Group {
+    dir: PathBuf,
+    stem: String,
+    header: Option<usize>,
+    sources: Vec<usize>,
+}
+
+impl Group {
+    /// The file that names the module: its (first) source, else its header.
+    fn primary(&self) -> usize {
+        self.sources
+            .first()
+            .copied()
+            .or(self.header)
+            .unwrap_or_default()
+    }
+
+    /// Header first, then sources.
+    fn files(&self) -> impl Iterator<Item = usize> + '_ {
+        self.header.into_iter().chain(self.sources.iter().copied())
+    }
+}
+
+/// Group files by directory + stem, then pair a lone source with a lone
+/// header of the same stem elsewhere in the tree.
+fn pair_files(files: &[ParsedFile]) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+        let stem = file
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        // A second header with the same stem (`foo.h` + `foo.hpp`) gets its
+        // own module.
+        let existing = groups.iter().position(|g| {
+            g.dir == dir && g.stem == stem && !(file.is_header && g.header.is_some())
+        });
+        let group = match existing {
+            Some(g) => &mut groups[g],
+            None => {
+                groups.push(Group {
+                    dir,
+                    stem,
+                    header: None,
+                    sources: Vec::new(),
+                });
+                groups.last_mut().expect("just pushed")
+            }
+        };
+        if file.is_header {
+            group.header = Some(i);
+        } else {
+            group.sources.push(i);
+        }
+    }
+
+    let lone = |headers: bool| {
+        let mut by_stem: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (g, group) in groups.iter().enumerate() {
+            if group.header.is_some() == headers && group.sources.is_empty() == headers {
+                by_stem.entry(group.stem.as_str()).or_default().push(g);
+            }
+        }
+        by_stem
+    };
+    let (lone_headers, lone_sources) = (lone(true), lone(false));
+    let mut moves = Vec::new();
+    for (stem, sources) in &lone_sources {
+        if let (Some([h]), [s]) = (
+            lone_headers.get(stem).map(Vec::as_slice),
+            sources.as_slice(),
+        ) {
+            moves.push((*h, *s));
+        }
+    }
+    for (h, s) in moves {
+        groups[s].header = groups[h].header.take();
+    }
+
+    groups.retain(|g| g.header.is_some() || !g.sources.is_empty());
+    groups
+}
+
+/// Resolve an include to the module containing the included file.
+fn resolve_include(
+    include: &Include,
+    from: &Path,
+    include_dirs: &[PathBuf],
+    headers: &[PathBuf],
+    module_of: &HashMap<PathBuf, usize>,
+) -> Option<usize> {
+    let from_dir = from.parent().unwrap_or(Path::new(""));
+    let local = (!include.system).then(|| from_dir.join(&include.path));
+    let found = local
+        .into_iter()
+        .chain(include_dirs.iter().map(|d| d.join(&include.path)))
+        .find_map(|candidate| module_of.get(&canonical(&candidate)).copied());
+    if found.is_some() || include.system {
+        return found;
+    }
+
+    // No compile database entry: fall back to a unique path-suffix match.
+    let suffix = normalize_path(Path::new(&include.path));
+    let mut matches = headers.iter().filter(|h| h.ends_with(&suffix));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => module_of.get(&canonical(only)).copied(),
+        _ => None,
+    }
+}
+
+/// Key used to compare paths from discovery, `#include` resolution and
+/// `compile_commands.json`, which may be relative or absolute.
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| normalize_path(path))
+}
+
+/// C and POSIX headers, plus the extension-less C++ standard library
+/// headers, are `Std`; everything else is a third-party library.
+fn classify(include: &str) -> ExternalKind {
+    const SYSTEM: &[&str] = &[
+        "assert.h",
+        "complex.h",
+        "ctype.h",
+        "errno.h",
+        "fenv.h",
+        "float.h",
+        "inttypes.h",
+        "iso646.h",
+        "limits.h",
+        "locale.h",
+        "math.h",
+        "setjmp.h",
+        "signal.h",
+        "stdalign.h",
+        "stdarg.h",
+        "stdatomic.h",
+        "stdbool.h",
+        "stddef.h",
+        "stdint.h",
+        "stdio.h",
+        "stdlib.h",
+        "stdnoreturn.h",
+        "string.h",
+        "tgmath.h",
+        "threads.h",
+        "time.h",
+        "uchar.h",
+        "wchar.h",
+        "wctype.h",
+        "dirent.h",
+        "fcntl.h",
+        "pthread.h",
+        "unistd.h",
+    ];
+    if !include.contains('.') || SYSTEM.contains(&include) || include.starts_with("sys/") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
+
+/// Include directories from `compile_commands.json`, per translation unit.
+struct CompileDb {
+    by_file: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Union of every entry's directories, for headers (which have no entry
+    /// of their own).
+    all: Vec<PathBuf>,
+}
+
+impl CompileDb {
+    /// Load the first `compile_commands.json` among `paths`, or from `root`
+    /// or `root/build`.
+    fn locate(paths: &[PathBuf], root: &Path) -> Option<Self> {
+        let path = paths
+            .iter()
+            .find(|p| p.file_name().is_some_and(|n| n == "compile_commands.json"))
+            .cloned()
+            .or_else(|| {
+                [
+                    root.join("compile_commands.json"),
+                    root.join("build/compile_commands.json"),
+                ]
+                .into_iter()
+                .find(|p| p.is_file())
+            })?;
+        let text = std::fs::read_to_string(&path).ok()?;
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&text).ok()?;
+
+        let mut db = CompileDb {
+            by_file: HashMap::new(),
+            all: Vec::new(),
+        };
+        for entry in &entries {
+            let dir = PathBuf::from(entry["directory"].as_str().unwrap_or("."));
+            let Some(file) = entry["file"].as_str() else {
+                continue;
+            };
+            let args: Vec<String> = match entry["arguments"].as_array() {
+                Some(args) => args
+                    .iter()
+                    .filter_map(|a| a.as_str().map(str::to_string))
+                    .collect(),
+                None => entry["command"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect(),
+            };
+            let dirs = include_dirs(&args, &dir);
+            for d in &dirs {
+                if !db.all.contains(d) {
+                    db.all.push(d.clone());
+                }
+            }
+            db.by_file.insert(canonical(&dir.join(file)), dirs);
+        }
+        Some(db)
+    }
+
+    fn dirs_for(&self, file: &Path) -> &[PathBuf] {
+        self.by_file.get(&canonical(file)).unwrap_or(&self.all)
+    }
+}
+
+/// `-Idir`, `-I dir`, `-iquote dir`, `-isystem dir` and MSVC `/Idir`,
+/// relative to the entry's working directory.
+fn include_dirs(args: &[String], cwd: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "-I" | "-iquote" | "-isystem" | "/I" => args.next().map(String::as_str),
+            a => ["-I", "-iquote", "-isystem", "/I"]
+                .iter()
+                .find_map(|flag| a.strip_prefix(flag)),
+        };
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+            dirs.push(normalize_path(&cwd.join(value)));
+        }
+    }
+    dirs
+}
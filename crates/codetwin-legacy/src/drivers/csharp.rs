@@ -0,0 +1,801 @@
+//! C# driver (scanner-based).
+//!
+//! There is no C# grammar for the `tree-sitter` 0.20 line the workspace is
+//! pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer and tracks `namespace` / type scopes by brace depth.
+//!
+//! Namespaces are the unit of organisation in .NET, so each namespace
+//! becomes one [`Module`] collecting the types declared in it across every
+//! file (id `Acme.Billing`; types outside any namespace land in `global`).
+//! `using` directives naming another namespace become [`EdgeKind::Import`]
+//! edges, and every `<ProjectReference>` in a `.csproj` links the root
+//! namespace of the referencing project to that of the referenced one.
+//! Base lists become [`EdgeKind::Extends`] / [`EdgeKind::Implements`] edges
+//! depending on whether the resolved base is an interface.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{
+    EdgeSet, clean_block_comment, collapse_whitespace, has_extension, join_doc, normalize_path,
+    read_source, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// C# driver (detects a `*.sln` or `*.csproj` file).
+#[derive(Default)]
+pub struct CSharpDriver;
+
+impl Driver for CSharpDriver {
+    fn name(&self) -> &'static str {
+        "csharp"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        std::fs::read_dir(project_root).is_ok_and(|entries| {
+            entries
+                .flatten()
+                .any(|e| has_extension(&e.path(), &["sln", "csproj"]) && e.path().is_file())
+        })
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["cs", "csproj"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| has_extension(p, &["cs"]),
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+        let projects = paths
+            .iter()
+            .filter(|p| has_extension(p, &["csproj"]))
+            .map(|p| read_project(p))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Merge namespace blocks from every file into one module each.
+        let mut namespaces: Vec<NamespaceAcc<'_>> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for file in &files {
+            for block in &file.blocks {
+                let i = *index.entry(block.name.clone()).or_insert_with(|| {
+                    namespaces.push(NamespaceAcc {
+                        name: block.name.clone(),
+                        path: file.path.parent().unwrap_or(Path::new("")).to_path_buf(),
+                        blocks: Vec::new(),
+                    });
+                    namespaces.len() - 1
+                });
+                namespaces[i].blocks.push(block);
+            }
+        }
+
+        // Fully-qualified type name → (namespace, is interface).
+        let mut types: HashMap<String, (&str, bool)> = HashMap::new();
+        for ns in &namespaces {
+            for decl in ns.blocks.iter().flat_map(|b| &b.types) {
+                types.insert(
+                    qualify(&ns.name, &decl.symbol.name),
+                    (&ns.name, decl.is_interface),
+                );
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for ns in &namespaces {
+            let id = module_id(&ns.name);
+            let mut external_imports: Vec<ExternalImport> = Vec::new();
+
+            for block in &ns.blocks {
+                for using in &block.usings {
+                    match longest_namespace(&index, &using.path) {
+                        Some(target) => edges.push(&id, &module_id(target), EdgeKind::Import),
+                        None if !external_imports.iter().any(|e| e.path == using.path) => {
+                            external_imports.push(ExternalImport {
+                                path: using.path.clone(),
+                                kind: classify(&using.path),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+
+                for decl in &block.types {
+                    for base in &decl.bases {
+                        let Some((target, is_interface)) =
+                            resolve_type(&types, &ns.name, block, base)
+                        else {
+                            continue;
+                        };
+                        let kind = if is_interface && !decl.is_interface {
+                            EdgeKind::Implements
+                        } else {
+                            EdgeKind::Extends
+                        };
+                        edges.push(&id, &module_id(target), kind);
+                    }
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id),
+                name: ns.name.rsplit('.').next().unwrap_or_default().to_string(),
+                path: ns.path.clone(),
+                symbols: ns
+                    .blocks
+                    .iter()
+                    .flat_map(|b| b.types.iter().map(|d| d.symbol.clone()))
+                    .collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+
+        for (from, to) in project_edges(&projects, &namespaces) {
+            edges.push(&module_id(from), &module_id(to), EdgeKind::Import);
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &["///"],
+    // Preprocessor directives (`#region`, `#if`) are skipped like comments.
+    line_comments: &["//", "#"],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/**"),
+    nested_block_comments: false,
+    quotes: &['"', '\''],
+    triple_quoted_strings: true,
+    verbatim_strings: true,
+    ident_chars: &['@'],
+};
+
+/// Modifiers that may precede a type declaration.
+const MODIFIERS: &[&str] = &[
+    "public",
+    "internal",
+    "protected",
+    "private",
+    "file",
+    "static",
+    "abstract",
+    "sealed",
+    "partial",
+    "readonly",
+    "ref",
+    "unsafe",
+    "new",
+];
+
+/// Everything extracted from a single `.cs` file.
+struct ParsedFile {
+    path: PathBuf,
+    blocks: Vec<NamespaceBlock>,
+}
+
+/// One `namespace` body (or the file's global scope) within a file.
+struct NamespaceBlock {
+    name: String,
+    usings: Vec<Using>,
+    types: Vec<TypeDecl>,
+}
+
+/// `using [static] [Alias =] Some.Namespace;`
+struct Using {
+    path: String,
+    alias: Option<String>,
+    is_static: bool,
+}
+
+/// A type declaration plus the (unresolved) names in its base list.
+struct TypeDecl {
+    symbol: Symbol,
+    is_interface: bool,
+    bases: Vec<String>,
+}
+
+/// Brace scopes the scanner tracks.
+enum Scope {
+    Namespace {
+        block: usize,
+    },
+    Type {
+        name: String,
+        interface: bool,
+        block: usize,
+    },
+    /// Member bodies, initializers, accessors — skipped wholesale.
+    Other,
+}
+
+/// Every namespace block with the same name, across files.
+struct NamespaceAcc<'a> {
+    name: String,
+    path: PathBuf,
+    blocks: Vec<&'a NamespaceBlock>,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let tokens = tokenize(src, &SYNTAX);
+    let mut blocks: Vec<NamespaceBlock> = Vec::new();
+    let mut file_usings: Vec<Using> = Vec::new();
+    // Target of a file-scoped `namespace X;`.
+    let mut file_block: Option<usize> = None;
+    let mut stack: Vec<Scope> = Vec::new();
+
+    // Start of the declaration being read, and doc comments seen since the
+    // last statement boundary.
+    let mut decl_start: Option<usize> = None;
+    let mut docs: Vec<&str> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Scope::Other)) {
+            if token.is("{") {
+                stack.push(Scope::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+
+        let current_block = stack.iter().rev().find_map(|s| match s {
+            Scope::Namespace { block } | Scope::Type { block, .. } => Some(*block),
+            Scope::Other => None,
+        });
+        let outer_type = stack.iter().rev().find_map(|s| match s {
+            Scope::Type {
+                name, interface, ..
+            } => Some((name.as_str(), *interface)),
+            _ => None,
+        });
+
+        match token.text {
+            _ if token.kind == TokenKind::Doc => docs.push(token.text),
+            _ if token.kind != TokenKind::Ident && token.kind != TokenKind::Punct => {}
+            // Attributes.
+            "[" => {
+                i = matching(&tokens, i);
+            }
+            "{" => {
+                stack.push(Scope::Other);
+                (decl_start, docs) = (None, Vec::new());
+            }
+            "}" => {
+                stack.pop();
+                (decl_start, docs) = (None, Vec::new());
+            }
+            ";" => (decl_start, docs) = (None, Vec::new()),
+            "using" if outer_type.is_none() && !tokens.get(i + 1).is_some_and(|t| t.is("(")) => {
+                let end = statement_end(&tokens, i);
+                let using = parse_using(&tokens[i + 1..end]);
+                match current_block.or(file_block) {
+                    Some(block) => blocks[block].usings.extend(using),
+                    None => file_usings.extend(using),
+                }
+                (decl_start, docs) = (None, Vec::new());
+                i = end;
+            }
+            "namespace" if outer_type.is_none() => {
+                let (name, end) = dotted_name(&tokens, i + 1);
+                let parent = current_block
+                    .or(file_block)
+                    .map(|b| blocks[b].name.as_str());
+                let full = match parent {
+                    Some(parent) if !parent.is_empty() => format!("{parent}.{name}"),
+                    _ => name,
+                };
+                blocks.push(NamespaceBlock {
+                    name: full,
+                    usings: Vec::new(),
+                    types: Vec::new(),
+                });
+                let block = blocks.len() - 1;
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Scope::Namespace { block });
+                } else {
+                    file_block = Some(block);
+                }
+                (decl_start, docs) = (None, Vec::new());
+                i = end;
+            }
+            "class" | "interface" | "struct" | "enum" | "record" | "delegate" => {
+                let start = decl_start.unwrap_or(i);
+                match type_declaration(&tokens, start, i, outer_type, &docs, src) {
+                    Some((decl, end)) => {
+                        let block = current_block.or(file_block).unwrap_or_else(|| {
+                            // Types outside any namespace.
+                            blocks.push(NamespaceBlock {
+                                name: String::new(),
+                                usings: Vec::new(),
+                                types: Vec::new(),
+                            });
+                            let block = blocks.len() - 1;
+                            file_block = Some(block);
+                            block
+                        });
+                        let opens_body = tokens.get(end).is_some_and(|t| t.is("{"));
+                        if opens_body {
+                            stack.push(match decl.symbol.kind {
+                                SymbolKind::Enum => Scope::Other,
+                                _ => Scope::Type {
+                                    name: decl.symbol.name.clone(),
+                                    interface: decl.is_interface,
+                                    block,
+                                },
+                            });
+                        }
+                        blocks[block].types.push(decl);
+                        (decl_start, docs) = (None, Vec::new());
+                        i = end;
+                    }
+                    // `where T : class`, `record` used as a name, ...
+                    None => decl_start = decl_start.or(Some(i)),
+                }
+            }
+            _ => decl_start = decl_start.or(Some(i)),
+        }
+        i += 1;
+    }
+
+    // Usings before any namespace apply to every namespace in the file.
+    if !file_usings.is_empty() {
+        if blocks.is_empty() {
+            blocks.push(NamespaceBlock {
+                name: String::new(),
+                usings: Vec::new(),
+                types: Vec::new(),
+            });
+        }
+        for block in &mut blocks {
+            for using in &file_usings {
+                block.usings.push(Using {
+                    path: using.path.clone(),
+                    alias: using.alias.clone(),
+                    is_static: using.is_static,
+                });
+            }
+        }
+    }
+
+    ParsedFile {
+        path: path.to_path_buf(),
+        blocks,
+    }
+}
+
+/// Parse a type declaration whose keyword is at `keyword` and whose
+/// modifiers start at `start`. Returns the declaration and the index of the
+/// `{` / `;` that ends its header.
+fn type_declaration(
+    tokens: &[Token<'_>],
+    start: usize,
+    keyword: usize,
+    outer: Option<(&str, bool)>,
+    docs: &[&str],
+    src: &str,
+) -> Option<(TypeDecl, usize)> {
+    let mut k = keyword;
+    let kind = match tokens[k].text {
+        "interface" => SymbolKind::Trait,
+        "enum" => SymbolKind::Enum,
+        "delegate" => SymbolKind::TypeAlias,
+        "record"
+            if tokens
+                .get(k + 1)
+                .is_some_and(|t| t.is("class") || t.is("struct")) =>
+        {
+            k += 1;
+            SymbolKind::Struct
+        }
+        _ => SymbolKind::Struct,
+    };
+
+    // The header runs to the body (or to `;` for positional records and
+    // delegates), skipping over parameter lists.
+    let mut end = k + 1;
+    while end < tokens.len() && !tokens[end].is("{") && !tokens[end].is(";") {
+        if tokens[end].is("(") {
+            end = matching(tokens, end);
+        }
+        end += 1;
+    }
+
+    let name = if kind == SymbolKind::TypeAlias {
+        // `delegate Task<int> Handler<T>(T value);` — the name is the last
+        // identifier outside `<...>` before the parameter list.
+        let mut depth = 0i32;
+        let mut name = None;
+        for t in &tokens[k + 1..end] {
+            match t.text {
+                "<" => depth += 1,
+                ">" => depth -= 1,
+                "(" => break,
+                _ if depth == 0 && t.is_ident() => name = Some(t.text),
+                _ => {}
+            }
+        }
+        name?
+    } else {
+        let name = tokens.get(k + 1).filter(|t| t.is_ident())?;
+        if MODIFIERS.contains(&name.text) || name.text == "where" {
+            return None;
+        }
+        name.text
+    };
+    let name = name.trim_start_matches('@');
+
+    let modifiers: Vec<&str> = tokens[start..keyword]
+        .iter()
+        .filter(|t| t.is_ident() && MODIFIERS.contains(&t.text))
+        .map(|t| t.text)
+        .collect();
+    let visibility = visibility(&modifiers, outer.map(|(_, interface)| interface));
+
+    let bases = if kind == SymbolKind::TypeAlias {
+        Vec::new()
+    } else {
+        base_list(&tokens[k + 2..end])
+    };
+
+    let header_end = tokens.get(end).map_or(src.len(), |t| t.offset);
+    let symbol = Symbol {
+        name: match outer {
+            Some((outer, _)) => format!("{outer}.{name}"),
+            None => name.to_string(),
+        },
+        kind: kind.clone(),
+        visibility,
+        line: tokens[start].line,
+        cell: None,
+        doc: xml_doc(docs),
+        signature: Some(collapse_whitespace(&src[tokens[start].offset..header_end])),
+        members: Vec::new(),
+    };
+    Some((
+        TypeDecl {
+            symbol,
+            is_interface: kind == SymbolKind::Trait,
+            bases,
+        },
+        end,
+    ))
+}
+
+/// Names after `:` in a type header (up to any `where` clause), generic
+/// arguments stripped.
+fn base_list(header: &[Token<'_>]) -> Vec<String> {
+    let mut bases = Vec::new();
+    let mut i = 0;
+    // Skip generic parameters and a primary constructor.
+    while i < header.len() && !header[i].is(":") {
+        if header[i].is("<") || header[i].is("(") {
+            i = matching(header, i);
+        }
+        i += 1;
+    }
+    let mut current = String::new();
+    i += 1;
+    while i < header.len() && !header[i].is("where") {
+        let t = header[i];
+        if t.is("<") || t.is("(") {
+            i = matching(header, i);
+        } else if t.is(",") {
+            bases.push(std::mem::take(&mut current));
+        } else if t.is_ident() || t.is(".") || t.is(":") {
+            current.push_str(t.text);
+        }
+        i += 1;
+    }
+    bases.push(current);
+    bases
+        .into_iter()
+        .map(|b| b.trim_start_matches("global::").to_string())
+        .filter(|b| !b.is_empty())
+        .collect()
+}
+
+/// `public` / `internal` / `protected` / `private`, falling back to the C#
+/// defaults: interface members are public, other nested types private and
+/// top-level types internal.
+fn visibility(modifiers: &[&str], outer_is_interface: Option<bool>) -> Visibility {
+    if modifiers.contains(&"public") {
+        Visibility::Public
+    } else if modifiers.contains(&"protected") {
+        Visibility::Protected
+    } else if modifiers.contains(&"internal") {
+        Visibility::Internal
+    } else if modifiers.contains(&"private") || modifiers.contains(&"file") {
+        Visibility::Private
+    } else {
+        match outer_is_interface {
+            Some(true) => Visibility::Public,
+            Some(false) => Visibility::Private,
+            None => Visibility::Internal,
+        }
+    }
+}
+
+fn parse_using(tokens: &[Token<'_>]) -> Option<Using> {
+    let mut i = 0;
+    let is_static = tokens.first().is_some_and(|t| t.is("static"));
+    if is_static {
+        i += 1;
+    }
+    let alias = match (tokens.get(i), tokens.get(i + 1)) {
+        (Some(name), Some(eq)) if name.is_ident() && eq.is("=") => {
+            i += 2;
+            Some(name.text.to_string())
+        }
+        _ => None,
+    };
+    let (path, _) = dotted_name(tokens, i);
+    let path = path.trim_start_matches("global::").to_string();
+    (!path.is_empty()).then_some(Using {
+        path,
+        alias,
+        is_static,
+    })
+}
+
+/// `A.B.C` (or `global::A.B`) starting at `start`; returns the name and the
+/// index just past it.
+fn dotted_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        if t.is_ident() || t.is(".") || t.is(":") {
+            name.push_str(t.text.trim_start_matches('@'));
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    (name, i)
+}
+
+/// Index of the `;` ending the statement at `start`.
+fn statement_end(tokens: &[Token<'_>], start: usize) -> usize {
+    tokens[start..]
+        .iter()
+        .position(|t| t.is(";"))
+        .map_or(tokens.len(), |p| start + p)
+}
+
+/// `<summary>` text of an XML doc comment (or all of its text when there is
+/// no summary), with `<see cref="X"/>` references kept as `X`.
+fn xml_doc(docs: &[&str]) -> Option<String> {
+    let body: Vec<String> = docs
+        .iter()
+        .flat_map(|d| {
+            if d.trim_start().starts_with("/*") {
+                clean_block_comment(d.trim())
+                    .map(|b| b.lines().map(String::from).collect())
+                    .unwrap_or_default()
+            } else {
+                d.lines()
+                    .map(|l| {
+                        let l = l.trim();
+                        l.strip_prefix("///").unwrap_or(l).trim().to_string()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        })
+        .collect();
+    let xml = body.join("\n");
+    let xml = match (xml.find("<summary>"), xml.find("</summary>")) {
+        (Some(open), Some(close)) if open < close => &xml[open + "<summary>".len()..close],
+        _ => xml.as_str(),
+    };
+
+    let mut text = String::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            break;
+        };
+        let tag = &rest[open + 1..open + close];
+        if let Some(reference) = ["cref=\"", "name=\"", "langword=\""]
+            .iter()
+            .find_map(|attr| tag.split_once(attr).and_then(|(_, v)| v.split_once('"')))
+        {
+            let reference = reference.0;
+            text.push_str(reference.split_once(':').map_or(reference, |(_, r)| r));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(rest);
+    join_doc(text.lines().map(|l| l.trim().to_string()).collect())
+}
+
+/// Resolve a base-list entry declared in namespace `ns` to the namespace and
+/// interface-ness of the type it names.
+fn resolve_type<'a>(
+    types: &HashMap<String, (&'a str, bool)>,
+    ns: &str,
+    block: &NamespaceBlock,
+    name: &str,
+) -> Option<(&'a str, bool)> {
+    let (first, rest) = match name.split_once('.') {
+        Some((first, rest)) => (first, Some(rest)),
+        None => (name, None),
+    };
+    let with_rest = |base: &str| match rest {
+        Some(rest) => format!("{base}.{rest}"),
+        None => base.to_string(),
+    };
+
+    if let Some(alias) = block
+        .usings
+        .iter()
+        .find(|u| u.alias.as_deref() == Some(first))
+    {
+        return types.get(&with_rest(&alias.path)).copied();
+    }
+    // Enclosing namespaces, innermost first, then imported namespaces.
+    let enclosing = std::iter::successors(Some(ns), |n| Some(n.rsplit_once('.')?.0))
+        .chain((!ns.is_empty()).then_some(""));
+    let imported = block
+        .usings
+        .iter()
+        .filter(|u| u.alias.is_none())
+        .map(|u| u.path.as_str());
+    enclosing
+        .chain(imported)
+        .find_map(|scope| types.get(&with_rest(&qualify(scope, first))))
+        .or_else(|| types.get(name))
+        .copied()
+}
+
+/// Longest prefix of `path` (including itself) that is a namespace in the
+/// model.
+fn longest_namespace<'a>(index: &'a HashMap<String, usize>, path: &str) -> Option<&'a str> {
+    let mut candidate = path;
+    loop {
+        if let Some((name, _)) = index.get_key_value(candidate) {
+            return Some(name);
+        }
+        candidate = &candidate[..candidate.rfind('.')?];
+    }
+}
+
+fn qualify(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}.{name}")
+    }
+}
+
+fn module_id(namespace: &str) -> String {
+    if namespace.is_empty() {
+        "global".to_string()
+    } else {
+        namespace.to_string()
+    }
+}
+
+/// `System.*` and `Microsoft.*` ship with .NET; everything else is a NuGet
+/// package.
+fn classify(using: &str) -> ExternalKind {
+    let root = using.split('.').next().unwrap_or(using);
+    if matches!(root, "System" | "Microsoft") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
+
+/// The parts of a `.csproj` that matter for the solution graph.
+struct Project {
+    path: PathBuf,
+    dir: PathBuf,
+    root_namespace: String,
+    references: Vec<PathBuf>,
+}
+
+fn read_project(path: &Path) -> Result<Project> {
+    let xml = read_source(path)?;
+    let dir = normalize_path(path.parent().unwrap_or(Path::new("")));
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let element = |tag: &str| {
+        let open = format!("<{tag}>");
+        let start = xml.find(&open)? + open.len();
+        let end = start + xml[start..].find('<')?;
+        Some(xml[start..end].trim().to_string())
+    };
+    let root_namespace = element("RootNamespace")
+        .or_else(|| element("AssemblyName"))
+        .unwrap_or(stem);
+
+    let references = xml
+        .split("<ProjectReference")
+        .skip(1)
+        .filter_map(|r| {
+            let value = r.split_once("Include=\"")?.1.split_once('"')?.0;
+            Some(normalize_path(&dir.join(value.replace('\\', "/"))))
+        })
+        .collect();
+
+    Ok(Project {
+        path: normalize_path(path),
+        dir,
+        root_namespace,
+        references,
+    })
+}
+
+/// `(from, to)` namespace pairs for every project reference between two
+/// projects whose sources are in the model.
+fn project_edges<'a>(
+    projects: &[Project],
+    namespaces: &'a [NamespaceAcc<'_>],
+) -> Vec<(&'a str, &'a str)> {
+    // The project owning a namespace is the one whose directory most
+    // closely contains the first file declaring it.
+    let owner = |ns: &NamespaceAcc<'_>| {
+        let dir = normalize_path(&ns.path);
+        projects
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| dir.starts_with(&p.dir))
+            .max_by_key(|(_, p)| p.dir.components().count())
+            .map(|(i, _)| i)
+    };
+    let owners: Vec<Option<usize>> = namespaces.iter().map(owner).collect();
+
+    // A project's representative module: its root namespace when declared,
+    // otherwise its shallowest namespace.
+    let root = |project: usize| -> Option<&'a str> {
+        let owned = namespaces
+            .iter()
+            .zip(&owners)
+            .filter(|(_, o)| **o == Some(project))
+            .map(|(ns, _)| ns.name.as_str());
+        let mut shallowest: Option<&str> = None;
+        for name in owned {
+            if name == projects[project].root_namespace {
+                return Some(name);
+            }
+            let depth = |n: &str| n.matches('.').count();
+            if shallowest.is_none_or(|s| (depth(name), name) < (depth(s), s)) {
+                shallowest = Some(name);
+            }
+        }
+        shallowest
+    };
+
+    let mut edges = Vec::new();
+    for (i, project) in projects.iter().enumerate() {
+        for reference in &project.references {
+            let Some(target) = projects.iter().position(|p| p.path == *reference) else {
+                continue;
+            };
+            if let (Some(from), Some(to)) = (root(i), root(target)) {
+                edges.push((from, to));
+            }
+        }
+    }
+    edges
+}
@@ -0,0 +1,509 @@
+//! Docker Compose / Dockerfile driver.
+//!
+//! Compose files (`compose.yaml`, `docker-compose.yml`, and their
+//! `*.override.yml`-style variants) are read with `serde_yaml`; every
+//! service becomes a [`Module`] carrying a [`Container`] — image, ports,
+//! networks and build context — instead of symbols. Service ids are the
+//! service names, prefixed with the compose file's directory when it is
+//! not the common root (`deploy/api`); a service declared in several
+//! files of one directory is merged, later files overriding the image and
+//! build and adding ports and networks.
+//!
+//! Services built from source take the base image of their Dockerfile's
+//! final stage and its `EXPOSE`d ports when the compose file sets none.
+//! Dockerfiles no service builds are containers of their own, named after
+//! their directory (`Dockerfile.worker` adds `worker`). The directories
+//! under a build context holding discovered files become
+//! [`Container::sources`], tying the container to the modules other
+//! drivers produced for that code.
+//!
+//! `depends_on` and `links` become [`EdgeKind::Uses`] edges between the
+//! services of one compose project. Two services of a project that share a
+//! declared network without either depending on the other can reach each
+//! other, so they get `Uses` edges both ways; the implicit default network
+//! links nothing.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_yaml::Value;
+
+use super::common::{EdgeSet, common_root, normalize_path, scan_each};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, Container, EdgeKind, Module, ModuleId};
+
+/// Docker driver (detects a compose file or a `Dockerfile` at the root).
+#[derive(Default)]
+pub struct DockerDriver;
+
+impl Driver for DockerDriver {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        std::fs::read_dir(project_root).is_ok_and(|entries| {
+            entries.flatten().any(|e| {
+                let path = e.path();
+                (is_compose(&path) || is_dockerfile(&path)) && path.is_file()
+            })
+        })
+    }
+
+    fn claims(&self) -> FileClaims {
+        // Every other file, to find the sources in each build context.
+        FileClaims {
+            names: &[
+                "Dockerfile",
+                "Dockerfile.*",
+                "*.Dockerfile",
+                "compose.yml",
+                "compose.yaml",
+                "compose.*.yml",
+                "compose.*.yaml",
+                "docker-compose.yml",
+                "docker-compose.yaml",
+                "docker-compose.*.yml",
+                "docker-compose.*.yaml",
+            ],
+            reads: &["*"],
+            ..FileClaims::extensions(&[])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let mut composes = scan_each(paths, is_compose, read_compose)?;
+        // The base file of a directory applies before its overrides.
+        composes.sort_by_key(|c| {
+            let name = file_name(&c.path);
+            let base = name.starts_with("compose.y") || name.starts_with("docker-compose.y");
+            (c.path.parent().map(Path::to_path_buf), !base)
+        });
+        let dockerfiles = scan_each(paths, is_dockerfile, |path, source| {
+            Ok(read_dockerfile(path, source))
+        })?;
+        let root = common_root(
+            composes
+                .iter()
+                .map(|c| c.path.as_path())
+                .chain(dockerfiles.iter().map(|d| d.path.as_path())),
+        );
+        let sources_under = |context: &Path| -> Vec<PathBuf> {
+            let mut dirs: Vec<PathBuf> = paths
+                .iter()
+                .map(|p| normalize_path(p))
+                .filter(|p| p.starts_with(context) && !is_docker_file(p))
+                .filter_map(|p| p.parent().map(Path::to_path_buf))
+                .collect();
+            dirs.sort();
+            dirs.dedup();
+            dirs
+        };
+
+        // Services keyed by module id, merged across the compose files of a
+        // directory.
+        let mut services: Vec<(String, &Path, Service)> = Vec::new();
+        for compose in &composes {
+            let dir = normalize_path(compose.path.parent().unwrap_or(Path::new("")));
+            let prefix = relative_dir(&dir, &root);
+            for service in &compose.services {
+                let id = qualify(&prefix, &service.name);
+                match services.iter_mut().find(|(i, _, _)| *i == id) {
+                    Some((_, _, existing)) => existing.merge(service),
+                    None => services.push((id, &compose.path, service.clone())),
+                }
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        let mut built: Vec<&Path> = Vec::new();
+        for (id, path, service) in &services {
+            let prefix = id.rsplit_once('/').map_or("", |(prefix, _)| prefix);
+            for dependency in &service.depends_on {
+                let to = qualify(prefix, dependency);
+                if services.iter().any(|(i, _, _)| *i == to) {
+                    edges.push(id, &to, EdgeKind::Uses);
+                }
+            }
+
+            let dockerfile = service.build.as_ref().and_then(|build| {
+                dockerfiles
+                    .iter()
+                    .find(|d| normalize_path(&d.path) == build.dockerfile)
+            });
+            if let Some(dockerfile) = dockerfile {
+                built.push(&dockerfile.path);
+            }
+            let mut container = Container {
+                image: service.image.clone(),
+                ports: service.ports.clone(),
+                networks: service.networks.clone(),
+                build_context: service.build.as_ref().map(|b| b.context.clone()),
+                sources: service
+                    .build
+                    .as_ref()
+                    .map(|b| sources_under(&b.context))
+                    .unwrap_or_default(),
+            };
+            if let Some(dockerfile) = dockerfile {
+                if container.image.is_none() {
+                    container.image.clone_from(&dockerfile.image);
+                }
+                if container.ports.is_empty() {
+                    container.ports.clone_from(&dockerfile.ports);
+                }
+            }
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: service.name.clone(),
+                path: path.to_path_buf(),
+                symbols: Vec::new(),
+                doc: None,
+                external_imports: Vec::new(),
+                group: None,
+                container: Some(container),
+            });
+        }
+
+        for (i, (a, _, first)) in services.iter().enumerate() {
+            for (b, _, second) in &services[i + 1..] {
+                let prefix = a.rsplit_once('/').map_or("", |(prefix, _)| prefix);
+                let same_project = b.rsplit_once('/').map_or("", |(prefix, _)| prefix) == prefix;
+                let depends = |service: &Service, on: &str| {
+                    service.depends_on.iter().any(|d| qualify(prefix, d) == on)
+                };
+                if same_project
+                    && first.networks.iter().any(|n| second.networks.contains(n))
+                    && !depends(first, b)
+                    && !depends(second, a)
+                {
+                    edges.push(a, b, EdgeKind::Uses);
+                    edges.push(b, a, EdgeKind::Uses);
+                }
+            }
+        }
+
+        for dockerfile in dockerfiles
+            .iter()
+            .filter(|d| !built.contains(&d.path.as_path()))
+        {
+            let context = normalize_path(dockerfile.path.parent().unwrap_or(Path::new("")));
+            let mut id = relative_dir(&context, &root);
+            if id.is_empty() {
+                id = context
+                    .file_name()
+                    .map_or_else(|| "root".to_string(), |n| n.to_string_lossy().into_owned());
+            }
+            if let Some(variant) = dockerfile_variant(&dockerfile.path) {
+                id = format!("{id}/{variant}");
+            }
+            model.modules.push(Module {
+                name: id.rsplit('/').next().unwrap_or(&id).to_string(),
+                id: ModuleId(id),
+                path: dockerfile.path.clone(),
+                symbols: Vec::new(),
+                doc: None,
+                external_imports: Vec::new(),
+                group: None,
+                container: Some(Container {
+                    image: dockerfile.image.clone(),
+                    ports: dockerfile.ports.clone(),
+                    networks: Vec::new(),
+                    sources: sources_under(&context),
+                    build_context: Some(context),
+                }),
+            });
+        }
+        model.edges = edges.into_edges();
+        Ok(model)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// `compose.yaml`, `docker-compose.yml`, `docker-compose.prod.yaml`, ...
+fn is_compose(path: &Path) -> bool {
+    let name = file_name(path);
+    let Some(stem) = name
+        .strip_suffix(".yml")
+        .or_else(|| name.strip_suffix(".yaml"))
+    else {
+        return false;
+    };
+    ["compose", "docker-compose"]
+        .iter()
+        .any(|base| stem == *base || stem.starts_with(&format!("{base}.")))
+}
+
+/// `Dockerfile`, `Dockerfile.worker`, `worker.Dockerfile`.
+fn is_dockerfile(path: &Path) -> bool {
+    dockerfile_variant(path).is_some() || file_name(path) == "dockerfile"
+}
+
+/// `worker` for `Dockerfile.worker` and `worker.Dockerfile`.
+fn dockerfile_variant(path: &Path) -> Option<String> {
+    let name = file_name(path);
+    name.strip_prefix("dockerfile.")
+        .or_else(|| name.strip_suffix(".dockerfile"))
+        .filter(|variant| !variant.is_empty())
+        .map(str::to_string)
+}
+
+/// Files describing the containers rather than their code.
+fn is_docker_file(path: &Path) -> bool {
+    is_compose(path) || is_dockerfile(path) || file_name(path) == ".dockerignore"
+}
+
+/// `dir` relative to `root`, `/`-separated (empty for the root itself).
+fn relative_dir(dir: &Path, root: &Path) -> String {
+    dir.strip_prefix(root)
+        .unwrap_or(dir)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn qualify(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
+
+struct ComposeFile {
+    path: PathBuf,
+    services: Vec<Service>,
+}
+
+#[derive(Clone)]
+struct Service {
+    name: String,
+    image: Option<String>,
+    build: Option<Build>,
+    ports: Vec<String>,
+    networks: Vec<String>,
+    /// Services named in `depends_on` and `links`.
+    depends_on: Vec<String>,
+}
+
+#[derive(Clone)]
+struct Build {
+    context: PathBuf,
+    dockerfile: PathBuf,
+}
+
+impl Service {
+    /// Apply a later compose file's definition of the same service.
+    fn merge(&mut self, other: &Service) {
+        if other.image.is_some() {
+            self.image.clone_from(&other.image);
+        }
+        if other.build.is_some() {
+            self.build.clone_from(&other.build);
+        }
+        for (list, more) in [
+            (&mut self.ports, &other.ports),
+            (&mut self.networks, &other.networks),
+            (&mut self.depends_on, &other.depends_on),
+        ] {
+            for item in more {
+                if !list.contains(item) {
+                    list.push(item.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Read the services of one compose file; a file that is not valid YAML is
+/// logged and declares none.
+fn read_compose(path: &Path, source: &str) -> Result<ComposeFile> {
+    let doc: Value = serde_yaml::from_str(source).unwrap_or_else(|e| {
+        tracing::warn!(path = %path.display(), error = %e, "skipping malformed compose file");
+        Value::Null
+    });
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let services = doc
+        .get("services")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, service)| Some(read_service(dir, name.as_str()?, service)))
+        .collect();
+    Ok(ComposeFile {
+        path: path.to_path_buf(),
+        services,
+    })
+}
+
+fn read_service(dir: &Path, name: &str, service: &Value) -> Service {
+    let build = service.get("build").and_then(|build| {
+        let (context, dockerfile) = match build {
+            Value::String(context) => (context.as_str(), None),
+            Value::Mapping(_) => (
+                build.get("context").and_then(Value::as_str).unwrap_or("."),
+                build.get("dockerfile").and_then(Value::as_str),
+            ),
+            _ => return None,
+        };
+        let context = normalize_path(&dir.join(context));
+        Some(Build {
+            dockerfile: normalize_path(&context.join(dockerfile.unwrap_or("Dockerfile"))),
+            context,
+        })
+    });
+
+    let mut ports: Vec<String> = service
+        .get("ports")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+        .filter_map(port)
+        .collect();
+    ports.extend(
+        service
+            .get("expose")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(scalar),
+    );
+
+    let mut depends_on = names(service.get("depends_on"));
+    for link in names(service.get("links")) {
+        // `db:database` links service `db` under an alias.
+        let target = link.split(':').next().unwrap_or(&link).to_string();
+        if !depends_on.contains(&target) {
+            depends_on.push(target);
+        }
+    }
+
+    Service {
+        name: name.to_string(),
+        image: service.get("image").and_then(scalar),
+        build,
+        ports,
+        networks: names(service.get("networks")),
+        depends_on,
+    }
+}
+
+/// Service names from either list form (`[db]`) or map form
+/// (`{db: {condition: ...}}`).
+fn names(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Sequence(items)) => items.iter().filter_map(scalar).collect(),
+        Some(Value::Mapping(map)) => map.keys().filter_map(scalar).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// A `ports` entry in short (`"8080:80"`) or long (`{target: 80,
+/// published: 8080}`) syntax, rendered in short syntax.
+fn port(value: &Value) -> Option<String> {
+    if !value.is_mapping() {
+        return scalar(value);
+    }
+    let target = value.get("target").and_then(scalar)?;
+    let mut port = match value.get("published").and_then(scalar) {
+        Some(published) => format!("{published}:{target}"),
+        None => target,
+    };
+    if let Some(protocol) = value.get("protocol").and_then(Value::as_str) {
+        port.push_str(&format!("/{protocol}"));
+    }
+    Some(port)
+}
+
+struct Dockerfile {
+    path: PathBuf,
+    /// Base image of the final stage, with stage aliases resolved.
+    image: Option<String>,
+    /// Ports the final stage `EXPOSE`s.
+    ports: Vec<String>,
+}
+
+fn read_dockerfile(path: &Path, source: &str) -> Dockerfile {
+    let mut stages: Vec<(Option<String>, String)> = Vec::new();
+    let mut ports = Vec::new();
+    for instruction in instructions(source) {
+        let mut words = instruction.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = words.filter(|w| !w.starts_with("--")).collect();
+        match keyword.to_ascii_uppercase().as_str() {
+            "FROM" => {
+                let Some(image) = args.first() else {
+                    continue;
+                };
+                // A stage built on an earlier stage runs that stage's image.
+                let image = stages
+                    .iter()
+                    .find(|(alias, _)| {
+                        alias
+                            .as_deref()
+                            .is_some_and(|a| a.eq_ignore_ascii_case(image))
+                    })
+                    .map_or_else(|| image.to_string(), |(_, base)| base.clone());
+                let alias = match args.as_slice() {
+                    [_, keyword, alias] if keyword.eq_ignore_ascii_case("as") => {
+                        Some(alias.to_string())
+                    }
+                    _ => None,
+                };
+                stages.push((alias, image));
+                ports.clear();
+            }
+            "EXPOSE" => ports.extend(args.iter().map(|p| p.to_string())),
+            _ => {}
+        }
+    }
+    Dockerfile {
+        path: path.to_path_buf(),
+        image: stages.pop().map(|(_, image)| image),
+        ports,
+    }
+}
+
+/// Instructions of a Dockerfile, comments dropped and `\` continuations
+/// joined.
+fn instructions(source: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for line in source.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        match line.strip_suffix('\\') {
+            Some(part) => {
+                current.push_str(part);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line);
+                out.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        out.push(current);
+    }
+    out
+}
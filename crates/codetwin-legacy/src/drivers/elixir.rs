@@ -0,0 +1,810 @@
+//! Elixir driver (scanner-based).
+//!
+//! There is no Elixir grammar for the `tree-sitter` 0.20 line the
+//! workspace is pinned to, so this driver runs the shared
+//! [`scanner`](super::scanner) tokenizer (sigils and character literals
+//! blanked out first) and tracks `do`/`fn` ... `end` blocks.
+//!
+//! Every `defmodule`, `defprotocol` and `defimpl` becomes one [`Module`]
+//! whose id is the Elixir module name (`MyApp.Accounts.User`; a `defimpl
+//! Proto, for: Type` is `Proto.Type`, as at runtime). In an umbrella
+//! project, modules under `apps/<app>/` get the app as their
+//! [`Module::group`] (`accounts`).
+//!
+//! Symbols follow OTP conventions: `def`/`defp` (and macros, guards and
+//! delegates) are functions named `name/arity`, public or private;
+//! `defstruct` and `defexception` give the module a struct; protocols and
+//! behaviours (modules declaring `@callback`s) a trait; `@type`/`@typep`/
+//! `@opaque` are type aliases. `alias`, `import`, `use` and `require`
+//! become [`EdgeKind::Import`] edges; `defimpl` and `@behaviour` become
+//! [`EdgeKind::Implements`]. Dependencies outside the model are kept as
+//! [`ExternalImport`]s.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{EdgeSet, collapse_whitespace, has_extension, join_doc, scan_each};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Elixir driver (detects `mix.exs`).
+#[derive(Default)]
+pub struct ElixirDriver;
+
+impl Driver for ElixirDriver {
+    fn name(&self) -> &'static str {
+        "elixir"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("mix.exs").is_file()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            interpreters: &["elixir"],
+            ..FileClaims::extensions(&["ex", "exs"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| {
+                (has_extension(p, &["ex", "exs"]) && !is_project_script(p))
+                    || self.claims().owns_script(p)
+            },
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+
+        let known: HashSet<&str> = files
+            .iter()
+            .flat_map(|f| &f.modules)
+            .map(|m| m.name.as_str())
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for file in &files {
+            let app = umbrella_app(&file.path);
+            for m in &file.modules {
+                let id = m.name.as_str();
+                let mut external_imports: Vec<ExternalImport> = Vec::new();
+                let mut external = |name: &str| {
+                    if !external_imports.iter().any(|e| e.path == name) {
+                        external_imports.push(ExternalImport {
+                            path: name.to_string(),
+                            kind: classify(name),
+                        });
+                    }
+                };
+
+                for dep in &m.deps {
+                    if known.contains(dep.as_str()) {
+                        edges.push(id, dep, EdgeKind::Import);
+                    } else {
+                        external(dep);
+                    }
+                }
+                for behaviour in &m.behaviours {
+                    if known.contains(behaviour.as_str()) {
+                        edges.push(id, behaviour, EdgeKind::Implements);
+                    } else {
+                        external(behaviour);
+                    }
+                }
+                if let Some((protocol, for_type)) = &m.implements {
+                    // The implementing type carries the edge when it is
+                    // ours; impls for built-ins (`for: Integer`) keep it on
+                    // the impl module.
+                    let from = if known.contains(for_type.as_str()) {
+                        for_type.as_str()
+                    } else {
+                        id
+                    };
+                    if known.contains(protocol.as_str()) {
+                        edges.push(from, protocol, EdgeKind::Implements);
+                    } else {
+                        external(protocol);
+                    }
+                }
+
+                model.modules.push(Module {
+                    id: ModuleId(id.to_string()),
+                    name: m.name.clone(),
+                    path: file.path.clone(),
+                    symbols: m.symbols.clone(),
+                    doc: m.doc.clone(),
+                    external_imports,
+                    group: app.clone(),
+                    container: None,
+                });
+            }
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// `mix.exs`, `.formatter.exs` and `config/*.exs` configure the project
+/// rather than being part of it.
+fn is_project_script(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default();
+    name == "mix.exs"
+        || name == ".formatter.exs"
+        || (has_extension(path, &["exs"])
+            && path
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|d| d == "config"))
+}
+
+/// `my_umbrella/apps/accounts/lib/...` → `accounts`, when
+/// `apps/accounts/mix.exs` exists.
+fn umbrella_app(path: &Path) -> Option<String> {
+    let components: Vec<Component<'_>> = path.components().collect();
+    components.windows(2).enumerate().find_map(|(i, pair)| {
+        if pair[0].as_os_str() != "apps" {
+            return None;
+        }
+        let app_dir: PathBuf = components[..i + 2].iter().collect();
+        app_dir
+            .join("mix.exs")
+            .is_file()
+            .then(|| pair[1].as_os_str().to_string_lossy().into_owned())
+    })
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &[],
+    line_comments: &["#"],
+    block_comment: None,
+    doc_block_comment: None,
+    nested_block_comments: false,
+    quotes: &['"', '\''],
+    triple_quoted_strings: true,
+    verbatim_strings: false,
+    // `valid?`, `save!`.
+    ident_chars: &['?', '!'],
+};
+
+/// Everything extracted from a single `.ex` / `.exs` file.
+struct ParsedFile {
+    path: PathBuf,
+    modules: Vec<ElixirModule>,
+}
+
+/// One `defmodule`, `defprotocol` or `defimpl`.
+struct ElixirModule {
+    /// Fully-qualified module name.
+    name: String,
+    parent: Option<usize>,
+    /// Short name → module, from `alias` and nested `defmodule`s.
+    aliases: Vec<(String, String)>,
+    symbols: Vec<Symbol>,
+    doc: Option<String>,
+    /// Modules named by `alias` / `import` / `use` / `require`.
+    deps: Vec<String>,
+    /// Modules named by `@behaviour`.
+    behaviours: Vec<String>,
+    /// `(protocol, type)` of a `defimpl`.
+    implements: Option<(String, String)>,
+}
+
+/// `do`/`fn` ... `end` blocks the scanner tracks.
+enum Block {
+    Module(usize),
+    /// Function bodies, `case`, `quote`, anonymous functions — skipped
+    /// wholesale.
+    Other,
+}
+
+/// Definitions that declare functions, with their visibility.
+const FUNCTION_DEFS: &[(&str, Visibility)] = &[
+    ("def", Visibility::Public),
+    ("defp", Visibility::Private),
+    ("defmacro", Visibility::Public),
+    ("defmacrop", Visibility::Private),
+    ("defguard", Visibility::Public),
+    ("defguardp", Visibility::Private),
+    ("defdelegate", Visibility::Public),
+];
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let masked = mask_literals(src);
+    let src = masked.as_str();
+    let tokens = tokenize(src, &SYNTAX);
+    let mut modules: Vec<ElixirModule> = Vec::new();
+    let mut stack: Vec<Block> = Vec::new();
+    // A module header read but whose `do` has not been reached yet.
+    let mut pending: Option<usize> = None;
+    // `@doc` waiting for the next definition.
+    let mut doc: Option<String> = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let keyword = token.is_ident() && is_keyword(&tokens, i);
+        if matches!(stack.last(), Some(Block::Other)) {
+            if keyword && (token.text == "do" || token.text == "fn") {
+                stack.push(Block::Other);
+            } else if keyword && token.text == "end" {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let current = stack.iter().rev().find_map(|b| match b {
+            Block::Module(m) => Some(*m),
+            Block::Other => None,
+        });
+
+        match token.text {
+            _ if !keyword => {}
+            "do" => stack.push(match pending.take() {
+                Some(m) => Block::Module(m),
+                None => Block::Other,
+            }),
+            "fn" => stack.push(Block::Other),
+            "end" => {
+                stack.pop();
+            }
+            "defmodule" | "defprotocol" => {
+                let (written, end) = module_name(&tokens, i + 1);
+                if written.is_empty() {
+                    i = end;
+                    continue;
+                }
+                let name = match current {
+                    Some(parent) => {
+                        let written = written.strip_prefix("__MODULE__.").unwrap_or(&written);
+                        let parent = &mut modules[parent];
+                        // `defmodule Inner` is aliased in its parent.
+                        let first = written.split('.').next().unwrap_or_default();
+                        let alias = (first.to_string(), format!("{}.{first}", parent.name));
+                        parent.aliases.push(alias);
+                        format!("{}.{written}", parent.name)
+                    }
+                    None => written,
+                };
+                let mut module = new_module(name, current);
+                if token.text == "defprotocol" {
+                    module.symbols.push(Symbol {
+                        name: last_segment(&module.name).to_string(),
+                        kind: SymbolKind::Trait,
+                        visibility: Visibility::Public,
+                        line: token.line,
+                        cell: None,
+                        doc: None,
+                        signature: Some(collapse_whitespace(
+                            &src[token.offset..tokens[end - 1].offset + tokens[end - 1].text.len()],
+                        )),
+                        members: Vec::new(),
+                    });
+                }
+                modules.push(module);
+                pending = Some(modules.len() - 1);
+                doc = None;
+                i = end;
+                continue;
+            }
+            "defimpl" => {
+                let (written, mut end) = module_name(&tokens, i + 1);
+                let protocol = resolve(&written, current, &modules);
+                let mut for_type = current.map(|m| modules[m].name.clone());
+                while let Some(t) = tokens
+                    .get(end)
+                    .filter(|t| !(t.is("do") && is_keyword(&tokens, end)))
+                {
+                    if t.is("for") && tokens.get(end + 1).is_some_and(|c| c.is(":")) {
+                        // `for: [A, B]` is named after its first type.
+                        let start =
+                            end + 2 + usize::from(tokens.get(end + 2).is_some_and(|t| t.is("[")));
+                        let (written, after) = module_name(&tokens, start);
+                        if !written.is_empty() {
+                            for_type = Some(resolve(&written, current, &modules));
+                        }
+                        end = after.max(end + 1);
+                        continue;
+                    }
+                    end += 1;
+                }
+                let for_type = for_type.unwrap_or_default();
+                let mut module = new_module(format!("{protocol}.{}", for_type), current);
+                module.implements = Some((protocol, for_type));
+                modules.push(module);
+                pending = Some(modules.len() - 1);
+                doc = None;
+                i = end;
+                continue;
+            }
+            "alias" | "import" | "use" | "require" if current.is_some() => {
+                let m = current.unwrap_or_default();
+                let (names, end) = dependency_names(&tokens, i + 1);
+                for (written, as_alias) in names {
+                    let full = resolve(&written, current, &modules);
+                    if token.text == "alias" {
+                        let short = as_alias.unwrap_or_else(|| last_segment(&full).to_string());
+                        modules[m].aliases.push((short, full.clone()));
+                    }
+                    if !modules[m].deps.contains(&full) {
+                        modules[m].deps.push(full);
+                    }
+                }
+                i = end;
+                continue;
+            }
+            "defstruct" | "defexception" if current.is_some() => {
+                let m = current.unwrap_or_default();
+                let name = last_segment(&modules[m].name).to_string();
+                modules[m].symbols.push(Symbol {
+                    name,
+                    kind: SymbolKind::Struct,
+                    visibility: Visibility::Public,
+                    line: token.line,
+                    cell: None,
+                    doc: None,
+                    signature: Some(collapse_whitespace(
+                        &src[token.offset..line_end(src, token.offset)],
+                    )),
+                    members: Vec::new(),
+                });
+            }
+            text if current.is_some() && FUNCTION_DEFS.iter().any(|(d, _)| *d == text) => {
+                let m = current.unwrap_or_default();
+                let visibility = FUNCTION_DEFS
+                    .iter()
+                    .find(|(d, _)| *d == text)
+                    .map_or(Visibility::Public, |(_, v)| *v);
+                if let Some((name, arity, end)) = function_head(&tokens, i + 1) {
+                    let name = format!("{name}/{arity}");
+                    let symbol_doc = doc.take();
+                    if !modules[m]
+                        .symbols
+                        .iter()
+                        .any(|s| s.name == name && s.kind == SymbolKind::Function)
+                    {
+                        modules[m].symbols.push(Symbol {
+                            name,
+                            kind: SymbolKind::Function,
+                            visibility,
+                            line: token.line,
+                            cell: None,
+                            doc: symbol_doc,
+                            signature: Some(collapse_whitespace(
+                                &src[token.offset
+                                    ..tokens[end - 1].offset + tokens[end - 1].text.len()],
+                            )),
+                            members: Vec::new(),
+                        });
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        // Module attributes: `@moduledoc`, `@doc`, `@behaviour`, `@callback`,
+        // `@type`.
+        if token.is("@")
+            && let (Some(m), Some(attr)) = (current, tokens.get(i + 1))
+        {
+            match attr.text {
+                "moduledoc" => modules[m].doc = string_value(tokens.get(i + 2)),
+                "doc" => doc = string_value(tokens.get(i + 2)),
+                "behaviour" | "behavior" => {
+                    let (written, _) = module_name(&tokens, i + 2);
+                    if !written.is_empty() {
+                        let full = resolve(&written, current, &modules);
+                        modules[m].behaviours.push(full);
+                    }
+                }
+                "callback" | "macrocallback" => {
+                    if let Some((name, arity, end)) = function_head(&tokens, i + 2) {
+                        let module = &mut modules[m];
+                        if !module.symbols.iter().any(|s| s.kind == SymbolKind::Trait) {
+                            module.symbols.insert(
+                                0,
+                                Symbol {
+                                    name: last_segment(&module.name).to_string(),
+                                    kind: SymbolKind::Trait,
+                                    visibility: Visibility::Public,
+                                    line: token.line,
+                                    cell: None,
+                                    doc: None,
+                                    signature: None,
+                                    members: Vec::new(),
+                                },
+                            );
+                        }
+                        module.symbols.push(Symbol {
+                            name: format!("{name}/{arity}"),
+                            kind: SymbolKind::Function,
+                            visibility: Visibility::Public,
+                            line: token.line,
+                            cell: None,
+                            doc: doc.take(),
+                            signature: Some(collapse_whitespace(
+                                &src[token.offset
+                                    ..tokens[end - 1].offset + tokens[end - 1].text.len()],
+                            )),
+                            members: Vec::new(),
+                        });
+                        i = end;
+                        continue;
+                    }
+                }
+                "type" | "typep" | "opaque" => {
+                    if let Some(name) = tokens.get(i + 2).filter(|t| t.is_ident()) {
+                        modules[m].symbols.push(Symbol {
+                            name: name.text.to_string(),
+                            kind: SymbolKind::TypeAlias,
+                            visibility: if attr.text == "typep" {
+                                Visibility::Private
+                            } else {
+                                Visibility::Public
+                            },
+                            line: token.line,
+                            cell: None,
+                            doc: None,
+                            signature: Some(collapse_whitespace(
+                                &src[token.offset..line_end(src, token.offset)],
+                            )),
+                            members: Vec::new(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+
+    ParsedFile {
+        path: path.to_path_buf(),
+        modules,
+    }
+}
+
+fn new_module(name: String, parent: Option<usize>) -> ElixirModule {
+    ElixirModule {
+        name,
+        parent,
+        aliases: Vec::new(),
+        symbols: Vec::new(),
+        doc: None,
+        deps: Vec::new(),
+        behaviours: Vec::new(),
+        implements: None,
+    }
+}
+
+/// `do`, `end` and friends used as keywords rather than as atoms
+/// (`:end`), keyword-list keys (`do:`) or fields (`range.end`).
+fn is_keyword(tokens: &[Token<'_>], i: usize) -> bool {
+    let token = tokens[i];
+    let adjacent = |t: &Token<'_>, offset: usize| t.offset == offset;
+    let after_colon_or_dot = i
+        .checked_sub(1)
+        .map(|p| tokens[p])
+        .is_some_and(|p| (p.is(":") || p.is(".")) && adjacent(&token, p.offset + 1));
+    let before_colon = tokens
+        .get(i + 1)
+        .is_some_and(|n| n.is(":") && adjacent(n, token.offset + token.text.len()));
+    !after_colon_or_dot && !before_colon
+}
+
+/// A module name starting at `start`: `Foo.Bar`, `__MODULE__.Sub` or an
+/// Erlang module (`:crypto`). Returns the name and the index just past it.
+fn module_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    if tokens.get(start).is_some_and(|t| t.is(":"))
+        && let Some(atom) = tokens.get(start + 1).filter(|t| t.is_ident())
+    {
+        return (format!(":{}", atom.text), start + 2);
+    }
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        let expects_segment = name.is_empty() || name.ends_with('.');
+        if expects_segment && t.is_ident() && is_module_segment(t.text) {
+            name.push_str(t.text);
+        } else if !expects_segment && t.is(".") {
+            name.push('.');
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if name.ends_with('.') {
+        name.pop();
+        i -= 1;
+    }
+    (name, i)
+}
+
+fn is_module_segment(text: &str) -> bool {
+    text == "__MODULE__" || text.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Names after `alias` / `import` / `use` / `require`, with an `as:`
+/// alias: `Foo.Bar`, `Foo.{Bar, Baz}`, `Foo.Bar, as: Qux`. Returns them and
+/// the index just past the names.
+fn dependency_names(tokens: &[Token<'_>], start: usize) -> (Vec<(String, Option<String>)>, usize) {
+    let (name, mut end) = module_name(tokens, start);
+    if name.is_empty() {
+        return (Vec::new(), end);
+    }
+    if tokens.get(end).is_some_and(|t| t.is(".")) && tokens.get(end + 1).is_some_and(|t| t.is("{"))
+    {
+        let close = matching(tokens, end + 1);
+        let mut names = Vec::new();
+        let mut j = end + 2;
+        while j < close {
+            let (member, after) = module_name(tokens, j);
+            if !member.is_empty() {
+                names.push((format!("{name}.{member}"), None));
+            }
+            j = after.max(j + 1);
+        }
+        return (names, close + 1);
+    }
+    let mut alias = None;
+    if tokens.get(end).is_some_and(|t| t.is(","))
+        && tokens.get(end + 1).is_some_and(|t| t.is("as"))
+        && tokens.get(end + 2).is_some_and(|t| t.is(":"))
+    {
+        let (short, after) = module_name(tokens, end + 3);
+        if !short.is_empty() {
+            alias = Some(short);
+            end = after;
+        }
+    }
+    (vec![(name, alias)], end)
+}
+
+/// Name and arity of a function head starting at `start` (`name(a, b)`,
+/// `name` or `name(a) when guard`), and the index just past its argument
+/// list.
+fn function_head<'a>(tokens: &[Token<'a>], start: usize) -> Option<(&'a str, usize, usize)> {
+    let name = tokens.get(start).filter(|t| t.is_ident())?;
+    let open = start + 1;
+    let adjacent = tokens
+        .get(open)
+        .is_some_and(|t| t.is("(") && t.offset == name.offset + name.text.len());
+    if !adjacent {
+        return Some((name.text, 0, open));
+    }
+    // An unclosed list (`def f(` at the end of a half-saved file) runs to
+    // the end of the file.
+    let close = matching(tokens, open).max(open + 1);
+    let mut arity = 0;
+    let mut depth = 0usize;
+    for t in &tokens[open + 1..close] {
+        match t.text {
+            "(" | "[" | "{" if t.kind == TokenKind::Punct => depth += 1,
+            ")" | "]" | "}" if t.kind == TokenKind::Punct => depth = depth.saturating_sub(1),
+            "," if t.kind == TokenKind::Punct && depth == 0 => arity += 1,
+            _ => {}
+        }
+    }
+    if close > open + 1 {
+        arity += 1;
+    }
+    Some((name.text, arity, (close + 1).min(tokens.len())))
+}
+
+/// Expand aliases and `__MODULE__` in a module name written inside
+/// `current`, looking through enclosing modules' aliases.
+fn resolve(name: &str, current: Option<usize>, modules: &[ElixirModule]) -> String {
+    let (first, rest) = match name.split_once('.') {
+        Some((first, rest)) => (first, Some(rest)),
+        None => (name, None),
+    };
+    let mut scope = current;
+    let mut base = None;
+    if first == "__MODULE__" {
+        base = current.map(|m| modules[m].name.clone());
+    }
+    while base.is_none()
+        && let Some(m) = scope
+    {
+        base = modules[m]
+            .aliases
+            .iter()
+            .rev()
+            .find(|(short, _)| short == first)
+            .map(|(_, full)| full.clone());
+        scope = modules[m].parent;
+    }
+    let base = base.unwrap_or_else(|| first.to_string());
+    match rest {
+        Some(rest) => format!("{base}.{rest}"),
+        None => base,
+    }
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Text of a string literal (`"..."` or `"""..."""`) with common
+/// indentation removed; `None` for anything else (`@doc false`).
+fn string_value(token: Option<&Token<'_>>) -> Option<String> {
+    let token = token.filter(|t| t.kind == TokenKind::Str)?;
+    let body = token
+        .text
+        .strip_prefix("\"\"\"")
+        .and_then(|s| s.strip_suffix("\"\"\""))
+        .unwrap_or_else(|| token.text.trim_matches(|c| c == '"' || c == '\''));
+    join_doc(body.lines().map(|l| l.trim().to_string()).collect())
+}
+
+/// Byte offset of the end of the line containing `offset`.
+fn line_end(src: &str, offset: usize) -> usize {
+    src[offset..].find('\n').map_or(src.len(), |p| offset + p)
+}
+
+/// Elixir and OTP modules shipped with the runtime; anything else is a Hex
+/// dependency.
+const STDLIB: &[&str] = &[
+    "Access",
+    "Agent",
+    "Application",
+    "Atom",
+    "Base",
+    "Bitwise",
+    "Calendar",
+    "Code",
+    "Config",
+    "Date",
+    "DateTime",
+    "DynamicSupervisor",
+    "EEx",
+    "Enum",
+    "Exception",
+    "ExUnit",
+    "File",
+    "Float",
+    "GenServer",
+    "IEx",
+    "IO",
+    "Inspect",
+    "Integer",
+    "Kernel",
+    "Keyword",
+    "List",
+    "Logger",
+    "Macro",
+    "Map",
+    "MapSet",
+    "Mix",
+    "Module",
+    "NaiveDateTime",
+    "Node",
+    "Path",
+    "Port",
+    "Process",
+    "Protocol",
+    "Range",
+    "Record",
+    "Regex",
+    "Registry",
+    "Stream",
+    "String",
+    "Supervisor",
+    "System",
+    "Task",
+    "Time",
+    "Tuple",
+    "URI",
+];
+
+/// Runtime modules (and every Erlang module, `:crypto`) are standard
+/// library.
+fn classify(name: &str) -> ExternalKind {
+    let first = name.split('.').next().unwrap_or(name);
+    if name.starts_with(':') || STDLIB.contains(&first) {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
+
+/// Blank out (keeping newlines and byte offsets) sigil bodies
+/// (`~r/.../`, `~w(...)`) and character literals (`?(`), whose contents
+/// the tokenizer would otherwise read as code. String sigils (`~S"""`)
+/// only lose their prefix, leaving an ordinary string.
+fn mask_literals(src: &str) -> String {
+    let b = src.as_bytes();
+    let mut out = b.to_vec();
+    let mut blank = |from: usize, to: usize| {
+        for byte in &mut out[from..to.min(b.len())] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let is_ident = |c: u8| c.is_ascii_alphanumeric() || c == b'_';
+
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b'#' => {
+                i = b[i..]
+                    .iter()
+                    .position(|&c| c == b'\n')
+                    .map_or(b.len(), |p| i + p);
+            }
+            b'"' if b[i..].starts_with(b"\"\"\"") => {
+                i = src[i + 3..]
+                    .find("\"\"\"")
+                    .map_or(b.len(), |p| i + 3 + p + 3);
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < b.len() && b[i] != quote {
+                    i += if b[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'?' if i + 1 < b.len() && (i == 0 || !is_ident(b[i - 1])) => {
+                let len = if b[i + 1] == b'\\' { 3 } else { 2 };
+                blank(i, i + len);
+                i += len;
+            }
+            b'~' if b.get(i + 1).is_some_and(|c| c.is_ascii_alphabetic()) => {
+                let mut j = i + 1;
+                while b.get(j).is_some_and(|c| c.is_ascii_alphabetic()) {
+                    j += 1;
+                }
+                let Some(&open) = b.get(j) else {
+                    break;
+                };
+                if open == b'"' || open == b'\'' {
+                    blank(i, j);
+                    i = j;
+                    continue;
+                }
+                let close = match open {
+                    b'(' => b')',
+                    b'[' => b']',
+                    b'{' => b'}',
+                    b'<' => b'>',
+                    other => other,
+                };
+                let mut depth = 0usize;
+                let mut k = j + 1;
+                while k < b.len() {
+                    if b[k] == b'\\' {
+                        k += 2;
+                        continue;
+                    }
+                    if b[k] == close && depth == 0 {
+                        break;
+                    }
+                    if close != open {
+                        if b[k] == open {
+                            depth += 1;
+                        } else if b[k] == close {
+                            depth -= 1;
+                        }
+                    }
+                    k += 1;
+                }
+                let end = (k + 1).min(b.len());
+                blank(i, end);
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
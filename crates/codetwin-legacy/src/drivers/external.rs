@@ -0,0 +1,172 @@
+//! Out-of-process drivers declared as `[[plugins.drivers]]` in
+//! `codetwin.toml`.
+//!
+//! The protocol is one process per parse: codetwin runs the configured
+//! command in the project root, writes a JSON request to its stdin and
+//! reads a [`CodeModel`] as JSON from its stdout.
+//!
+//! ```json
+//! { "protocol": 1, "driver": "dsl", "project_root": "/abs/path", "files": ["src/a.dsl"] }
+//! ```
+//!
+//! `files` are the discovered files the driver's extensions claim, as the
+//! pipeline found them (relative to the project root for relative
+//! `source_dirs`). A non-zero exit status fails the run with the plugin's
+//! stderr; so does output that does not deserialize into the IR — symbol
+//! kinds, visibilities and edge kinds must name variants of the IR enums
+//! (`"struct"`, `"public"`, `"uses"`) — or whose edges point at modules it
+//! does not declare. Anything the plugin logs to
+//! stderr on success is passed on at debug level.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+use super::{Driver, FileClaims};
+use crate::config::PluginDriverSpec;
+use crate::ir::CodeModel;
+
+/// Version of the request format, sent as `"protocol"`.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// A driver backed by an external executable.
+///
+/// [`Driver`] hands out `&'static` names and claims, so constructing one
+/// leaks its name and extensions. Build them once per process: `gen
+/// --watch` keeps one registry for the whole session (see
+/// [`pipeline::run_with`](crate::pipeline::run_with)).
+pub struct ExternalDriver {
+    name: &'static str,
+    command: PathBuf,
+    args: Vec<String>,
+    extensions: &'static [&'static str],
+    detect: Vec<String>,
+    project_root: PathBuf,
+}
+
+impl ExternalDriver {
+    /// Driver for `spec`, run in `project_root`.
+    pub fn new(spec: &PluginDriverSpec, project_root: &Path) -> Self {
+        let command = if spec.command.components().count() > 1 {
+            project_root.join(&spec.command)
+        } else {
+            spec.command.clone()
+        };
+        let extensions: Vec<&'static str> = spec
+            .extensions
+            .iter()
+            .map(|e| &*Box::leak(e.trim_start_matches('.').into()))
+            .collect();
+        Self {
+            name: Box::leak(spec.name.as_str().into()),
+            command,
+            args: spec.args.clone(),
+            extensions: Box::leak(extensions.into_boxed_slice()),
+            detect: spec.detect.clone(),
+            project_root: project_root.to_path_buf(),
+        }
+    }
+}
+
+impl Driver for ExternalDriver {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        if self.detect.is_empty() {
+            project_root == self.project_root
+        } else {
+            self.detect.iter().any(|m| project_root.join(m).exists())
+        }
+    }
+
+    fn claims(&self) -> FileClaims {
+        if self.extensions.is_empty() {
+            FileClaims::ALL
+        } else {
+            FileClaims::extensions(self.extensions)
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let claims = self.claims();
+        let files: Vec<&PathBuf> = paths.iter().filter(|p| claims.wants(p)).collect();
+        let request = serde_json::json!({
+            "protocol": PROTOCOL_VERSION,
+            "driver": self.name,
+            "project_root": self.project_root,
+            "files": files,
+        });
+        let plugin = || format!("driver plugin `{}`", self.name);
+
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .current_dir(&self.project_root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to run {} ({})", plugin(), self.command.display()))?;
+        // Write from another thread so a plugin streaming its output before
+        // reading all of its input cannot deadlock on a full pipe.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let (output, written) = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || serde_json::to_writer(&mut stdin, &request));
+            let output = child.wait_with_output();
+            let written = writer.join().expect("request writer panicked");
+            (output, written)
+        });
+        let output = output.with_context(|| format!("failed to wait for {}", plugin()))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            bail!("{} failed ({}): {}", plugin(), output.status, stderr.trim());
+        }
+        // A plugin may exit without reading its input; that is its choice.
+        if let Err(err) = written {
+            tracing::debug!(driver = self.name, %err, "plugin did not read its request");
+        }
+        if !stderr.trim().is_empty() {
+            tracing::debug!(driver = self.name, stderr = %stderr.trim(), "plugin output");
+        }
+
+        let mut model: CodeModel = serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("{} returned an invalid code model", plugin()))?;
+        validate(&model).with_context(|| format!("{} returned an invalid code model", plugin()))?;
+        if model.language.is_empty() {
+            model.language = self.name.to_string();
+        }
+        Ok(model)
+    }
+}
+
+/// Checks serde cannot: module ids are non-empty and unique, and every
+/// edge connects declared modules.
+fn validate(model: &CodeModel) -> Result<()> {
+    let mut ids = HashSet::new();
+    for module in &model.modules {
+        if module.id.0.is_empty() {
+            bail!("module `{}` has an empty id", module.name);
+        }
+        if !ids.insert(module.id.0.as_str()) {
+            bail!("module id `{}` is declared twice", module.id.0);
+        }
+    }
+    for edge in &model.edges {
+        for end in [&edge.from, &edge.to] {
+            if !ids.contains(end.0.as_str()) {
+                bail!(
+                    "{:?} edge {} -> {} names undeclared module `{}`",
+                    edge.kind,
+                    edge.from.0,
+                    edge.to.0,
+                    end.0
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,11 +1,31 @@
-//! Go driver — NEW_ROADMAP Phase 5.b.
+//! Go driver (tree-sitter-based) — NEW_ROADMAP Phase 5.b.
+//!
+//! Go's unit of encapsulation is the package, so every directory of `.go`
+//! files becomes one [`Module`] whose id is the package import path: the
+//! `module` line of the nearest `go.mod` joined with the directory's path
+//! below it. `_test.go` files are skipped.
+//!
+//! Methods are recorded as `Receiver.Method` functions next to their
+//! receiver type. Imports of other packages in the model become
+//! [`EdgeKind::Import`] edges; standard-library and third-party imports are
+//! kept as [`ExternalImport`]s. A struct or interface embedding a type of
+//! another package in the model (`store.Base`) adds an [`EdgeKind::Extends`]
+//! edge to it.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use tree_sitter::Node;
 
-use super::Driver;
-use crate::ir::CodeModel;
+use super::common::{
+    EdgeSet, has_extension, header, join_doc, line, normalize_path, parse_each, text,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
 
 /// Go driver (detects `go.mod`).
 #[derive(Default)]
@@ -20,9 +40,437 @@ impl Driver for GoDriver {
         project_root.join("go.mod").is_file()
     }
 
-    fn parse(&self, _paths: &[PathBuf]) -> Result<CodeModel> {
-        // TODO(Phase 5.b): use `tree-sitter-go` to extract packages,
-        //                  structs, interfaces, functions, and methods.
-        Ok(CodeModel::new(self.name()))
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["go"])
     }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_each(
+            paths,
+            |p| {
+                let is_test = p.to_string_lossy().ends_with("_test.go");
+                (has_extension(p, &["go"]) && !is_test).then(tree_sitter_go::language)
+            },
+            |path, source, tree| Ok(extract_file(path, source.as_bytes(), tree.root_node())),
+        )?;
+
+        // Group files into packages by directory.
+        let mut packages: BTreeMap<PathBuf, Vec<ParsedFile>> = BTreeMap::new();
+        for file in files {
+            let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+            packages.entry(dir).or_default().push(file);
+        }
+
+        let mut go_mods = GoModCache::default();
+        let ids: Vec<String> = packages
+            .keys()
+            .map(|dir| import_path(dir, go_mods.nearest(dir)))
+            .collect();
+        let known: HashSet<&str> = ids.iter().map(String::as_str).collect();
+        // Package names, which qualify the types of an imported package.
+        let names: Vec<String> = packages
+            .values()
+            .zip(&ids)
+            .map(|(files, id)| {
+                files
+                    .iter()
+                    .find_map(|f| f.package.clone())
+                    .unwrap_or_else(|| id.rsplit('/').next().unwrap_or(id).to_string())
+            })
+            .collect();
+        let name_of: HashMap<&str, &str> = ids
+            .iter()
+            .map(String::as_str)
+            .zip(names.iter().map(String::as_str))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (((dir, files), id), name) in packages.into_iter().zip(&ids).zip(&names) {
+            let module_path = go_mods.nearest(&dir).map(|m| m.path.clone());
+            let mut module = Module {
+                id: ModuleId(id.clone()),
+                name: name.clone(),
+                path: dir,
+                symbols: Vec::new(),
+                doc: files.iter().find_map(|f| f.doc.clone()),
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            };
+
+            let mut seen = HashSet::new();
+            for file in files {
+                module.symbols.extend(file.symbols);
+                // Qualifier → imported package, for the embedded types.
+                let mut qualifiers: HashMap<&str, &str> = file
+                    .imports
+                    .iter()
+                    .filter_map(|i| Some((*name_of.get(i.as_str())?, i.as_str())))
+                    .collect();
+                qualifiers.extend(
+                    file.aliases
+                        .iter()
+                        .map(|(alias, path)| (alias.as_str(), path.as_str())),
+                );
+                for qualifier in &file.embeds {
+                    if let Some(target) = qualifiers.get(qualifier.as_str())
+                        && known.contains(target)
+                    {
+                        edges.push(id, target, EdgeKind::Extends);
+                    }
+                }
+                for import in file.imports {
+                    if known.contains(import.as_str()) {
+                        edges.push(id, &import, EdgeKind::Import);
+                    } else if is_within(&import, module_path.as_deref()) {
+                        // Same Go module but not part of this run (excluded,
+                        // or only test files): nothing to link to.
+                    } else if seen.insert(import.clone()) {
+                        let kind = classify(&import);
+                        module
+                            .external_imports
+                            .push(ExternalImport { path: import, kind });
+                    }
+                }
+            }
+            model.modules.push(module);
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// Everything extracted from a single `.go` file.
+struct ParsedFile {
+    path: PathBuf,
+    package: Option<String>,
+    /// Package doc comment (the comment attached to the `package` clause).
+    doc: Option<String>,
+    symbols: Vec<Symbol>,
+    imports: Vec<String>,
+    /// Renamed imports (`name "path"`), name → path.
+    aliases: HashMap<String, String>,
+    /// Package qualifiers of the embedded types of structs and interfaces
+    /// (`store` for `store.Base`).
+    embeds: Vec<String>,
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        package: None,
+        doc: None,
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        aliases: HashMap::new(),
+        embeds: Vec::new(),
+    };
+
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "package_clause" => {
+                file.package = node.named_child(0).map(|n| text(n, src).to_string());
+                file.doc = doc_comment(node, src);
+            }
+            "import_declaration" => collect_imports(node, src, &mut file),
+            "function_declaration" => file.symbols.extend(function(node, None, src)),
+            "method_declaration" => {
+                let receiver = node
+                    .child_by_field_name("receiver")
+                    .and_then(|r| receiver_type(r, src));
+                file.symbols.extend(function(node, receiver, src));
+            }
+            "type_declaration" => {
+                type_declaration(node, src, &mut file.symbols);
+                collect_embeds(node, src, &mut file.embeds);
+            }
+            "const_declaration" | "var_declaration" => {
+                value_declaration(node, src, &mut file.symbols)
+            }
+            _ => {}
+        }
+    }
+
+    file
+}
+
+fn collect_imports(node: Node<'_>, src: &[u8], file: &mut ParsedFile) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "import_spec" => {
+                let Some(path) = child.child_by_field_name("path") else {
+                    continue;
+                };
+                let path = text(path, src).trim_matches(['"', '`']).to_string();
+                if let Some(name) = child
+                    .child_by_field_name("name")
+                    .filter(|n| n.kind() == "package_identifier")
+                {
+                    file.aliases
+                        .insert(text(name, src).to_string(), path.clone());
+                }
+                file.imports.push(path);
+            }
+            "import_spec_list" => collect_imports(child, src, file),
+            _ => {}
+        }
+    }
+}
+
+/// Qualifiers of the types embedded in the structs and interfaces of a
+/// `type` declaration: fields without a name, and interface elements that
+/// name a single type.
+fn collect_embeds(node: Node<'_>, src: &[u8], out: &mut Vec<String>) {
+    let mut cursor = node.walk();
+    for spec in node.named_children(&mut cursor) {
+        let Some(ty) = spec.child_by_field_name("type") else {
+            continue;
+        };
+        let elements: Vec<Node<'_>> = match ty.kind() {
+            "struct_type" => {
+                let mut c = ty.walk();
+                ty.named_child(0)
+                    .map(|list| {
+                        list.named_children(&mut c)
+                            .filter(|f| f.child_by_field_name("name").is_none())
+                            .filter_map(|f| f.child_by_field_name("type"))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }
+            "interface_type" => {
+                let mut c = ty.walk();
+                ty.named_children(&mut c)
+                    .filter(|e| e.kind() != "method_spec")
+                    .collect()
+            }
+            _ => continue,
+        };
+        out.extend(elements.into_iter().filter_map(|e| qualifier(e, src)));
+    }
+}
+
+/// `store` for `store.Base`, `*store.Base`, `store.List[T]`; `None` for a
+/// type of the same package or a type constraint (`~int | store.ID`).
+fn qualifier(node: Node<'_>, src: &[u8]) -> Option<String> {
+    match node.kind() {
+        "qualified_type" => Some(text(node.child_by_field_name("package")?, src).to_string()),
+        "generic_type" => qualifier(node.child_by_field_name("type")?, src),
+        "pointer_type" | "constraint_elem" | "constraint_term" if node.named_child_count() == 1 => {
+            qualifier(node.named_child(0)?, src)
+        }
+        _ => None,
+    }
+}
+
+/// Functions, and methods named `Receiver.Method`.
+fn function(node: Node<'_>, receiver: Option<String>, src: &[u8]) -> Option<Symbol> {
+    let name = text(node.child_by_field_name("name")?, src);
+    Some(Symbol {
+        name: match receiver {
+            Some(receiver) => format!("{receiver}.{name}"),
+            None => name.to_string(),
+        },
+        kind: SymbolKind::Function,
+        visibility: visibility(name),
+        line: line(node),
+        cell: None,
+        doc: doc_comment(node, src),
+        signature: Some(header(node, node.child_by_field_name("body"), src)),
+        members: Vec::new(),
+    })
+}
+
+/// `(s *Server)` / `(l List[T])` → `Server` / `List`.
+fn receiver_type(params: Node<'_>, src: &[u8]) -> Option<String> {
+    let param = params.named_child(0)?;
+    let ty = text(param.child_by_field_name("type")?, src);
+    let ty = ty.trim_start_matches('*');
+    Some(ty.split('[').next().unwrap_or(ty).trim().to_string())
+}
+
+fn type_declaration(node: Node<'_>, src: &[u8], out: &mut Vec<Symbol>) {
+    // A doc comment on a grouped `type ( ... )` applies to every spec that
+    // has none of its own.
+    let group_doc = doc_comment(node, src);
+    let mut cursor = node.walk();
+    for spec in node.named_children(&mut cursor) {
+        let Some(name) = spec.child_by_field_name("name") else {
+            continue;
+        };
+        let name = text(name, src);
+        let ty = spec.child_by_field_name("type");
+        let (kind, signature) = match (spec.kind(), ty.map(|t| t.kind())) {
+            ("type_spec", Some("struct_type")) => {
+                (SymbolKind::Struct, format!("type {name} struct"))
+            }
+            ("type_spec", Some("interface_type")) => {
+                (SymbolKind::Trait, format!("type {name} interface"))
+            }
+            _ => (
+                SymbolKind::TypeAlias,
+                format!("type {}", header(spec, None, src)),
+            ),
+        };
+        out.push(Symbol {
+            name: name.to_string(),
+            kind,
+            visibility: visibility(name),
+            line: line(spec),
+            cell: None,
+            doc: doc_comment(spec, src).or_else(|| group_doc.clone()),
+            signature: Some(signature),
+            members: Vec::new(),
+        });
+    }
+}
+
+/// Package-level `const` and `var` specs, one [`SymbolKind::Constant`] per
+/// declared name; blank (`_`) names are skipped.
+fn value_declaration(node: Node<'_>, src: &[u8], out: &mut Vec<Symbol>) {
+    let group_doc = doc_comment(node, src);
+    let mut cursor = node.walk();
+    for spec in node.named_children(&mut cursor) {
+        let keyword = match spec.kind() {
+            "const_spec" => "const",
+            "var_spec" => "var",
+            _ => continue,
+        };
+        let doc = doc_comment(spec, src).or_else(|| group_doc.clone());
+        let ty = spec.child_by_field_name("type").map(|t| text(t, src));
+        let mut c = spec.walk();
+        for name in spec.children_by_field_name("name", &mut c) {
+            let name = text(name, src);
+            if name == "," || name == "_" {
+                continue;
+            }
+            out.push(Symbol {
+                name: name.to_string(),
+                kind: SymbolKind::Constant,
+                visibility: visibility(name),
+                line: line(spec),
+                cell: None,
+                doc: doc.clone(),
+                signature: Some(match ty {
+                    Some(ty) => format!("{keyword} {name} {ty}"),
+                    None => format!("{keyword} {name}"),
+                }),
+                members: Vec::new(),
+            });
+        }
+    }
+}
+
+/// Exported identifiers start with an upper-case letter.
+fn visibility(name: &str) -> Visibility {
+    if name.chars().next().is_some_and(char::is_uppercase) {
+        Visibility::Public
+    } else {
+        Visibility::Private
+    }
+}
+
+/// `//` lines directly above `node` (no blank line in between).
+fn doc_comment(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut expected_row = node.start_position().row;
+    let mut prev = node.prev_sibling();
+    while let Some(comment) = prev.filter(|p| p.kind() == "comment") {
+        if comment.end_position().row + 1 != expected_row {
+            break;
+        }
+        let t = text(comment, src);
+        let body = match t.strip_prefix("//") {
+            Some(rest) => rest.strip_prefix(' ').unwrap_or(rest).to_string(),
+            None => t
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .trim()
+                .to_string(),
+        };
+        lines.push(body);
+        expected_row = comment.start_position().row;
+        prev = comment.prev_sibling();
+    }
+    lines.reverse();
+    join_doc(lines)
+}
+
+/// Standard-library import paths have no dot in their first element
+/// (`fmt`, `net/http`); everything else is a third-party module.
+fn classify(import: &str) -> ExternalKind {
+    let first = import.split('/').next().unwrap_or(import);
+    if first.contains('.') {
+        ExternalKind::ThirdParty
+    } else {
+        ExternalKind::Std
+    }
+}
+
+fn is_within(import: &str, module_path: Option<&str>) -> bool {
+    module_path.is_some_and(|m| import == m || import.starts_with(&format!("{m}/")))
+}
+
+/// The `module` declared by a `go.mod` and the directory it lives in.
+#[derive(Clone)]
+struct GoMod {
+    dir: PathBuf,
+    path: String,
+}
+
+/// `<module path>/<dir below go.mod>`, or the bare directory when no
+/// `go.mod` was found.
+fn import_path(dir: &Path, go_mod: Option<&GoMod>) -> String {
+    let rel = |p: &Path| -> String {
+        p.components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    match go_mod {
+        Some(m) => {
+            let below = rel(dir.strip_prefix(&m.dir).unwrap_or(dir));
+            if below.is_empty() {
+                m.path.clone()
+            } else {
+                format!("{}/{below}", m.path)
+            }
+        }
+        None => rel(dir),
+    }
+}
+
+/// Per-directory memo of the nearest `go.mod`.
+#[derive(Default)]
+struct GoModCache {
+    by_dir: HashMap<PathBuf, Option<GoMod>>,
+}
+
+impl GoModCache {
+    fn nearest(&mut self, dir: &Path) -> Option<&GoMod> {
+        if !self.by_dir.contains_key(dir) {
+            let found = dir.ancestors().find_map(|a| {
+                let text = std::fs::read_to_string(a.join("go.mod")).ok()?;
+                Some(GoMod {
+                    dir: a.to_path_buf(),
+                    path: module_directive(&text)?,
+                })
+            });
+            self.by_dir.insert(dir.to_path_buf(), found);
+        }
+        self.by_dir.get(dir).and_then(Option::as_ref)
+    }
+}
+
+/// The path from a `module example.com/foo` line.
+fn module_directive(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|l| {
+        let rest = l.trim().strip_prefix("module")?;
+        let rest = rest.split("//").next().unwrap_or(rest).trim();
+        (!rest.is_empty()).then(|| rest.trim_matches('"').to_string())
+    })
 }
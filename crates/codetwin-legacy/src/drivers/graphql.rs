@@ -0,0 +1,538 @@
+//! GraphQL schema (SDL) driver.
+//!
+//! `.graphql`, `.gql` and `.graphqls` files are parsed with
+//! `graphql-parser`; files holding operations rather than type system
+//! definitions (client queries next to the schema) are skipped. Each
+//! schema file becomes a [`Module`], so the subgraphs of a federated
+//! gateway (`subgraphs/accounts/schema.graphql`) stay separate modules;
+//! a file named `schema.*` is named after its directory.
+//!
+//! Object and input types become [`SymbolKind::Struct`]s, interfaces
+//! [`SymbolKind::Trait`]s, unions and enums [`SymbolKind::Enum`]s and
+//! scalars [`SymbolKind::TypeAlias`]es, with their fields or enum values
+//! as [`members`](Symbol::members) (directives and defaults as
+//! modifiers). The fields of the root operation types (`Query`,
+//! `Mutation`, `Subscription`, or whatever a `schema { ... }` block names)
+//! become functions named `Query.user` instead, their arguments in the
+//! signature. `extend type` declarations live in the module of the file
+//! declaring them.
+//!
+//! `implements` clauses become [`EdgeKind::Implements`] edges, and field,
+//! argument and union member types [`EdgeKind::Uses`] edges, to the module
+//! defining the named type — the file's own definition first, then the
+//! first file defining it. Extensions add an [`EdgeKind::Extends`] edge to
+//! the module defining the extended type.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use graphql_parser::schema::{
+    Definition, Directive, Document, EnumValue, Field, InputValue, Type, TypeDefinition,
+    TypeExtension,
+};
+
+use super::common::{EdgeSet, common_root, has_extension, relative_module_id, scan_each};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Member, Module, ModuleId, Symbol, SymbolKind, Visibility};
+
+const EXTENSIONS: &[&str] = &["graphql", "gql", "graphqls"];
+
+/// Config files of GraphQL tooling that mark a schema-first project.
+const CONFIG_FILES: &[&str] = &[
+    ".graphqlrc",
+    ".graphqlrc.yml",
+    ".graphqlrc.yaml",
+    ".graphqlrc.json",
+    "graphql.config.yml",
+    "graphql.config.yaml",
+    "graphql.config.json",
+    "supergraph.yaml",
+];
+
+/// Conventional directories holding a project's schema files.
+const SCHEMA_DIRS: &[&str] = &["schema", "graphql"];
+
+/// GraphQL driver (detects tooling config files, or schema files at the
+/// root or in `schema/` or `graphql/`).
+#[derive(Default)]
+pub struct GraphqlDriver;
+
+impl Driver for GraphqlDriver {
+    fn name(&self) -> &'static str {
+        "graphql"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        CONFIG_FILES.iter().any(|f| project_root.join(f).is_file())
+            || has_schema(project_root)
+            || !self.source_dirs(project_root).is_empty()
+    }
+
+    fn detects_loose_sources(&self, project_root: &Path) -> bool {
+        !CONFIG_FILES.iter().any(|f| project_root.join(f).is_file())
+    }
+
+    fn source_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        SCHEMA_DIRS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|dir| has_schema(&project_root.join(dir)))
+            .collect()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(EXTENSIONS)
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files: Vec<SchemaFile> = scan_each(paths, |p| has_extension(p, EXTENSIONS), read_file)?
+            .into_iter()
+            .flatten()
+            .collect();
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        let ids: Vec<String> = files
+            .iter()
+            .map(|f| relative_module_id(&f.path, &root))
+            .collect();
+
+        let mut roots: Vec<&str> = files
+            .iter()
+            .flat_map(|f| &f.roots)
+            .map(String::as_str)
+            .collect();
+        if roots.is_empty() {
+            roots = vec!["Query", "Mutation", "Subscription"];
+        }
+
+        // Type name → first module defining it.
+        let mut defined: HashMap<&str, &str> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            for decl in file.decls.iter().filter(|d| !d.extension) {
+                defined.entry(&decl.name).or_insert(id);
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let resolve = |name: &str| -> Option<&str> {
+                if file.decls.iter().any(|d| !d.extension && d.name == name) {
+                    Some(id)
+                } else {
+                    defined.get(name).copied()
+                }
+            };
+
+            let mut symbols = Vec::new();
+            for decl in &file.decls {
+                for interface in &decl.implements {
+                    if let Some(to) = resolve(interface) {
+                        edges.push(id, to, EdgeKind::Implements);
+                    }
+                }
+                for used in &decl.uses {
+                    if let Some(to) = resolve(used) {
+                        edges.push(id, to, EdgeKind::Uses);
+                    }
+                }
+                if decl.extension
+                    && let Some(to) = defined.get(decl.name.as_str())
+                {
+                    edges.push(id, to, EdgeKind::Extends);
+                }
+
+                if roots.contains(&decl.name.as_str()) && decl.kind == SymbolKind::Struct {
+                    symbols.extend(decl.operations.iter().map(|op| Symbol {
+                        name: format!("{}.{}", decl.name, op.name),
+                        kind: SymbolKind::Function,
+                        visibility: Visibility::Public,
+                        line: op.line,
+                        cell: None,
+                        doc: op.doc.clone(),
+                        signature: Some(op.signature.clone()),
+                        members: Vec::new(),
+                    }));
+                } else {
+                    symbols.push(Symbol {
+                        name: decl.name.clone(),
+                        kind: decl.kind.clone(),
+                        visibility: Visibility::Public,
+                        line: decl.line,
+                        cell: None,
+                        doc: decl.doc.clone(),
+                        signature: Some(decl.signature.clone()),
+                        members: decl.members.clone(),
+                    });
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: module_name(&file.path),
+                path: file.path.clone(),
+                symbols,
+                doc: None,
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+        Ok(model)
+    }
+}
+
+fn has_schema(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| has_extension(&e.path(), EXTENSIONS) && e.path().is_file())
+    })
+}
+
+/// File stem, or the directory name for the customary `schema.graphql`.
+fn module_name(path: &Path) -> String {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+    let dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|d| d.to_string_lossy().into_owned());
+    match (stem, dir) {
+        (Some(stem), Some(dir)) if stem == "schema" => dir,
+        (stem, _) => stem.unwrap_or_default(),
+    }
+}
+
+struct SchemaFile {
+    path: PathBuf,
+    /// Root operation types named by `schema { ... }` blocks.
+    roots: Vec<String>,
+    decls: Vec<Decl>,
+}
+
+/// A type definition or extension.
+struct Decl {
+    name: String,
+    kind: SymbolKind,
+    extension: bool,
+    line: u32,
+    doc: Option<String>,
+    signature: String,
+    members: Vec<Member>,
+    /// Fields, as functions should this be a root operation type.
+    operations: Vec<Operation>,
+    implements: Vec<String>,
+    /// Named types of fields, arguments and union members.
+    uses: Vec<String>,
+}
+
+struct Operation {
+    name: String,
+    line: u32,
+    doc: Option<String>,
+    signature: String,
+}
+
+/// `None` for executable documents (queries, fragments), which may share
+/// the schema's extensions. A file that parses as neither is logged and
+/// declares nothing.
+fn read_file(path: &Path, source: &str) -> Result<Option<SchemaFile>> {
+    let mut file = SchemaFile {
+        path: path.to_path_buf(),
+        roots: Vec::new(),
+        decls: Vec::new(),
+    };
+    let document: Document<'_, String> = match graphql_parser::parse_schema(source) {
+        Ok(document) => document,
+        Err(_) if graphql_parser::parse_query::<String>(source).is_ok() => return Ok(None),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "skipping malformed schema file");
+            return Ok(Some(file));
+        }
+    };
+
+    for definition in &document.definitions {
+        match definition {
+            Definition::SchemaDefinition(schema) => file.roots.extend(
+                [&schema.query, &schema.mutation, &schema.subscription]
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+            ),
+            Definition::TypeDefinition(definition) => file.decls.push(type_definition(definition)),
+            Definition::TypeExtension(extension) => file.decls.push(type_extension(extension)),
+            Definition::DirectiveDefinition(_) => {}
+        }
+    }
+    Ok(Some(file))
+}
+
+fn type_definition(definition: &TypeDefinition<'_, String>) -> Decl {
+    match definition {
+        TypeDefinition::Scalar(t) => Decl::new(
+            &t.name,
+            SymbolKind::TypeAlias,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!("scalar {}", t.name),
+        ),
+        TypeDefinition::Object(t) => Decl::new(
+            &t.name,
+            SymbolKind::Struct,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!("type {}{}", t.name, implements(&t.implements_interfaces)),
+        )
+        .with_fields(&t.fields)
+        .implementing(&t.implements_interfaces),
+        TypeDefinition::Interface(t) => Decl::new(
+            &t.name,
+            SymbolKind::Trait,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!(
+                "interface {}{}",
+                t.name,
+                implements(&t.implements_interfaces)
+            ),
+        )
+        .with_fields(&t.fields)
+        .implementing(&t.implements_interfaces),
+        TypeDefinition::Union(t) => Decl::new(
+            &t.name,
+            SymbolKind::Enum,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!("union {} = {}", t.name, t.types.join(" | ")),
+        )
+        .with_members(&t.types),
+        TypeDefinition::Enum(t) => Decl::new(
+            &t.name,
+            SymbolKind::Enum,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!("enum {}", t.name),
+        )
+        .with_values(&t.values),
+        TypeDefinition::InputObject(t) => Decl::new(
+            &t.name,
+            SymbolKind::Struct,
+            false,
+            t.position.line,
+            t.description.clone(),
+            format!("input {}", t.name),
+        )
+        .with_inputs(&t.fields),
+    }
+}
+
+fn type_extension(extension: &TypeExtension<'_, String>) -> Decl {
+    match extension {
+        TypeExtension::Scalar(t) => Decl::new(
+            &t.name,
+            SymbolKind::TypeAlias,
+            true,
+            t.position.line,
+            None,
+            format!("extend scalar {}", t.name),
+        ),
+        TypeExtension::Object(t) => Decl::new(
+            &t.name,
+            SymbolKind::Struct,
+            true,
+            t.position.line,
+            None,
+            format!(
+                "extend type {}{}",
+                t.name,
+                implements(&t.implements_interfaces)
+            ),
+        )
+        .with_fields(&t.fields)
+        .implementing(&t.implements_interfaces),
+        TypeExtension::Interface(t) => Decl::new(
+            &t.name,
+            SymbolKind::Trait,
+            true,
+            t.position.line,
+            None,
+            format!(
+                "extend interface {}{}",
+                t.name,
+                implements(&t.implements_interfaces)
+            ),
+        )
+        .with_fields(&t.fields)
+        .implementing(&t.implements_interfaces),
+        TypeExtension::Union(t) => Decl::new(
+            &t.name,
+            SymbolKind::Enum,
+            true,
+            t.position.line,
+            None,
+            format!("extend union {} = {}", t.name, t.types.join(" | ")),
+        )
+        .with_members(&t.types),
+        TypeExtension::Enum(t) => Decl::new(
+            &t.name,
+            SymbolKind::Enum,
+            true,
+            t.position.line,
+            None,
+            format!("extend enum {}", t.name),
+        )
+        .with_values(&t.values),
+        TypeExtension::InputObject(t) => Decl::new(
+            &t.name,
+            SymbolKind::Struct,
+            true,
+            t.position.line,
+            None,
+            format!("extend input {}", t.name),
+        )
+        .with_inputs(&t.fields),
+    }
+}
+
+/// ` implements A & B`, or nothing.
+fn implements(interfaces: &[String]) -> String {
+    if interfaces.is_empty() {
+        String::new()
+    } else {
+        format!(" implements {}", interfaces.join(" & "))
+    }
+}
+
+/// The named type under any list / non-null wrappers.
+fn named_type<'a>(ty: &'a Type<'_, String>) -> &'a str {
+    match ty {
+        Type::NamedType(name) => name,
+        Type::ListType(inner) | Type::NonNullType(inner) => named_type(inner),
+    }
+}
+
+fn directives(directives: &[Directive<'_, String>]) -> Vec<String> {
+    directives.iter().map(ToString::to_string).collect()
+}
+
+/// `id: ID! = 1`, as written in an argument list.
+fn input_value(input: &InputValue<'_, String>) -> String {
+    match &input.default_value {
+        Some(default) => format!("{}: {} = {default}", input.name, input.value_type),
+        None => format!("{}: {}", input.name, input.value_type),
+    }
+}
+
+impl Decl {
+    fn new(
+        name: &str,
+        kind: SymbolKind,
+        extension: bool,
+        line: usize,
+        doc: Option<String>,
+        signature: String,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            extension,
+            line: line as u32,
+            doc,
+            signature,
+            members: Vec::new(),
+            operations: Vec::new(),
+            implements: Vec::new(),
+            uses: Vec::new(),
+        }
+    }
+
+    fn implementing(mut self, interfaces: &[String]) -> Self {
+        self.implements = interfaces.to_vec();
+        self
+    }
+
+    fn with_fields(mut self, fields: &[Field<'_, String>]) -> Self {
+        for field in fields {
+            self.uses.push(named_type(&field.field_type).to_string());
+            self.uses.extend(
+                field
+                    .arguments
+                    .iter()
+                    .map(|a| named_type(&a.value_type).to_string()),
+            );
+            self.members.push(Member {
+                name: field.name.clone(),
+                ty: Some(field.field_type.to_string()),
+                modifiers: directives(&field.directives),
+                doc: field.description.clone(),
+            });
+            let arguments: Vec<String> = field.arguments.iter().map(input_value).collect();
+            self.operations.push(Operation {
+                name: field.name.clone(),
+                line: field.position.line as u32,
+                doc: field.description.clone(),
+                signature: if arguments.is_empty() {
+                    format!("{}: {}", field.name, field.field_type)
+                } else {
+                    format!(
+                        "{}({}): {}",
+                        field.name,
+                        arguments.join(", "),
+                        field.field_type
+                    )
+                },
+            });
+        }
+        self
+    }
+
+    fn with_inputs(mut self, fields: &[InputValue<'_, String>]) -> Self {
+        for field in fields {
+            self.uses.push(named_type(&field.value_type).to_string());
+            let mut modifiers = directives(&field.directives);
+            if let Some(default) = &field.default_value {
+                modifiers.insert(0, format!("= {default}"));
+            }
+            self.members.push(Member {
+                name: field.name.clone(),
+                ty: Some(field.value_type.to_string()),
+                modifiers,
+                doc: field.description.clone(),
+            });
+        }
+        self
+    }
+
+    fn with_members(mut self, types: &[String]) -> Self {
+        self.uses.extend(types.iter().cloned());
+        self.members = types
+            .iter()
+            .map(|t| Member {
+                name: t.clone(),
+                ty: None,
+                modifiers: Vec::new(),
+                doc: None,
+            })
+            .collect();
+        self
+    }
+
+    fn with_values(mut self, values: &[EnumValue<'_, String>]) -> Self {
+        self.members = values
+            .iter()
+            .map(|v| Member {
+                name: v.name.clone(),
+                ty: None,
+                modifiers: directives(&v.directives),
+                doc: v.description.clone(),
+            })
+            .collect();
+        self
+    }
+}
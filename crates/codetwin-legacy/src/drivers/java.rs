@@ -0,0 +1,496 @@
+//! Java driver (tree-sitter-based).
+//!
+//! Every `.java` compilation unit becomes one [`Module`] whose id is the
+//! declared package joined with the file stem (`com.acme.billing.Invoice`).
+//! `package-info.java` and `module-info.java` carry no types and are skipped.
+//!
+//! Classes, records, interfaces, enums and annotation types are extracted as
+//! symbols (nested types as `Outer.Inner`). Imports and `extends` /
+//! `implements` clauses are resolved against the types declared in the
+//! model — through single-type imports, the file's own package and on-demand
+//! (`.*`) imports — and become [`EdgeKind::Import`], [`EdgeKind::Extends`]
+//! and [`EdgeKind::Implements`] edges. Imports outside the model are kept as
+//! [`ExternalImport`]s. Types declared by `.kt` and `.scala` files among the
+//! paths resolve too, so Java code in a mixed project links to its Kotlin
+//! and Scala neighbours (see the [Kotlin](super::kotlin) and
+//! [Scala](super::scala) drivers).
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use tree_sitter::Node;
+
+use super::common::{EdgeSet, clean_block_comment, has_extension, header, line, parse_each, text};
+use super::{Driver, FileClaims, kotlin, scala};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Java driver (detects `pom.xml`, `build.gradle` or `build.gradle.kts`).
+#[derive(Default)]
+pub struct JavaDriver;
+
+impl Driver for JavaDriver {
+    fn name(&self) -> &'static str {
+        "java"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        ["pom.xml", "build.gradle", "build.gradle.kts"]
+            .iter()
+            .any(|manifest| project_root.join(manifest).is_file())
+    }
+
+    fn claims(&self) -> FileClaims {
+        // Kotlin and Scala types resolve Java references across languages.
+        FileClaims {
+            reads: &["*.kt", "*.scala"],
+            ..FileClaims::extensions(&["java"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_files(paths)?;
+        let ids: Vec<String> = files.iter().map(module_id).collect();
+        let mut jvm_types = kotlin::declared_types(paths)?;
+        jvm_types.extend(scala::declared_types(paths)?);
+
+        // Fully-qualified type name → declaring module, and package → modules.
+        let mut types: HashMap<String, &str> = HashMap::new();
+        let mut packages: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            for decl in &file.types {
+                types.insert(qualify(&file.package, &decl.symbol.name), id);
+            }
+            packages.entry(file.package.as_str()).or_default().push(id);
+        }
+        for ty in &jvm_types {
+            types.entry(ty.name.clone()).or_insert(&ty.module);
+            let modules = packages.entry(ty.package.as_str()).or_default();
+            if !modules.contains(&ty.module.as_str()) {
+                modules.push(&ty.module);
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let scope = Scope::new(&file.package, &file.imports, &types);
+            let mut external_imports = Vec::new();
+            let mut seen = HashSet::new();
+
+            for import in &file.imports {
+                let targets: Vec<&str> = if import.wildcard {
+                    match packages.get(import.path.as_str()) {
+                        Some(modules) if !import.is_static => modules.clone(),
+                        _ => lookup_prefix(&types, &import.path).into_iter().collect(),
+                    }
+                } else {
+                    lookup_prefix(&types, &import.path).into_iter().collect()
+                };
+                if targets.is_empty() {
+                    if seen.insert(import.path.as_str()) {
+                        external_imports.push(ExternalImport {
+                            path: import.path.clone(),
+                            kind: classify(&import.path),
+                        });
+                    }
+                    continue;
+                }
+                for target in targets {
+                    edges.push(id, target, EdgeKind::Import);
+                }
+            }
+
+            for decl in &file.types {
+                for (name, kind) in &decl.supers {
+                    if let Some(target) = scope.resolve(name) {
+                        edges.push(id, target, *kind);
+                    }
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file.types.iter().map(|d| d.symbol.clone()).collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// A type declared in Java, Kotlin or Scala source, as seen by the other
+/// JVM drivers.
+pub(super) struct JvmType {
+    /// Fully-qualified name (`com.acme.Outer.Inner`).
+    pub name: String,
+    pub package: String,
+    /// Id of the declaring module.
+    pub module: String,
+    pub interface: bool,
+}
+
+/// Types declared by the `.java` files among `paths`, for the Kotlin and
+/// Scala drivers.
+pub(super) fn declared_types(paths: &[PathBuf]) -> Result<Vec<JvmType>> {
+    let files = parse_files(paths)?;
+    Ok(files
+        .iter()
+        .flat_map(|file| {
+            let module = module_id(file);
+            file.types.iter().map(move |decl| JvmType {
+                name: qualify(&file.package, &decl.symbol.name),
+                package: file.package.clone(),
+                module: module.clone(),
+                interface: decl.symbol.kind == SymbolKind::Trait,
+            })
+        })
+        .collect())
+}
+
+fn parse_files(paths: &[PathBuf]) -> Result<Vec<ParsedFile>> {
+    parse_each(
+        paths,
+        |p| {
+            let is_info = p
+                .file_stem()
+                .is_some_and(|s| s == "package-info" || s == "module-info");
+            (has_extension(p, &["java"]) && !is_info).then(tree_sitter_java::language)
+        },
+        |path, source, tree| Ok(extract_file(path, source.as_bytes(), tree.root_node())),
+    )
+}
+
+/// Everything extracted from a single `.java` file.
+struct ParsedFile {
+    path: PathBuf,
+    /// Declared package; empty for the default package.
+    package: String,
+    imports: Vec<Import>,
+    types: Vec<TypeDecl>,
+}
+
+/// `import [static] a.b.C[.*];` (or Kotlin's and Scala's renaming imports).
+pub(super) struct Import {
+    pub path: String,
+    /// Kotlin or Scala import alias; always `None` for Java.
+    pub alias: Option<String>,
+    pub is_static: bool,
+    pub wildcard: bool,
+}
+
+/// A type declaration plus the (unresolved) supertypes it names.
+struct TypeDecl {
+    symbol: Symbol,
+    supers: Vec<(String, EdgeKind)>,
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        package: String::new(),
+        imports: Vec::new(),
+        types: Vec::new(),
+    };
+
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "package_declaration" => {
+                if let Some(name) = qualified_name(node, src) {
+                    file.package = name;
+                }
+            }
+            "import_declaration" => {
+                if let Some(path) = qualified_name(node, src) {
+                    let mut c = node.walk();
+                    let tokens: Vec<_> = node.children(&mut c).map(|n| n.kind()).collect();
+                    file.imports.push(Import {
+                        path,
+                        alias: None,
+                        is_static: tokens.contains(&"static"),
+                        wildcard: tokens.contains(&"asterisk"),
+                    });
+                }
+            }
+            _ => type_declaration(node, None, false, src, &mut file.types),
+        }
+    }
+
+    file
+}
+
+/// The dotted name inside a `package` / `import` declaration.
+fn qualified_name(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut cursor = node.walk();
+    let name = node
+        .named_children(&mut cursor)
+        .find(|n| matches!(n.kind(), "identifier" | "scoped_identifier"))?;
+    Some(text(name, src).split_whitespace().collect())
+}
+
+/// Record `node` if it declares a type, then recurse into its body for
+/// nested types. `in_interface` marks members of an interface or annotation
+/// type, which are implicitly public.
+fn type_declaration(
+    node: Node<'_>,
+    outer: Option<&str>,
+    in_interface: bool,
+    src: &[u8],
+    out: &mut Vec<TypeDecl>,
+) {
+    let kind = match node.kind() {
+        "class_declaration" | "record_declaration" => SymbolKind::Struct,
+        "interface_declaration" | "annotation_type_declaration" => SymbolKind::Trait,
+        "enum_declaration" => SymbolKind::Enum,
+        _ => return,
+    };
+    let Some(name) = node.child_by_field_name("name") else {
+        return;
+    };
+    let name = match outer {
+        Some(outer) => format!("{outer}.{}", text(name, src)),
+        None => text(name, src).to_string(),
+    };
+    let body = node.child_by_field_name("body");
+    let members_public = kind == SymbolKind::Trait;
+
+    let mut supers = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "superclass" => supers.extend(
+                child
+                    .named_child(0)
+                    .map(|t| (type_name(t, src), EdgeKind::Extends)),
+            ),
+            "super_interfaces" => supers.extend(
+                type_list(child, src)
+                    .into_iter()
+                    .map(|t| (t, EdgeKind::Implements)),
+            ),
+            "extends_interfaces" => supers.extend(
+                type_list(child, src)
+                    .into_iter()
+                    .map(|t| (t, EdgeKind::Extends)),
+            ),
+            _ => {}
+        }
+    }
+
+    out.push(TypeDecl {
+        symbol: Symbol {
+            name: name.clone(),
+            kind,
+            visibility: visibility(node, in_interface, src),
+            line: line(node),
+            cell: None,
+            doc: javadoc(node, src),
+            signature: Some(header(node, body, src)),
+            members: Vec::new(),
+        },
+        supers,
+    });
+
+    let Some(body) = body else {
+        return;
+    };
+    let mut cursor = body.walk();
+    for member in body.named_children(&mut cursor) {
+        if member.kind() == "enum_body_declarations" {
+            let mut c = member.walk();
+            for nested in member.named_children(&mut c) {
+                type_declaration(nested, Some(&name), false, src, out);
+            }
+        } else {
+            type_declaration(member, Some(&name), members_public, src, out);
+        }
+    }
+}
+
+/// Type names in a `super_interfaces` / `extends_interfaces` clause.
+fn type_list(clause: Node<'_>, src: &[u8]) -> Vec<String> {
+    let mut cursor = clause.walk();
+    let Some(list) = clause
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "type_list")
+    else {
+        return Vec::new();
+    };
+    let mut c = list.walk();
+    list.named_children(&mut c)
+        .map(|t| type_name(t, src))
+        .collect()
+}
+
+/// `Base<T>` / `com.acme.Base<T>` → `Base` / `com.acme.Base`.
+fn type_name(ty: Node<'_>, src: &[u8]) -> String {
+    let t = text(ty, src);
+    t.split('<')
+        .next()
+        .unwrap_or(t)
+        .split_whitespace()
+        .collect()
+}
+
+/// `public` / `protected` / `private`, or package-private (`Internal`) when
+/// no access modifier is present.
+fn visibility(node: Node<'_>, in_interface: bool, src: &[u8]) -> Visibility {
+    let mut cursor = node.walk();
+    let modifiers = node
+        .named_children(&mut cursor)
+        .find(|n| n.kind() == "modifiers");
+    if let Some(modifiers) = modifiers {
+        let mut c = modifiers.walk();
+        for token in modifiers.children(&mut c) {
+            match text(token, src) {
+                "public" => return Visibility::Public,
+                "protected" => return Visibility::Protected,
+                "private" => return Visibility::Private,
+                _ => {}
+            }
+        }
+    }
+    if in_interface {
+        Visibility::Public
+    } else {
+        Visibility::Internal
+    }
+}
+
+/// The `/** ... */` block directly above `node`.
+fn javadoc(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let comment = node
+        .prev_sibling()
+        .filter(|c| c.kind() == "block_comment")?;
+    let t = text(comment, src);
+    if !t.starts_with("/**") || comment.end_position().row + 1 < node.start_position().row {
+        return None;
+    }
+    clean_block_comment(t)
+}
+
+/// `com/acme/Invoice.java` in `package com.acme` → `com.acme.Invoice`.
+fn module_id(file: &ParsedFile) -> String {
+    let stem = file
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    qualify(&file.package, &stem)
+}
+
+pub(super) fn qualify(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else {
+        format!("{package}.{name}")
+    }
+}
+
+/// Longest dotted prefix of `path` that names a known type. Handles nested
+/// types (`a.B.Inner`) and static member imports (`a.B.CONSTANT`).
+pub(super) fn lookup_prefix<'a>(types: &HashMap<String, &'a str>, path: &str) -> Option<&'a str> {
+    let mut candidate = path;
+    loop {
+        if let Some(id) = types.get(candidate) {
+            return Some(id);
+        }
+        candidate = &candidate[..candidate.rfind('.')?];
+    }
+}
+
+/// Name resolution for the type references in one compilation unit.
+pub(super) struct Scope<'a> {
+    types: &'a HashMap<String, &'a str>,
+    /// Simple name (or alias) → fully-qualified name, from single-type
+    /// imports.
+    single: HashMap<&'a str, &'a str>,
+    /// Packages (or types) searched for simple names, in order: the file's
+    /// own package first, then every on-demand import.
+    on_demand: Vec<&'a str>,
+}
+
+impl<'a> Scope<'a> {
+    pub(super) fn new(
+        package: &'a str,
+        imports: &'a [Import],
+        types: &'a HashMap<String, &'a str>,
+    ) -> Self {
+        let mut single = HashMap::new();
+        let mut on_demand = vec![package];
+        for import in imports.iter().filter(|i| !i.is_static) {
+            if import.wildcard {
+                on_demand.push(&import.path);
+            } else if let Some(alias) = &import.alias {
+                single.insert(alias.as_str(), import.path.as_str());
+            } else if let Some((_, simple)) = import.path.rsplit_once('.') {
+                single.insert(simple, import.path.as_str());
+            }
+        }
+        Self {
+            types,
+            single,
+            on_demand,
+        }
+    }
+
+    /// Resolve a (possibly qualified) type reference to its module.
+    fn resolve(&self, name: &str) -> Option<&'a str> {
+        lookup_prefix(self.types, &self.qualified(name))
+    }
+
+    /// The fully-qualified form of a type reference, or the reference itself
+    /// when nothing in scope declares it.
+    pub(super) fn qualified(&self, name: &str) -> String {
+        let (first, rest) = match name.split_once('.') {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (name, None),
+        };
+        let with_rest = |base: &str| match rest {
+            Some(rest) => format!("{base}.{rest}"),
+            None => base.to_string(),
+        };
+
+        let base = match self.single.get(first) {
+            // An explicit import shadows the package and on-demand imports.
+            Some(fq) => fq.to_string(),
+            None => match self
+                .on_demand
+                .iter()
+                .map(|pkg| qualify(pkg, first))
+                .find(|fq| self.types.contains_key(fq))
+            {
+                Some(fq) => fq,
+                // Already fully qualified (`com.acme.Base`).
+                None => return name.to_string(),
+            },
+        };
+        with_rest(&base)
+    }
+}
+
+/// `java.*`, `javax.*` and `jdk.*` ship with the JDK; everything else comes
+/// from a dependency.
+fn classify(import: &str) -> ExternalKind {
+    let root = import.split('.').next().unwrap_or(import);
+    if matches!(root, "java" | "javax" | "jdk") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
@@ -0,0 +1,827 @@
+//! Kotlin driver (scanner-based).
+//!
+//! There is no Kotlin grammar for the `tree-sitter` 0.20 line the workspace
+//! is pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer and tracks type scopes by brace depth.
+//!
+//! Module ids follow the [Java driver](super::java): every `.kt` file
+//! becomes one [`Module`] whose id is the declared package joined with the
+//! file stem (`com.acme.billing.Invoice`). In a mixed Gradle project both
+//! drivers run over the same paths, and each resolves references against
+//! the other language's types too, so their edges meet in one polyglot
+//! model.
+//!
+//! Classes (including data, sealed, enum and annotation classes),
+//! interfaces, objects, type aliases, `const val`s and top-level functions
+//! are extracted as symbols; nested types are named `Outer.Inner` and
+//! extension functions `Receiver.name`. Imports become
+//! [`EdgeKind::Import`] edges. A supertype invoked with a constructor
+//! (`Base()`) is a superclass and becomes [`EdgeKind::Extends`]; any other
+//! supertype is [`EdgeKind::Implements`] when it resolves to an interface.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{EdgeSet, clean_block_comment, collapse_whitespace, has_extension, scan_each};
+use super::java::{self, Import, JvmType, Scope, lookup_prefix, qualify};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Kotlin driver (detects a Gradle or Maven build that mentions Kotlin, or a
+/// `src/main/kotlin` source root).
+#[derive(Default)]
+pub struct KotlinDriver;
+
+impl Driver for KotlinDriver {
+    fn name(&self) -> &'static str {
+        "kotlin"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        // `build.gradle.kts` alone only means the build script is Kotlin;
+        // the Kotlin plugin (`kotlin("jvm")`, `org.jetbrains.kotlin.*`) is
+        // what makes it a Kotlin project.
+        let mentions_kotlin = [
+            "build.gradle.kts",
+            "build.gradle",
+            "settings.gradle.kts",
+            "settings.gradle",
+            "pom.xml",
+        ]
+        .iter()
+        .any(|manifest| {
+            std::fs::read_to_string(project_root.join(manifest))
+                .is_ok_and(|text| text.contains("kotlin"))
+        });
+        mentions_kotlin || project_root.join("src/main/kotlin").is_dir()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            reads: &["*.java"],
+            ..FileClaims::extensions(&["kt"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_files(paths)?;
+        let ids: Vec<String> = files.iter().map(module_id).collect();
+        let java_types = java::declared_types(paths)?;
+
+        // Fully-qualified name → declaring module, the names that are
+        // interfaces, and package → modules. Java types are included so
+        // Kotlin code extending or importing them links across languages.
+        let mut names: HashMap<String, &str> = HashMap::new();
+        let mut interfaces: HashSet<String> = HashSet::new();
+        let mut packages: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            for decl in &file.decls {
+                let name = qualify(&file.package, &decl.import_name);
+                if decl.symbol.kind == SymbolKind::Trait {
+                    interfaces.insert(name.clone());
+                }
+                names.entry(name).or_insert(id);
+            }
+            packages.entry(file.package.as_str()).or_default().push(id);
+        }
+        for ty in &java_types {
+            if ty.interface {
+                interfaces.insert(ty.name.clone());
+            }
+            names.entry(ty.name.clone()).or_insert(&ty.module);
+            let modules = packages.entry(ty.package.as_str()).or_default();
+            if !modules.contains(&ty.module.as_str()) {
+                modules.push(&ty.module);
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let scope = Scope::new(&file.package, &file.imports, &names);
+            let mut external_imports = Vec::new();
+            let mut seen = HashSet::new();
+
+            for import in &file.imports {
+                let targets: Vec<&str> = match packages.get(import.path.as_str()) {
+                    Some(modules) if import.wildcard => modules.clone(),
+                    _ => lookup_prefix(&names, &import.path).into_iter().collect(),
+                };
+                if targets.is_empty() {
+                    if seen.insert(import.path.as_str()) {
+                        external_imports.push(ExternalImport {
+                            path: import.path.clone(),
+                            kind: classify(&import.path),
+                        });
+                    }
+                    continue;
+                }
+                for target in targets {
+                    edges.push(id, target, EdgeKind::Import);
+                }
+            }
+
+            for decl in &file.decls {
+                let is_interface = decl.symbol.kind == SymbolKind::Trait;
+                for supertype in &decl.supers {
+                    let name = scope.qualified(&supertype.name);
+                    let Some(target) = lookup_prefix(&names, &name) else {
+                        continue;
+                    };
+                    let kind =
+                        if !supertype.constructed && !is_interface && interfaces.contains(&name) {
+                            EdgeKind::Implements
+                        } else {
+                            EdgeKind::Extends
+                        };
+                    edges.push(id, target, kind);
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file.decls.iter().map(|d| d.symbol.clone()).collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// Types declared by the `.kt` files among `paths`, for the Java driver.
+pub(super) fn declared_types(paths: &[PathBuf]) -> Result<Vec<JvmType>> {
+    let files = parse_files(paths)?;
+    Ok(files
+        .iter()
+        .flat_map(|file| {
+            let module = module_id(file);
+            file.decls
+                .iter()
+                .filter(|d| {
+                    matches!(
+                        d.symbol.kind,
+                        SymbolKind::Struct | SymbolKind::Trait | SymbolKind::Enum
+                    )
+                })
+                .map(move |d| JvmType {
+                    name: qualify(&file.package, &d.import_name),
+                    package: file.package.clone(),
+                    module: module.clone(),
+                    interface: d.symbol.kind == SymbolKind::Trait,
+                })
+        })
+        .collect())
+}
+
+fn parse_files(paths: &[PathBuf]) -> Result<Vec<ParsedFile>> {
+    scan_each(
+        paths,
+        |p| has_extension(p, &["kt"]),
+        |path, source| Ok(extract_file(path, source)),
+    )
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &[],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/**"),
+    nested_block_comments: true,
+    // Backticks quote identifiers (`` `is valid`() ``); treating them as
+    // strings keeps their contents from being read as code.
+    quotes: &['"', '\'', '`'],
+    triple_quoted_strings: true,
+    verbatim_strings: false,
+    ident_chars: &[],
+};
+
+/// Modifiers that may precede a declaration.
+const MODIFIERS: &[&str] = &[
+    "public",
+    "internal",
+    "protected",
+    "private",
+    "open",
+    "final",
+    "abstract",
+    "sealed",
+    "data",
+    "enum",
+    "annotation",
+    "value",
+    "inline",
+    "inner",
+    "companion",
+    "const",
+    "lateinit",
+    "override",
+    "suspend",
+    "tailrec",
+    "operator",
+    "infix",
+    "external",
+    "expect",
+    "actual",
+];
+
+/// Everything extracted from a single `.kt` file.
+struct ParsedFile {
+    path: PathBuf,
+    /// Declared package; empty for the default package.
+    package: String,
+    imports: Vec<Import>,
+    decls: Vec<Decl>,
+}
+
+/// A declaration plus the (unresolved) supertypes it names.
+struct Decl {
+    symbol: Symbol,
+    /// Name below the package as written in an import: `Outer.Inner` for a
+    /// nested type, `slugify` for the extension function `String.slugify`.
+    import_name: String,
+    supers: Vec<Supertype>,
+}
+
+/// One entry of a `: Base(), Iface` list.
+struct Supertype {
+    name: String,
+    /// Invoked with a constructor call, which only a superclass can be.
+    constructed: bool,
+}
+
+/// Brace scopes the scanner tracks.
+enum Block {
+    Type {
+        name: String,
+    },
+    /// Function bodies, initializers, companion objects — skipped wholesale.
+    Other,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let tokens = tokenize(src, &SYNTAX);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        package: String::new(),
+        imports: Vec::new(),
+        decls: Vec::new(),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Block::Other)) {
+            if token.is("{") {
+                stack.push(Block::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let outer = match stack.last() {
+            Some(Block::Type { name }) => Some(name.as_str()),
+            _ => None,
+        };
+
+        match token.text {
+            _ if token.kind != TokenKind::Ident && token.kind != TokenKind::Punct => {}
+            "{" => stack.push(Block::Other),
+            "}" => {
+                stack.pop();
+            }
+            "package" if stack.is_empty() => {
+                let (name, end) = dotted_name(&tokens, i + 1);
+                file.package = name;
+                i = end;
+                continue;
+            }
+            "import" if stack.is_empty() => {
+                let (path, mut end) = dotted_name(&tokens, i + 1);
+                let alias = match (tokens.get(end), tokens.get(end + 1)) {
+                    (Some(r#as), Some(alias)) if r#as.is("as") && alias.is_ident() => {
+                        end += 2;
+                        Some(alias.text.to_string())
+                    }
+                    _ => None,
+                };
+                let (path, wildcard) = match path.strip_suffix(".*") {
+                    Some(path) => (path.to_string(), true),
+                    None => (path, false),
+                };
+                if !path.is_empty() {
+                    file.imports.push(Import {
+                        path,
+                        alias,
+                        is_static: false,
+                        wildcard,
+                    });
+                }
+                i = end;
+                continue;
+            }
+            // `fun interface Handler` is read from its `interface` keyword.
+            "fun" if tokens.get(i + 1).is_some_and(|t| t.is("interface")) => {}
+            "class" | "interface" | "object" => {
+                if let Some((decl, end)) = type_declaration(&tokens, i, outer, src) {
+                    if tokens.get(end).is_some_and(|t| t.is("{")) {
+                        stack.push(Block::Type {
+                            name: decl.symbol.name.clone(),
+                        });
+                        i = end + 1;
+                    } else {
+                        i = end;
+                    }
+                    file.decls.push(decl);
+                    continue;
+                }
+            }
+            "fun" => {
+                let (decl, end) = function(&tokens, i, outer, src);
+                // Only top-level functions are symbols; members are skipped
+                // like other member declarations.
+                file.decls.extend(decl.filter(|_| outer.is_none()));
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Block::Other);
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                continue;
+            }
+            "typealias" if outer.is_none() => {
+                if let Some((decl, end)) = type_alias(&tokens, i, src) {
+                    file.decls.push(decl);
+                    i = end;
+                    continue;
+                }
+            }
+            "val" if outer.is_none() => {
+                if let Some((decl, end)) = constant(&tokens, i, src) {
+                    file.decls.push(decl);
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// Parse a class, interface or object whose keyword is at `keyword`.
+/// Returns the declaration and the index just past its header (the `{` of
+/// the body, when there is one).
+fn type_declaration(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    src: &str,
+) -> Option<(Decl, usize)> {
+    let start = modifiers_start(tokens, keyword);
+    let modifiers = modifiers(&tokens[start..keyword]);
+    // Companion objects are part of their class; `object : Foo {}` is an
+    // expression.
+    if modifiers.contains(&"companion") {
+        return None;
+    }
+    let name = tokens.get(keyword + 1).filter(|t| t.is_ident())?;
+    let kind = match tokens[keyword].text {
+        "interface" => SymbolKind::Trait,
+        _ if modifiers.contains(&"enum") => SymbolKind::Enum,
+        _ if modifiers.contains(&"annotation") => SymbolKind::Trait,
+        _ => SymbolKind::Struct,
+    };
+
+    let mut end = keyword + 2;
+    if tokens.get(end).is_some_and(|t| t.is("<")) {
+        end = matching(tokens, end) + 1;
+    }
+    // Primary constructor: `(...)`, or `private @Inject constructor(...)`.
+    let mut j = end;
+    while let Some(t) = tokens.get(j) {
+        if t.is("@") {
+            j = skip_annotation(tokens, j);
+        } else if t.is_ident() && (MODIFIERS.contains(&t.text) || t.text == "constructor") {
+            j += 1;
+        } else {
+            break;
+        }
+    }
+    if tokens.get(j).is_some_and(|t| t.is("(")) && (j == end || tokens[j - 1].is("constructor")) {
+        end = matching(tokens, j) + 1;
+    }
+
+    let mut supers = Vec::new();
+    if tokens.get(end).is_some_and(|t| t.is(":")) {
+        end += 1;
+        loop {
+            let (name, after) = dotted_name(tokens, end);
+            if name.is_empty() {
+                break;
+            }
+            end = skip_type_arguments(tokens, after);
+            let constructed = tokens.get(end).is_some_and(|t| t.is("("));
+            if constructed {
+                end = matching(tokens, end) + 1;
+            }
+            // Delegation: `Repository by impl`.
+            if tokens.get(end).is_some_and(|t| t.is("by")) {
+                end = dotted_name(tokens, end + 1).1;
+                if tokens.get(end).is_some_and(|t| t.is("(")) {
+                    end = matching(tokens, end) + 1;
+                }
+            }
+            supers.push(Supertype { name, constructed });
+            if !tokens.get(end).is_some_and(|t| t.is(",")) {
+                break;
+            }
+            end += 1;
+        }
+    }
+    end = skip_where_clause(tokens, end);
+
+    let name = match outer {
+        Some(outer) => format!("{outer}.{}", name.text),
+        None => name.text.to_string(),
+    };
+    let symbol = Symbol {
+        name: name.clone(),
+        kind,
+        visibility: visibility(&modifiers),
+        line: tokens[start].line,
+        cell: None,
+        doc: kdoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    Some((
+        Decl {
+            symbol,
+            import_name: name,
+            supers,
+        },
+        end,
+    ))
+}
+
+/// Parse the function whose `fun` keyword is at `keyword`. Returns the
+/// declaration (when it has a name) and the index just past its header —
+/// the `{` or `=` of its body, when there is one.
+fn function(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    src: &str,
+) -> (Option<Decl>, usize) {
+    let start = modifiers_start(tokens, keyword);
+    let mut j = skip_type_arguments(tokens, keyword + 1);
+
+    // `name`, or `Receiver.name` for an extension function.
+    let name_start = j;
+    let mut last_dot = None;
+    while let Some(t) = tokens.get(j) {
+        if t.is("(") {
+            break;
+        } else if t.is("<") {
+            j = matching(tokens, j);
+        } else if t.is(".") {
+            last_dot = Some(j);
+        } else if !t.is_ident() && !t.is("?") {
+            // Anonymous function or a lambda type.
+            return (None, j.max(keyword + 1));
+        }
+        j += 1;
+    }
+    let Some(name) = j.checked_sub(1).and_then(|n| tokens.get(n)) else {
+        return (None, j);
+    };
+    if j >= tokens.len() || j == name_start || !name.is_ident() {
+        return (None, j);
+    }
+    let receiver = last_dot.map(|dot| {
+        let mut receiver = String::new();
+        let mut k = name_start;
+        while k < dot {
+            if tokens[k].is("<") {
+                k = matching(tokens, k);
+            } else if tokens[k].is_ident() || tokens[k].is(".") {
+                receiver.push_str(tokens[k].text);
+            }
+            k += 1;
+        }
+        receiver
+    });
+
+    let mut end = matching(tokens, j) + 1;
+    if tokens.get(end).is_some_and(|t| t.is(":")) {
+        // The return type runs to the body, or to the end of the line.
+        let line = tokens[end].line;
+        end += 1;
+        while let Some(t) = tokens.get(end) {
+            if t.is("{") || t.is("=") || t.is("where") || t.is(";") || t.line != line {
+                break;
+            }
+            if t.is("<") || t.is("(") {
+                end = matching(tokens, end);
+            }
+            end += 1;
+        }
+    }
+    end = skip_where_clause(tokens, end);
+
+    let modifiers = modifiers(&tokens[start..keyword]);
+    let symbol = Symbol {
+        name: match (outer, &receiver) {
+            (_, Some(receiver)) => format!("{receiver}.{}", name.text),
+            (Some(outer), None) => format!("{outer}.{}", name.text),
+            (None, None) => name.text.to_string(),
+        },
+        kind: SymbolKind::Function,
+        visibility: visibility(&modifiers),
+        line: tokens[start].line,
+        cell: None,
+        doc: kdoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    let decl = Decl {
+        symbol,
+        import_name: name.text.to_string(),
+        supers: Vec::new(),
+    };
+    (Some(decl), end)
+}
+
+/// `typealias Name<T> = Target` at `keyword`.
+fn type_alias(tokens: &[Token<'_>], keyword: usize, src: &str) -> Option<(Decl, usize)> {
+    let start = modifiers_start(tokens, keyword);
+    let name = tokens.get(keyword + 1).filter(|t| t.is_ident())?;
+    let eq = skip_type_arguments(tokens, keyword + 2);
+    if !tokens.get(eq).is_some_and(|t| t.is("=")) {
+        return None;
+    }
+    let end = line_end(tokens, eq);
+    let symbol = Symbol {
+        name: name.text.to_string(),
+        kind: SymbolKind::TypeAlias,
+        visibility: visibility(&modifiers(&tokens[start..keyword])),
+        line: tokens[start].line,
+        cell: None,
+        doc: kdoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    Some((
+        Decl {
+            symbol,
+            import_name: name.text.to_string(),
+            supers: Vec::new(),
+        },
+        end,
+    ))
+}
+
+/// A top-level `const val NAME: Type = value` at `keyword`. Other
+/// properties are not symbols.
+fn constant(tokens: &[Token<'_>], keyword: usize, src: &str) -> Option<(Decl, usize)> {
+    let start = modifiers_start(tokens, keyword);
+    let modifiers = modifiers(&tokens[start..keyword]);
+    if !modifiers.contains(&"const") {
+        return None;
+    }
+    let name = tokens.get(keyword + 1).filter(|t| t.is_ident())?;
+    let end = line_end(tokens, keyword);
+    // The signature stops before the initializer.
+    let header_end = tokens[keyword..end]
+        .iter()
+        .position(|t| t.is("="))
+        .map_or(end, |p| keyword + p);
+    let symbol = Symbol {
+        name: name.text.to_string(),
+        kind: SymbolKind::Constant,
+        visibility: visibility(&modifiers),
+        line: tokens[start].line,
+        cell: None,
+        doc: kdoc(tokens, start),
+        signature: Some(signature(tokens, start, header_end, src)),
+        members: Vec::new(),
+    };
+    Some((
+        Decl {
+            symbol,
+            import_name: name.text.to_string(),
+            supers: Vec::new(),
+        },
+        end,
+    ))
+}
+
+/// Index of the first modifier or annotation in front of the keyword at
+/// `keyword`. Kotlin needs no `;` between declarations, so the start is
+/// found by walking backwards rather than from the previous statement.
+fn modifiers_start(tokens: &[Token<'_>], keyword: usize) -> usize {
+    let mut start = keyword;
+    while start > 0 {
+        let prev = tokens[start - 1];
+        let is_modifier = prev.is_ident()
+            && (MODIFIERS.contains(&prev.text)
+                || (prev.text == "fun" && tokens[keyword].is("interface")));
+        if is_modifier {
+            start -= 1;
+        } else if let Some(at) = annotation_start(tokens, start) {
+            start = at;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Start of the annotation (`@Foo`, `@a.b.Foo(...)`, `@field:Foo`) that ends
+/// just before `end`, if there is one.
+fn annotation_start(tokens: &[Token<'_>], end: usize) -> Option<usize> {
+    let mut i = end.checked_sub(1)?;
+    if tokens[i].is(")") {
+        let mut depth = 0usize;
+        loop {
+            if tokens[i].is(")") {
+                depth += 1;
+            } else if tokens[i].is("(") {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            i = i.checked_sub(1)?;
+        }
+        i = i.checked_sub(1)?;
+    }
+    loop {
+        if !tokens[i].is_ident() {
+            return None;
+        }
+        let before = i.checked_sub(1)?;
+        if tokens[before].is("@") {
+            return Some(before);
+        }
+        if !tokens[before].is(".") && !tokens[before].is(":") {
+            return None;
+        }
+        i = before.checked_sub(1)?;
+    }
+}
+
+/// Index just past the annotation starting with the `@` at `at`.
+fn skip_annotation(tokens: &[Token<'_>], at: usize) -> usize {
+    let (_, mut end) = dotted_name(tokens, at + 1);
+    if tokens.get(end).is_some_and(|t| t.is("(")) {
+        end = matching(tokens, end) + 1;
+    }
+    end
+}
+
+/// Index past a `<...>` list at `at`, or `at` when there is none.
+fn skip_type_arguments(tokens: &[Token<'_>], at: usize) -> usize {
+    if tokens.get(at).is_some_and(|t| t.is("<")) {
+        matching(tokens, at) + 1
+    } else {
+        at
+    }
+}
+
+/// Index past a `where T : Bound, U : Other` clause at `at`.
+fn skip_where_clause(tokens: &[Token<'_>], at: usize) -> usize {
+    if !tokens.get(at).is_some_and(|t| t.is("where")) {
+        return at;
+    }
+    let mut end = at + 1;
+    loop {
+        end = dotted_name(tokens, end).1;
+        if !tokens.get(end).is_some_and(|t| t.is(":")) {
+            return end;
+        }
+        end = skip_type_arguments(tokens, dotted_name(tokens, end + 1).1);
+        if !tokens.get(end).is_some_and(|t| t.is(",")) {
+            return end;
+        }
+        end += 1;
+    }
+}
+
+/// Index of the first token after the line `at` is on, jumping over
+/// brackets that span lines.
+fn line_end(tokens: &[Token<'_>], at: usize) -> usize {
+    let line = tokens[at].line;
+    let mut end = at;
+    while let Some(t) = tokens.get(end) {
+        if t.line != line || t.is(";") {
+            break;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            end = matching(tokens, end);
+        }
+        end += 1;
+    }
+    end
+}
+
+/// `a.b.C` (or `a.b.*`) starting at `start`; returns the name and the index
+/// just past it.
+fn dotted_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        let expects_ident = name.is_empty() || name.ends_with('.');
+        if expects_ident && (t.is_ident() || (t.is("*") && !name.is_empty())) {
+            name.push_str(t.text);
+        } else if !expects_ident && t.is(".") {
+            name.push('.');
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if name.ends_with('.') {
+        name.pop();
+        i -= 1;
+    }
+    (name, i)
+}
+
+fn modifiers<'a>(tokens: &[Token<'a>]) -> Vec<&'a str> {
+    tokens
+        .iter()
+        .filter(|t| t.is_ident() && MODIFIERS.contains(&t.text))
+        .map(|t| t.text)
+        .collect()
+}
+
+/// Kotlin declarations are public unless marked otherwise.
+fn visibility(modifiers: &[&str]) -> Visibility {
+    if modifiers.contains(&"private") {
+        Visibility::Private
+    } else if modifiers.contains(&"protected") {
+        Visibility::Protected
+    } else if modifiers.contains(&"internal") {
+        Visibility::Internal
+    } else {
+        Visibility::Public
+    }
+}
+
+/// The `/** ... */` block directly in front of the declaration at `start`.
+fn kdoc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    let doc = tokens.get(start.checked_sub(1)?)?;
+    if doc.kind != TokenKind::Doc {
+        return None;
+    }
+    clean_block_comment(doc.text)
+}
+
+/// Source text of `tokens[start..end]`, whitespace collapsed.
+fn signature(tokens: &[Token<'_>], start: usize, end: usize, src: &str) -> String {
+    let last = tokens[end.clamp(start + 1, tokens.len()) - 1];
+    collapse_whitespace(&src[tokens[start].offset..last.offset + last.text.len()])
+}
+
+/// `com/acme/Invoice.kt` in `package com.acme` → `com.acme.Invoice`.
+fn module_id(file: &ParsedFile) -> String {
+    let stem = file
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    qualify(&file.package, &stem)
+}
+
+/// `kotlin.*` and the JDK packages ship with the platform; everything else
+/// comes from a dependency.
+fn classify(import: &str) -> ExternalKind {
+    let root = import.split('.').next().unwrap_or(import);
+    if matches!(root, "kotlin" | "java" | "javax" | "jdk") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
@@ -4,17 +4,55 @@
 //! collects the available drivers and picks the ones whose [`Driver::detect`]
 //! returns `true` for the current project root.
 
+mod claims;
+mod common;
+mod cpp;
+mod csharp;
+mod docker;
+mod elixir;
+mod external;
 mod go;
+mod graphql;
+mod java;
+mod kotlin;
+mod openapi;
+mod php;
+mod protobuf;
 mod python;
 mod registry;
+mod ruby;
 mod rust;
+mod scala;
+mod scanner;
+mod sql;
+mod swift;
+mod terraform;
 mod typescript;
+mod zig;
 
+pub use claims::{FileClaims, script_interpreter};
+pub use cpp::CppDriver;
+pub use csharp::CSharpDriver;
+pub use docker::DockerDriver;
+pub use elixir::ElixirDriver;
+pub use external::{ExternalDriver, PROTOCOL_VERSION};
 pub use go::GoDriver;
+pub use graphql::GraphqlDriver;
+pub use java::JavaDriver;
+pub use kotlin::KotlinDriver;
+pub use openapi::OpenApiDriver;
+pub use php::PhpDriver;
+pub use protobuf::ProtobufDriver;
 pub use python::PythonDriver;
 pub use registry::DriverRegistry;
+pub use ruby::RubyDriver;
 pub use rust::RustDriver;
+pub use scala::ScalaDriver;
+pub use sql::SqlDriver;
+pub use swift::SwiftDriver;
+pub use terraform::TerraformDriver;
 pub use typescript::TypeScriptDriver;
+pub use zig::ZigDriver;
 
 use std::path::{Path, PathBuf};
 
@@ -35,8 +73,35 @@ pub trait Driver: Send + Sync {
     /// `project_root` (usually by sniffing manifest files).
     fn detect(&self, project_root: &Path) -> bool;
 
+    /// Whether [`Driver::detect`] matches `project_root` through loose
+    /// source files alone, with no manifest marking it as a project root
+    /// (`.proto` files without a `buf.yaml`, say). The pipeline merges
+    /// nested directories detected this way into one project at their
+    /// common ancestor. Defaults to `false`.
+    fn detects_loose_sources(&self, _project_root: &Path) -> bool {
+        false
+    }
+
+    /// The files this driver parses (or reads), so the pipeline can route
+    /// each discovered file to the drivers that want it. Defaults to
+    /// [`FileClaims::ALL`].
+    fn claims(&self) -> FileClaims {
+        FileClaims::ALL
+    }
+
+    /// Directories below `project_root` (relative to it) that hold sources
+    /// the configured `source_dirs` may miss — the member crates of a Cargo
+    /// workspace, say. The pipeline scans them too. Defaults to none.
+    fn source_dirs(&self, _project_root: &Path) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Parse `paths` and produce a [`CodeModel`].
     ///
+    /// The pipeline passes only the files [`Driver::claims`] wants, but
+    /// drivers still filter `paths` themselves so they can be called
+    /// directly with a whole tree.
+    ///
     /// Implementations may be called from multiple threads concurrently by
     /// the pipeline — see NEW_ROADMAP Phase 1.d.
     fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel>;
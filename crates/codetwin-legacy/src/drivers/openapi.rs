@@ -0,0 +1,407 @@
+//! OpenAPI / Swagger driver.
+//!
+//! Specs are found by file name (`openapi.yaml`, `openapi.json`,
+//! `swagger.yml`, ...) and read with `serde_yaml`, which covers the JSON
+//! flavour too; files without a top-level `openapi` or `swagger` key are
+//! ignored, and so are files that fail to parse. Each spec is split into modules: one per tag (operations
+//! without tags are grouped by the first path segment that is not `api` or
+//! a version), with id `spec/tag` (`billing/openapi/invoices`), plus a
+//! `spec/schemas` module holding `components.schemas` (`definitions` in
+//! Swagger 2).
+//!
+//! Operations become functions named after their `operationId` (or
+//! `GET /path`) with a `METHOD /path` signature; schemas become structs,
+//! or enums when they list `enum` values. Every `$ref` an operation or a
+//! schema makes to a schema — directly or through a shared parameter,
+//! request body or response — becomes an [`EdgeKind::Uses`] edge, including
+//! references into other specs of the model (`common.yaml#/...`).
+//!
+//! The parsed document carries no positions, so symbol lines come from a
+//! textual search for the keys leading to each operation or schema.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_yaml::Value;
+
+use super::common::{
+    EdgeSet, common_root, has_extension, normalize_path, relative_module_id, scan_each,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Module, ModuleId, Symbol, SymbolKind, Visibility};
+
+/// OpenAPI driver (detects `openapi.*` or `swagger.*` at the root).
+#[derive(Default)]
+pub struct OpenApiDriver;
+
+impl Driver for OpenApiDriver {
+    fn name(&self) -> &'static str {
+        "openapi"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        std::fs::read_dir(project_root).is_ok_and(|entries| {
+            entries
+                .flatten()
+                .any(|e| is_spec(&e.path()) && e.path().is_file())
+        })
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            names: &[
+                "openapi.yaml",
+                "openapi.yml",
+                "openapi.json",
+                "swagger.yaml",
+                "swagger.yml",
+                "swagger.json",
+            ],
+            ..FileClaims::extensions(&[])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let specs: Vec<Spec> = scan_each(paths, is_spec, read_spec)?
+            .into_iter()
+            .flatten()
+            .collect();
+        let root = common_root(specs.iter().map(|s| s.path.as_path()));
+        let ids: Vec<String> = specs
+            .iter()
+            .map(|s| relative_module_id(&s.path, &root))
+            .collect();
+        let by_path: HashMap<PathBuf, usize> = specs
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (normalize_path(&s.path), i))
+            .collect();
+        let schemas_id = |spec: usize| format!("{}/schemas", ids[spec]);
+
+        // The schemas module a `$ref` made from `spec` points into.
+        let resolve = |spec: usize, reference: &str| -> Option<String> {
+            let (file, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+            let target = if file.is_empty() {
+                spec
+            } else {
+                let dir = specs[spec].path.parent().unwrap_or(Path::new(""));
+                *by_path.get(&normalize_path(&dir.join(file)))?
+            };
+            let name = pointer
+                .strip_prefix("/components/schemas/")
+                .or_else(|| pointer.strip_prefix("/definitions/"))?;
+            specs[target]
+                .schemas
+                .iter()
+                .any(|s| s.symbol.name == unescape(name))
+                .then(|| schemas_id(target))
+        };
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (i, spec) in specs.iter().enumerate() {
+            // Declared tags first, in declaration order, then any others
+            // as operations introduce them.
+            let mut groups: Vec<&str> = spec.tags.iter().map(|(name, _)| name.as_str()).collect();
+            for operation in &spec.operations {
+                if !groups.contains(&operation.group.as_str()) {
+                    groups.push(&operation.group);
+                }
+            }
+
+            for group in groups {
+                let operations: Vec<&Operation> = spec
+                    .operations
+                    .iter()
+                    .filter(|o| o.group == group)
+                    .collect();
+                if operations.is_empty() {
+                    continue;
+                }
+                let id = format!("{}/{group}", ids[i]);
+                for reference in operations.iter().flat_map(|o| &o.refs) {
+                    if let Some(to) = resolve(i, reference) {
+                        edges.push(&id, &to, EdgeKind::Uses);
+                    }
+                }
+                model.modules.push(Module {
+                    id: ModuleId(id),
+                    name: group.to_string(),
+                    path: spec.path.clone(),
+                    symbols: operations.iter().map(|o| o.symbol.clone()).collect(),
+                    doc: spec
+                        .tags
+                        .iter()
+                        .find(|(name, _)| name == group)
+                        .and_then(|(_, doc)| doc.clone()),
+                    external_imports: Vec::new(),
+                    group: None,
+                    container: None,
+                });
+            }
+
+            if spec.schemas.is_empty() {
+                continue;
+            }
+            let id = schemas_id(i);
+            for reference in spec.schemas.iter().flat_map(|s| &s.refs) {
+                if let Some(to) = resolve(i, reference) {
+                    edges.push(&id, &to, EdgeKind::Uses);
+                }
+            }
+            model.modules.push(Module {
+                id: ModuleId(id),
+                name: "schemas".to_string(),
+                path: spec.path.clone(),
+                symbols: spec.schemas.iter().map(|s| s.symbol.clone()).collect(),
+                doc: spec.title.clone(),
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// `openapi.yaml`, `swagger.json`, ...
+fn is_spec(path: &Path) -> bool {
+    has_extension(path, &["yaml", "yml", "json"])
+        && path
+            .file_stem()
+            .is_some_and(|s| s == "openapi" || s == "swagger")
+}
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Everything extracted from one spec.
+struct Spec {
+    path: PathBuf,
+    /// `info.title`.
+    title: Option<String>,
+    /// Top-level `tags`, with their descriptions.
+    tags: Vec<(String, Option<String>)>,
+    operations: Vec<Operation>,
+    schemas: Vec<Schema>,
+}
+
+struct Operation {
+    /// First tag, or the path prefix.
+    group: String,
+    symbol: Symbol,
+    /// `$ref`s made by the operation.
+    refs: Vec<String>,
+}
+
+struct Schema {
+    symbol: Symbol,
+    refs: Vec<String>,
+}
+
+/// Parse one spec; `None` when the document is not an OpenAPI or Swagger
+/// description, or is not valid YAML (logged).
+fn read_spec(path: &Path, source: &str) -> Result<Option<Spec>> {
+    let doc: Value = match serde_yaml::from_str(source) {
+        Ok(doc) => doc,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "skipping malformed spec");
+            return Ok(None);
+        }
+    };
+    if doc.get("openapi").is_none() && doc.get("swagger").is_none() {
+        return Ok(None);
+    }
+    let lines: Vec<&str> = source.lines().collect();
+
+    let tags = doc["tags"]
+        .as_sequence()
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| {
+            let name = tag["name"].as_str()?;
+            Some((name.to_string(), string(&tag["description"])))
+        })
+        .collect();
+
+    let mut operations = Vec::new();
+    for (route, item) in entries(&doc["paths"]) {
+        for (method, operation) in entries(item) {
+            if !METHODS.contains(&method) {
+                continue;
+            }
+            let endpoint = format!("{} {route}", method.to_uppercase());
+            let mut refs = Vec::new();
+            // Path-level parameters apply to every operation.
+            collect_refs(&item["parameters"], &doc, &mut HashSet::new(), &mut refs);
+            collect_refs(operation, &doc, &mut HashSet::new(), &mut refs);
+            operations.push(Operation {
+                group: operation["tags"][0]
+                    .as_str()
+                    .map_or_else(|| path_group(route), String::from),
+                symbol: Symbol {
+                    name: operation["operationId"]
+                        .as_str()
+                        .map_or_else(|| endpoint.clone(), String::from),
+                    kind: SymbolKind::Function,
+                    visibility: Visibility::Public,
+                    line: key_line(&lines, &["paths", route, method]),
+                    cell: None,
+                    doc: string(&operation["summary"])
+                        .or_else(|| string(&operation["description"])),
+                    signature: Some(endpoint),
+                    members: Vec::new(),
+                },
+                refs,
+            });
+        }
+    }
+
+    let (section, schemas) = match doc.get("components") {
+        Some(components) => (vec!["components", "schemas"], &components["schemas"]),
+        None => (vec!["definitions"], &doc["definitions"]),
+    };
+    let schemas = entries(schemas)
+        .map(|(name, schema)| {
+            let mut refs = Vec::new();
+            collect_refs(schema, &doc, &mut HashSet::new(), &mut refs);
+            let keys: Vec<&str> = section.iter().copied().chain([name]).collect();
+            Schema {
+                symbol: Symbol {
+                    name: name.to_string(),
+                    kind: if schema.get("enum").is_some() {
+                        SymbolKind::Enum
+                    } else {
+                        SymbolKind::Struct
+                    },
+                    visibility: Visibility::Public,
+                    line: key_line(&lines, &keys),
+                    cell: None,
+                    doc: string(&schema["description"]).or_else(|| string(&schema["title"])),
+                    signature: schema["type"].as_str().map(|ty| format!("{name}: {ty}")),
+                    members: Vec::new(),
+                },
+                refs,
+            }
+        })
+        .collect();
+
+    Ok(Some(Spec {
+        path: path.to_path_buf(),
+        title: string(&doc["info"]["title"]),
+        tags,
+        operations,
+        schemas,
+    }))
+}
+
+/// Record every `$ref` under `value`. References to shared parameters,
+/// request bodies and responses of the same document are followed, so the
+/// schemas they use count as used by the referrer.
+fn collect_refs(value: &Value, doc: &Value, seen: &mut HashSet<String>, out: &mut Vec<String>) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, child) in map {
+                let Some(reference) = child.as_str().filter(|_| key.as_str() == Some("$ref"))
+                else {
+                    collect_refs(child, doc, seen, out);
+                    continue;
+                };
+                let is_schema =
+                    reference.contains("/schemas/") || reference.contains("/definitions/");
+                match reference.strip_prefix('#') {
+                    Some(pointer) if !is_schema => {
+                        if seen.insert(reference.to_string())
+                            && let Some(target) = lookup(doc, pointer)
+                        {
+                            collect_refs(target, doc, seen, out);
+                        }
+                    }
+                    _ => {
+                        if !out.iter().any(|r| r == reference) {
+                            out.push(reference.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect_refs(item, doc, seen, out);
+            }
+        }
+        Value::Tagged(tagged) => collect_refs(&tagged.value, doc, seen, out),
+        _ => {}
+    }
+}
+
+/// Follow a JSON pointer (`/components/responses/NotFound`) into `doc`.
+fn lookup<'a>(doc: &'a Value, pointer: &str) -> Option<&'a Value> {
+    pointer
+        .split('/')
+        .skip(1)
+        .try_fold(doc, |value, segment| value.get(unescape(segment)))
+}
+
+/// JSON pointer escapes: `~1` is `/`, `~0` is `~`.
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// `(key, value)` pairs of a mapping with string keys.
+fn entries(value: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    value
+        .as_mapping()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.as_str()?, value)))
+}
+
+fn string(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+/// The first segment of `route` that is not `api`, a version or a
+/// parameter (`/api/v2/invoices/{id}` → `invoices`).
+fn path_group(route: &str) -> String {
+    let is_version = |s: &str| {
+        s.strip_prefix('v')
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    };
+    route
+        .split('/')
+        .find(|s| !s.is_empty() && *s != "api" && !is_version(s) && !s.starts_with('{'))
+        .unwrap_or("root")
+        .to_string()
+}
+
+/// 1-indexed line of the last of `keys`, each searched for below the
+/// previous one, in YAML (`key:`) or JSON (`"key":`) form.
+fn key_line(lines: &[&str], keys: &[&str]) -> u32 {
+    let mut line = 0;
+    for key in keys {
+        let found = lines[line..].iter().position(|l| {
+            let l = l.trim_start();
+            [
+                format!("{key}:"),
+                format!("\"{key}\":"),
+                format!("'{key}':"),
+            ]
+            .iter()
+            .any(|k| l.starts_with(k.as_str()))
+        });
+        match found {
+            Some(offset) => line += offset,
+            None => break,
+        }
+    }
+    line as u32 + 1
+}
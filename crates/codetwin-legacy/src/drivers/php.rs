@@ -0,0 +1,844 @@
+//! PHP driver (scanner-based).
+//!
+//! There is no PHP grammar for the `tree-sitter` 0.20 line the workspace is
+//! pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer over the `<?php` parts of each file (heredoc bodies blanked
+//! out) and tracks namespace and type scopes by brace depth.
+//!
+//! As in the [C# driver](super::csharp), each namespace becomes one
+//! [`Module`] collecting the classes, interfaces, traits, enums, functions
+//! and constants declared in it across files (id `App\Http\Controllers`;
+//! declarations outside any namespace land in `global`). The
+//! `autoload.psr-4` map of the nearest `composer.json` gives each namespace
+//! its directory, supplies the namespace of files that declare none, and
+//! tells first-party names missing from the run apart from vendor packages.
+//!
+//! `use` statements become [`EdgeKind::Import`] edges; `extends` becomes
+//! [`EdgeKind::Extends`], and `implements` and trait `use` inside a class
+//! become [`EdgeKind::Implements`]. Imports that resolve neither to the
+//! model nor to the PSR-4 map are kept as [`ExternalImport`]s.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::common::{
+    EdgeSet, clean_block_comment, collapse_whitespace, has_extension, normalize_path, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// PHP driver (detects `composer.json`).
+#[derive(Default)]
+pub struct PhpDriver;
+
+impl Driver for PhpDriver {
+    fn name(&self) -> &'static str {
+        "php"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("composer.json").is_file()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            interpreters: &["php"],
+            ..FileClaims::extensions(&["php"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let mut files = scan_each(
+            paths,
+            // Blade templates are views, not PHP code.
+            |p| {
+                (has_extension(p, &["php"]) && !p.to_string_lossy().ends_with(".blade.php"))
+                    || self.claims().owns_script(p)
+            },
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+
+        let mut composer = ComposerCache::default();
+        for file in &mut files {
+            let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+            let psr4 = composer.nearest(&dir);
+            for block in file.blocks.iter_mut().filter(|b| !b.declared) {
+                if let Some(name) = psr4.and_then(|p| p.namespace_of(&dir)) {
+                    block.name = name;
+                }
+            }
+        }
+
+        // Merge namespace blocks from every file into one module each.
+        let mut namespaces: Vec<NamespaceAcc<'_>> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for file in &files {
+            let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+            for block in &file.blocks {
+                let i = *index.entry(block.name.as_str()).or_insert_with(|| {
+                    let psr4 = composer.nearest(&dir).cloned();
+                    namespaces.push(NamespaceAcc {
+                        name: &block.name,
+                        path: psr4
+                            .as_ref()
+                            .and_then(|p| p.directory_of(&block.name))
+                            .unwrap_or_else(|| dir.clone()),
+                        psr4,
+                        blocks: Vec::new(),
+                    });
+                    namespaces.len() - 1
+                });
+                namespaces[i].blocks.push(block);
+            }
+        }
+
+        // Fully-qualified name → namespace, for types (with whether they are
+        // interfaces) and for functions and constants.
+        let mut types: HashMap<String, (&str, bool)> = HashMap::new();
+        let mut members: HashMap<String, &str> = HashMap::new();
+        for ns in &namespaces {
+            for block in &ns.blocks {
+                for decl in &block.types {
+                    types.insert(
+                        qualify(ns.name, &decl.symbol.name),
+                        (ns.name, decl.is_interface),
+                    );
+                }
+                for symbol in &block.members {
+                    members.insert(qualify(ns.name, &symbol.name), ns.name);
+                }
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for ns in &namespaces {
+            let id = module_id(ns.name);
+            let mut external_imports: Vec<ExternalImport> = Vec::new();
+
+            for block in &ns.blocks {
+                for import in &block.uses {
+                    let target = match import.kind {
+                        UseKind::Class => types
+                            .get(&import.path)
+                            .map(|(ns, _)| *ns)
+                            .or_else(|| index.get_key_value(import.path.as_str()).map(|(k, _)| *k)),
+                        UseKind::Function | UseKind::Const => members.get(&import.path).copied(),
+                    };
+                    match target {
+                        Some(target) => edges.push(&id, &module_id(target), EdgeKind::Import),
+                        // First-party, but not part of this run.
+                        None if ns.psr4.as_ref().is_some_and(|p| p.owns(&import.path)) => {}
+                        None if !external_imports.iter().any(|e| e.path == import.path) => {
+                            external_imports.push(ExternalImport {
+                                path: import.path.clone(),
+                                kind: classify(&import.path),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+
+                for decl in &block.types {
+                    for (name, kind) in &decl.bases {
+                        let name = resolve_name(name, &block.name, &block.uses);
+                        if let Some((target, _)) = types.get(&name) {
+                            edges.push(&id, &module_id(target), *kind);
+                        }
+                    }
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id),
+                name: ns.name.rsplit('\\').next().unwrap_or_default().to_string(),
+                path: ns.path.clone(),
+                symbols: ns
+                    .blocks
+                    .iter()
+                    .flat_map(|b| {
+                        b.types
+                            .iter()
+                            .map(|d| d.symbol.clone())
+                            .chain(b.members.iter().cloned())
+                    })
+                    .collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &[],
+    line_comments: &["//", "#"],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/**"),
+    nested_block_comments: false,
+    quotes: &['"', '\'', '`'],
+    triple_quoted_strings: false,
+    verbatim_strings: false,
+    // Variables, and namespaced names (`App\Models\User`) as one token.
+    ident_chars: &['$', '\\'],
+};
+
+/// Everything extracted from a single `.php` file.
+struct ParsedFile {
+    path: PathBuf,
+    blocks: Vec<NamespaceBlock>,
+}
+
+/// One `namespace` (or the file's global scope) within a file.
+struct NamespaceBlock {
+    name: String,
+    /// Named by a `namespace` declaration rather than implied.
+    declared: bool,
+    uses: Vec<Use>,
+    types: Vec<TypeDecl>,
+    /// Functions and constants.
+    members: Vec<Symbol>,
+}
+
+/// One name imported by a `use` statement.
+struct Use {
+    /// Fully-qualified, without the leading `\`.
+    path: String,
+    alias: String,
+    kind: UseKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum UseKind {
+    Class,
+    Function,
+    Const,
+}
+
+/// A type declaration plus the (unresolved) names it extends, implements or
+/// uses as traits.
+struct TypeDecl {
+    symbol: Symbol,
+    is_interface: bool,
+    bases: Vec<(String, EdgeKind)>,
+}
+
+/// Brace scopes the scanner tracks.
+enum Scope {
+    Namespace,
+    Type {
+        block: usize,
+        decl: usize,
+    },
+    /// Function bodies, closures, arrays — skipped wholesale.
+    Other,
+}
+
+/// Every namespace block with the same name, across files.
+struct NamespaceAcc<'a> {
+    name: &'a str,
+    path: PathBuf,
+    psr4: Option<Psr4>,
+    blocks: Vec<&'a NamespaceBlock>,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let masked = mask_source(src);
+    let src = masked.as_str();
+    let tokens = tokenize(src, &SYNTAX);
+    let mut blocks: Vec<NamespaceBlock> = Vec::new();
+    // Target of a `namespace X;` statement.
+    let mut file_block: Option<usize> = None;
+    let mut stack: Vec<Scope> = Vec::new();
+
+    // Start of the declaration being read, and doc comments seen since the
+    // last statement boundary.
+    let mut decl_start: Option<usize> = None;
+    let mut docs: Vec<&str> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Scope::Other)) {
+            if token.is("{") {
+                stack.push(Scope::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let in_type = match stack.last() {
+            Some(Scope::Type { block, decl }) => Some((*block, *decl)),
+            _ => None,
+        };
+        let keyword = token.is_ident() && !follows_member_access(&tokens, i);
+
+        match token.text {
+            _ if token.kind == TokenKind::Doc => docs.push(token.text),
+            _ if token.kind != TokenKind::Ident && token.kind != TokenKind::Punct => {}
+            // Attributes (`#[...]`, see `mask_source`).
+            "[" if decl_start.is_none() => {
+                i = matching(&tokens, i);
+            }
+            "{" => {
+                stack.push(Scope::Other);
+                (decl_start, docs) = (None, Vec::new());
+            }
+            "}" => {
+                stack.pop();
+                (decl_start, docs) = (None, Vec::new());
+            }
+            ";" => (decl_start, docs) = (None, Vec::new()),
+            "namespace" if keyword && stack.is_empty() => {
+                let name = tokens
+                    .get(i + 1)
+                    .filter(|t| t.is_ident())
+                    .map(|t| t.text.trim_start_matches('\\').to_string());
+                let end = i + 1 + usize::from(name.is_some());
+                blocks.push(NamespaceBlock {
+                    name: name.unwrap_or_default(),
+                    declared: true,
+                    uses: Vec::new(),
+                    types: Vec::new(),
+                    members: Vec::new(),
+                });
+                let block = blocks.len() - 1;
+                file_block = Some(block);
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Scope::Namespace);
+                }
+                (decl_start, docs) = (None, Vec::new());
+                i = end + 1;
+                continue;
+            }
+            // Closures' `use ($x)` never starts a statement.
+            "use" if keyword && decl_start.is_none() && in_type.is_none() && stack.len() <= 1 => {
+                let end = statement_end(&tokens, i);
+                let block = current_block(&mut blocks, &mut file_block);
+                blocks[block].uses.extend(parse_use(&tokens[i + 1..end]));
+                (decl_start, docs) = (None, Vec::new());
+                i = end + 1;
+                continue;
+            }
+            // Trait use inside a class body.
+            "use" if keyword && decl_start.is_none() => {
+                let (block, decl) = in_type.unwrap_or_default();
+                let mut end = i + 1;
+                while let Some(t) = tokens.get(end).filter(|t| !t.is(";") && !t.is("{")) {
+                    if t.is_ident() {
+                        blocks[block].types[decl]
+                            .bases
+                            .push((t.text.to_string(), EdgeKind::Implements));
+                    }
+                    end += 1;
+                }
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    // Conflict resolution block: `{ A::foo insteadof B; }`.
+                    end = matching(&tokens, end);
+                }
+                (decl_start, docs) = (None, Vec::new());
+                i = end + 1;
+                continue;
+            }
+            "class" | "interface" | "trait" | "enum"
+                if keyword && tokens.get(i + 1).is_some_and(|t| t.is_ident()) =>
+            {
+                let start = decl_start.unwrap_or(i);
+                let (decl, end) = type_declaration(&tokens, start, i, &docs, src);
+                let block = current_block(&mut blocks, &mut file_block);
+                blocks[block].types.push(decl);
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Scope::Type {
+                        block,
+                        decl: blocks[block].types.len() - 1,
+                    });
+                }
+                (decl_start, docs) = (None, Vec::new());
+                i = end + 1;
+                continue;
+            }
+            "function" if keyword && in_type.is_none() => {
+                let start = decl_start.unwrap_or(i);
+                if let Some((symbol, end)) = function(&tokens, start, i, &docs, src) {
+                    let block = current_block(&mut blocks, &mut file_block);
+                    blocks[block].members.push(symbol);
+                    (decl_start, docs) = (None, Vec::new());
+                    // Let the `{` of the body open an `Other` scope.
+                    i = end;
+                    continue;
+                }
+                decl_start = decl_start.or(Some(i));
+            }
+            "const" if keyword && in_type.is_none() => {
+                let end = statement_end(&tokens, i);
+                let block = current_block(&mut blocks, &mut file_block);
+                blocks[block]
+                    .members
+                    .extend(constants(&tokens, i, end, &docs));
+                (decl_start, docs) = (None, Vec::new());
+                i = end + 1;
+                continue;
+            }
+            _ => decl_start = decl_start.or(Some(i)),
+        }
+        i += 1;
+    }
+
+    ParsedFile {
+        path: path.to_path_buf(),
+        blocks,
+    }
+}
+
+/// The block declarations currently land in, opening an implicit global
+/// one when the file has no `namespace`.
+fn current_block(blocks: &mut Vec<NamespaceBlock>, file_block: &mut Option<usize>) -> usize {
+    *file_block.get_or_insert_with(|| {
+        blocks.push(NamespaceBlock {
+            name: String::new(),
+            declared: false,
+            uses: Vec::new(),
+            types: Vec::new(),
+            members: Vec::new(),
+        });
+        blocks.len() - 1
+    })
+}
+
+/// `Foo::class`, `$x->class`, `$x?->function` are not keywords.
+fn follows_member_access(tokens: &[Token<'_>], i: usize) -> bool {
+    i.checked_sub(1)
+        .is_some_and(|p| tokens[p].is(":") || tokens[p].is(">") || tokens[p].is("new"))
+}
+
+/// Parse a class-like declaration whose keyword is at `keyword` and whose
+/// modifiers start at `start`. Returns the declaration and the index of the
+/// `{` that opens its body.
+fn type_declaration(
+    tokens: &[Token<'_>],
+    start: usize,
+    keyword: usize,
+    docs: &[&str],
+    src: &str,
+) -> (TypeDecl, usize) {
+    let kind = match tokens[keyword].text {
+        "interface" | "trait" => SymbolKind::Trait,
+        "enum" => SymbolKind::Enum,
+        _ => SymbolKind::Struct,
+    };
+    let is_interface = tokens[keyword].text == "interface";
+
+    let mut bases = Vec::new();
+    let mut clause = None;
+    let mut end = keyword + 2;
+    while let Some(t) = tokens.get(end).filter(|t| !t.is("{") && !t.is(";")) {
+        match t.text {
+            "extends" => clause = Some(EdgeKind::Extends),
+            "implements" => clause = Some(EdgeKind::Implements),
+            // Backed enum type: `enum Suit: string`.
+            ":" => clause = None,
+            _ if t.is_ident() => {
+                if let Some(kind) = clause {
+                    bases.push((t.text.to_string(), kind));
+                }
+            }
+            _ => {}
+        }
+        end += 1;
+    }
+
+    let header_end = tokens.get(end).map_or(src.len(), |t| t.offset);
+    let symbol = Symbol {
+        name: tokens[keyword + 1].text.to_string(),
+        kind,
+        visibility: Visibility::Public,
+        line: tokens[start].line,
+        cell: None,
+        doc: php_doc(docs),
+        signature: Some(collapse_whitespace(&src[tokens[start].offset..header_end])),
+        members: Vec::new(),
+    };
+    (
+        TypeDecl {
+            symbol,
+            is_interface,
+            bases,
+        },
+        end,
+    )
+}
+
+/// A named function at `keyword`; closures (`function () use ($x)`) have
+/// no name and yield `None`. Returns the symbol and the index just past its
+/// header.
+fn function(
+    tokens: &[Token<'_>],
+    start: usize,
+    keyword: usize,
+    docs: &[&str],
+    src: &str,
+) -> Option<(Symbol, usize)> {
+    // `function &name()` returns by reference.
+    let mut name = keyword + 1;
+    if tokens.get(name).is_some_and(|t| t.is("&")) {
+        name += 1;
+    }
+    let name_token = tokens.get(name).filter(|t| t.is_ident())?;
+    if !tokens.get(name + 1).is_some_and(|t| t.is("(")) {
+        return None;
+    }
+    let mut end = matching(tokens, name + 1) + 1;
+    while tokens.get(end).is_some_and(|t| !t.is("{") && !t.is(";")) {
+        end += 1;
+    }
+    let header_end = tokens.get(end).map_or(src.len(), |t| t.offset);
+    Some((
+        Symbol {
+            name: name_token.text.to_string(),
+            kind: SymbolKind::Function,
+            visibility: Visibility::Public,
+            line: tokens[start].line,
+            cell: None,
+            doc: php_doc(docs),
+            signature: Some(collapse_whitespace(&src[tokens[start].offset..header_end])),
+            members: Vec::new(),
+        },
+        end,
+    ))
+}
+
+/// `const A = 1, B = 2;` between `keyword` and `end`.
+fn constants(tokens: &[Token<'_>], keyword: usize, end: usize, docs: &[&str]) -> Vec<Symbol> {
+    let mut out = Vec::new();
+    let mut i = keyword + 1;
+    while i < end {
+        let t = tokens[i];
+        if t.is("(") || t.is("[") || t.is("{") {
+            i = matching(tokens, i);
+        } else if t.is_ident() && tokens.get(i + 1).is_some_and(|n| n.is("=")) {
+            let previous = tokens[i - 1];
+            if previous.is("const") || previous.is(",") {
+                out.push(Symbol {
+                    name: t.text.to_string(),
+                    kind: SymbolKind::Constant,
+                    visibility: Visibility::Public,
+                    line: t.line,
+                    cell: None,
+                    doc: php_doc(docs),
+                    signature: Some(format!("const {}", t.text)),
+                    members: Vec::new(),
+                });
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+/// `use A\B[ as C], D;`, `use function A\f;`, `use const A\X;` and grouped
+/// `use A\{B, C as D};`.
+fn parse_use(tokens: &[Token<'_>]) -> Vec<Use> {
+    let mut kind = UseKind::Class;
+    let mut i = 0;
+    match tokens.first().map(|t| t.text) {
+        Some("function") => (kind, i) = (UseKind::Function, 1),
+        Some("const") => (kind, i) = (UseKind::Const, 1),
+        _ => {}
+    }
+
+    let mut out = Vec::new();
+    let mut prefix = String::new();
+    while i < tokens.len() {
+        let t = tokens[i];
+        if t.is("}") {
+            prefix.clear();
+        } else if t.is_ident() && t.text != "as" && t.text != "function" && t.text != "const" {
+            let name = t.text.trim_start_matches('\\');
+            // Group prefix: `A\{` is scanned as `A\` then `{`.
+            if name.ends_with('\\') && tokens.get(i + 1).is_some_and(|n| n.is("{")) {
+                prefix = name.to_string();
+                i += 2;
+                continue;
+            }
+            let path = format!("{prefix}{name}");
+            let alias = match (tokens.get(i + 1), tokens.get(i + 2)) {
+                (Some(r#as), Some(alias)) if r#as.is("as") && alias.is_ident() => {
+                    i += 2;
+                    alias.text.to_string()
+                }
+                _ => path.rsplit('\\').next().unwrap_or(&path).to_string(),
+            };
+            out.push(Use { path, alias, kind });
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Index of the `;` ending the statement at `start`.
+fn statement_end(tokens: &[Token<'_>], start: usize) -> usize {
+    tokens[start..]
+        .iter()
+        .position(|t| t.is(";"))
+        .map_or(tokens.len(), |p| start + p)
+}
+
+/// The PHPDoc block closest to the declaration, tags dropped.
+fn php_doc(docs: &[&str]) -> Option<String> {
+    let body = clean_block_comment(docs.last()?)?;
+    let summary: Vec<&str> = body
+        .lines()
+        .take_while(|l| !l.trim_start().starts_with('@'))
+        .collect();
+    let summary = summary.join("\n").trim().to_string();
+    (!summary.is_empty()).then_some(summary)
+}
+
+/// Resolve a class name as written in namespace `ns` to its fully-qualified
+/// form: `\A\B` is already qualified, a leading alias is expanded through
+/// the block's class imports, anything else is relative to `ns`.
+fn resolve_name(name: &str, ns: &str, uses: &[Use]) -> String {
+    if let Some(qualified) = name.strip_prefix('\\') {
+        return qualified.to_string();
+    }
+    let (first, rest) = match name.split_once('\\') {
+        Some((first, rest)) => (first, Some(rest)),
+        None => (name, None),
+    };
+    let base = uses
+        .iter()
+        .find(|u| u.kind == UseKind::Class && u.alias.eq_ignore_ascii_case(first))
+        .map_or_else(|| qualify(ns, first), |u| u.path.clone());
+    match rest {
+        Some(rest) => format!("{base}\\{rest}"),
+        None => base,
+    }
+}
+
+fn qualify(namespace: &str, name: &str) -> String {
+    if namespace.is_empty() {
+        name.to_string()
+    } else {
+        format!("{namespace}\\{name}")
+    }
+}
+
+fn module_id(namespace: &str) -> String {
+    if namespace.is_empty() {
+        "global".to_string()
+    } else {
+        namespace.to_string()
+    }
+}
+
+/// Unqualified names (`Exception`, `DateTimeImmutable`) are PHP built-ins;
+/// namespaced ones come from Composer packages.
+fn classify(name: &str) -> ExternalKind {
+    if name.contains('\\') {
+        ExternalKind::ThirdParty
+    } else {
+        ExternalKind::Std
+    }
+}
+
+/// Blank out (keeping newlines and byte offsets) inline HTML outside
+/// `<?php ... ?>` and heredoc / nowdoc bodies, and turn `#[` attributes
+/// into `[` so they are not read as `#` comments.
+fn mask_source(src: &str) -> String {
+    let b = src.as_bytes();
+    let mut out = b.to_vec();
+    let blank = |out: &mut [u8], from: usize, to: usize| {
+        for byte in &mut out[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let find = |from: usize, needle: &[u8]| {
+        b[from.min(b.len())..]
+            .windows(needle.len())
+            .position(|w| w == needle)
+            .map(|p| from + p)
+    };
+    let line_end = |from: usize| {
+        b[from..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(b.len(), |p| from + p)
+    };
+
+    let mut i = find(0, b"<?").unwrap_or(b.len());
+    blank(&mut out, 0, i);
+    while i < b.len() {
+        match b[i] {
+            b'?' if b.get(i + 1) == Some(&b'>') => {
+                let next = find(i + 2, b"<?").unwrap_or(b.len());
+                blank(&mut out, i + 2, next);
+                i = next.max(i + 2);
+            }
+            b'#' if b.get(i + 1) == Some(&b'[') => {
+                out[i] = b' ';
+                i += 1;
+            }
+            b'#' => i = line_end(i),
+            b'/' if b.get(i + 1) == Some(&b'/') => i = line_end(i),
+            b'/' if b.get(i + 1) == Some(&b'*') => {
+                i = find(i + 2, b"*/").map_or(b.len(), |p| p + 2);
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1;
+                while i < b.len() && b[i] != quote {
+                    i += if b[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'<' if b[i..].starts_with(b"<<<") => {
+                let mut j = i + 3;
+                while matches!(b.get(j), Some(b' ' | b'\t' | b'"' | b'\'')) {
+                    j += 1;
+                }
+                let start = j;
+                while b
+                    .get(j)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+                {
+                    j += 1;
+                }
+                let id = &b[start..j];
+                if id.is_empty() {
+                    i += 3;
+                    continue;
+                }
+                // The body runs to the first line starting (after any
+                // indentation) with the identifier.
+                let mut line = (line_end(j) + 1).min(b.len());
+                while line < b.len() {
+                    let body = &b[line..line_end(line)];
+                    let indent = body.iter().take_while(|c| c.is_ascii_whitespace()).count();
+                    let rest = &body[indent..];
+                    if rest.starts_with(id)
+                        && !rest
+                            .get(id.len())
+                            .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+                    {
+                        break;
+                    }
+                    line = (line_end(line) + 1).min(b.len());
+                }
+                let body_start = (line_end(j) + 1).min(b.len());
+                blank(&mut out, body_start, line);
+                i = line;
+            }
+            _ => i += 1,
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// The `autoload.psr-4` (and `autoload-dev.psr-4`) map of one
+/// `composer.json`: namespace prefix (no trailing `\`) → directory, longest
+/// prefix first.
+#[derive(Clone)]
+struct Psr4 {
+    roots: Vec<(String, PathBuf)>,
+}
+
+impl Psr4 {
+    fn load(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        let json: Value = serde_json::from_str(&text).ok()?;
+        let dir = normalize_path(path.parent().unwrap_or(Path::new("")));
+        let mut roots = Vec::new();
+        for section in ["autoload", "autoload-dev"] {
+            let Some(map) = json[section]["psr-4"].as_object() else {
+                continue;
+            };
+            for (prefix, dirs) in map {
+                let prefix = prefix.trim_end_matches('\\').to_string();
+                let dirs = match dirs {
+                    Value::Array(dirs) => dirs.iter().filter_map(Value::as_str).collect(),
+                    other => other.as_str().into_iter().collect::<Vec<_>>(),
+                };
+                for d in dirs {
+                    roots.push((prefix.clone(), normalize_path(&dir.join(d))));
+                }
+            }
+        }
+        roots.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        Some(Self { roots })
+    }
+
+    /// Namespace PSR-4 implies for files in `dir`.
+    fn namespace_of(&self, dir: &Path) -> Option<String> {
+        self.roots.iter().find_map(|(prefix, root)| {
+            let rel = dir.strip_prefix(root).ok()?;
+            let segments = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned());
+            let name = std::iter::once(prefix.clone())
+                .chain(segments)
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("\\");
+            Some(name)
+        })
+    }
+
+    /// Directory PSR-4 maps `namespace` to.
+    fn directory_of(&self, namespace: &str) -> Option<PathBuf> {
+        self.roots.iter().find_map(|(prefix, root)| {
+            let rest = if prefix.is_empty() {
+                namespace
+            } else if namespace == prefix {
+                ""
+            } else {
+                namespace
+                    .strip_prefix(prefix.as_str())?
+                    .strip_prefix('\\')?
+            };
+            Some(root.join(rest.replace('\\', "/")))
+        })
+    }
+
+    /// `name` lives under one of the map's (non-global) prefixes.
+    fn owns(&self, name: &str) -> bool {
+        self.roots.iter().any(|(prefix, _)| {
+            !prefix.is_empty()
+                && name
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.starts_with('\\'))
+        })
+    }
+}
+
+/// Per-directory memo of the nearest `composer.json`'s PSR-4 map.
+#[derive(Default)]
+struct ComposerCache {
+    by_dir: HashMap<PathBuf, Option<Psr4>>,
+}
+
+impl ComposerCache {
+    fn nearest(&mut self, dir: &Path) -> Option<&Psr4> {
+        if !self.by_dir.contains_key(dir) {
+            let found = dir
+                .ancestors()
+                .find(|a| a.join("composer.json").is_file())
+                .and_then(|a| Psr4::load(&a.join("composer.json")));
+            self.by_dir.insert(dir.to_path_buf(), found);
+        }
+        self.by_dir.get(dir).and_then(Option::as_ref)
+    }
+}
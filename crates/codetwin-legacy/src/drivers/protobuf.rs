@@ -0,0 +1,530 @@
+//! Protocol Buffers driver (scanner-based).
+//!
+//! There is no protobuf grammar for the `tree-sitter` 0.20 line the
+//! workspace is pinned to, so this driver runs the shared
+//! [`scanner`](super::scanner) tokenizer and tracks message and service
+//! scopes by brace depth.
+//!
+//! As in the [C# driver](super::csharp), each proto `package` becomes one
+//! [`Module`] collecting the declarations of every `.proto` file in it (id
+//! `acme.billing.v1`, named after its last segment that is not a version:
+//! `billing`). Files without a `package` statement are modules of their
+//! own, with an id relative to the common root.
+//!
+//! Messages and enums are extracted as symbols (nested ones named
+//! `Outer.Inner`), services as [`SymbolKind::Trait`]s and their rpcs as
+//! functions named `Service.Method` whose signature carries the request and
+//! response types. `import` statements become [`EdgeKind::Import`] edges to
+//! the package of the imported file, or [`ExternalImport`]s when it is not
+//! in the model. Message field types and rpc request/response types are
+//! resolved with protoc's scoping rules and become [`EdgeKind::Uses`]
+//! edges.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{
+    EdgeSet, clean_block_comment, collapse_whitespace, common_root, has_extension, join_doc,
+    normalize_path, relative_module_id, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Protocol Buffers driver (detects `buf.yaml`, `buf.work.yaml`, or
+/// `.proto` files at the root or in `proto/`).
+#[derive(Default)]
+pub struct ProtobufDriver;
+
+impl Driver for ProtobufDriver {
+    fn name(&self) -> &'static str {
+        "protobuf"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("buf.yaml").is_file()
+            || project_root.join("buf.work.yaml").is_file()
+            || has_proto(project_root)
+            || has_proto(&project_root.join("proto"))
+    }
+
+    fn detects_loose_sources(&self, project_root: &Path) -> bool {
+        !project_root.join("buf.yaml").is_file() && !project_root.join("buf.work.yaml").is_file()
+    }
+
+    fn source_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        let proto = PathBuf::from("proto");
+        if has_proto(&project_root.join(&proto)) {
+            vec![proto]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["proto"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| has_extension(p, &["proto"]),
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+
+        // Module id of every file; packages spread over several files share
+        // one module.
+        let ids: Vec<String> = files
+            .iter()
+            .map(|f| {
+                if f.package.is_empty() {
+                    relative_module_id(&f.path, &root)
+                } else {
+                    f.package.clone()
+                }
+            })
+            .collect();
+        let mut packages: Vec<PackageAcc<'_>> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            let i = *index.entry(id).or_insert_with(|| {
+                packages.push(PackageAcc {
+                    id,
+                    files: Vec::new(),
+                });
+                packages.len() - 1
+            });
+            packages[i].files.push(file);
+        }
+
+        // Fully-qualified message / enum name → declaring module.
+        let mut types: HashMap<String, &str> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            for symbol in file
+                .symbols
+                .iter()
+                .filter(|s| matches!(s.kind, SymbolKind::Struct | SymbolKind::Enum))
+            {
+                types.insert(qualify(&file.package, &symbol.name), id);
+            }
+        }
+        let by_path: Vec<(PathBuf, &str)> = files
+            .iter()
+            .zip(&ids)
+            .map(|(f, id)| (normalize_path(&f.path), id.as_str()))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for package in &packages {
+            let mut external_imports: Vec<ExternalImport> = Vec::new();
+            for file in &package.files {
+                for import in &file.imports {
+                    // Imports are relative to an include root, so any file
+                    // whose path ends with the import path matches.
+                    let target = by_path
+                        .iter()
+                        .find(|(path, _)| path.ends_with(import))
+                        .map(|(_, id)| *id);
+                    match target {
+                        Some(to) => edges.push(package.id, to, EdgeKind::Import),
+                        None if !external_imports.iter().any(|e| &e.path == import) => {
+                            external_imports.push(ExternalImport {
+                                path: import.clone(),
+                                kind: classify(import),
+                            });
+                        }
+                        None => {}
+                    }
+                }
+                for (scope, name) in &file.references {
+                    if let Some(to) = resolve(&types, scope, name) {
+                        edges.push(package.id, to, EdgeKind::Uses);
+                    }
+                }
+            }
+
+            let first = package.files[0];
+            let (name, path) = if first.package.is_empty() {
+                let stem = first
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                (stem, first.path.clone())
+            } else {
+                let dir = first.path.parent().unwrap_or(Path::new(""));
+                (display_name(&first.package), dir.to_path_buf())
+            };
+            model.modules.push(Module {
+                id: ModuleId(package.id.to_string()),
+                name,
+                path,
+                symbols: package
+                    .files
+                    .iter()
+                    .flat_map(|f| f.symbols.iter().cloned())
+                    .collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+const SYNTAX: Syntax = Syntax {
+    // protoc treats the comments directly above a declaration as its
+    // documentation, whatever their style.
+    doc_line_comments: &["//"],
+    line_comments: &[],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/*"),
+    nested_block_comments: false,
+    quotes: &['"', '\''],
+    triple_quoted_strings: false,
+    verbatim_strings: false,
+    ident_chars: &[],
+};
+
+/// Built-in field types, which never resolve to a declaration.
+const SCALARS: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32",
+    "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes",
+];
+
+/// Everything extracted from a single `.proto` file.
+struct ParsedFile {
+    path: PathBuf,
+    /// Declared package; empty when there is none.
+    package: String,
+    /// `import` paths, as written.
+    imports: Vec<String>,
+    symbols: Vec<Symbol>,
+    /// `(scope, type name)` of every field and rpc type reference; the
+    /// scope is the fully-qualified name of the enclosing message (or the
+    /// package, for rpcs).
+    references: Vec<(String, String)>,
+}
+
+/// The files of one package.
+struct PackageAcc<'a> {
+    id: &'a str,
+    files: Vec<&'a ParsedFile>,
+}
+
+/// Scopes the scanner tracks.
+enum Block {
+    /// A message (or one of its `oneof`s); fields are read from it.
+    Message(String),
+    Service(String),
+    /// Enum bodies, option blocks, `extend` blocks — skipped wholesale.
+    Other,
+}
+
+fn has_proto(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| has_extension(&e.path(), &["proto"]) && e.path().is_file())
+    })
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let tokens = tokenize(src, &SYNTAX);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        package: String::new(),
+        imports: Vec::new(),
+        symbols: Vec::new(),
+        references: Vec::new(),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        match stack.last() {
+            Some(Block::Other) => {
+                if token.is("{") {
+                    stack.push(Block::Other);
+                } else if token.is("}") {
+                    stack.pop();
+                }
+                i += 1;
+                continue;
+            }
+            _ if token.is("{") => stack.push(Block::Other),
+            _ if token.is("}") => {
+                stack.pop();
+            }
+            None if token.is("package") => {
+                let (name, end) = dotted_name(&tokens, i + 1);
+                file.package = name;
+                i = end;
+                continue;
+            }
+            None if token.is("import") => {
+                let path = tokens[i + 1..]
+                    .iter()
+                    .take(2)
+                    .find(|t| t.kind == TokenKind::Str);
+                // An unterminated string (`import "` at the end of a
+                // half-saved file) names nothing.
+                let path = path.and_then(|t| {
+                    let quote = t.text.chars().next()?;
+                    t.text.strip_prefix(quote)?.strip_suffix(quote)
+                });
+                if let Some(path) = path {
+                    file.imports.push(path.to_string());
+                }
+            }
+            None | Some(Block::Message(_))
+                if (token.is("message") || token.is("enum") || token.is("service"))
+                    && tokens.get(i + 1).is_some_and(|t| t.is_ident()) =>
+            {
+                let outer = match stack.last() {
+                    Some(Block::Message(outer)) => Some(outer.as_str()),
+                    _ => None,
+                };
+                let name = match outer {
+                    Some(outer) => format!("{outer}.{}", tokens[i + 1].text),
+                    None => tokens[i + 1].text.to_string(),
+                };
+                let kind = match token.text {
+                    "message" => SymbolKind::Struct,
+                    "enum" => SymbolKind::Enum,
+                    _ => SymbolKind::Trait,
+                };
+                file.symbols.push(Symbol {
+                    name: name.clone(),
+                    kind,
+                    visibility: Visibility::Public,
+                    line: token.line,
+                    cell: None,
+                    doc: proto_doc(&tokens, i),
+                    signature: Some(format!("{} {}", token.text, tokens[i + 1].text)),
+                    members: Vec::new(),
+                });
+                if tokens.get(i + 2).is_some_and(|t| t.is("{")) {
+                    stack.push(match token.text {
+                        "message" => Block::Message(name),
+                        "service" => Block::Service(name),
+                        _ => Block::Other,
+                    });
+                    i += 3;
+                } else {
+                    i += 2;
+                }
+                continue;
+            }
+            Some(Block::Message(name))
+                if token.is("oneof") && tokens.get(i + 2).is_some_and(|t| t.is("{")) =>
+            {
+                // Fields of a `oneof` belong to the enclosing message.
+                stack.push(Block::Message(name.clone()));
+                i += 3;
+                continue;
+            }
+            Some(Block::Message(name))
+                if token.is("map") && tokens.get(i + 1).is_some_and(|t| t.is("<")) =>
+            {
+                // `map<string, Money> totals = 3;`
+                let close = matching(&tokens, i + 1);
+                if let Some(comma) = (i + 2..close).find(|&j| tokens[j].is(",")) {
+                    let (value, _) = dotted_name(&tokens, comma + 1);
+                    file.references.push((qualify(&file.package, name), value));
+                }
+                i = close + 1;
+                continue;
+            }
+            Some(Block::Message(name)) if token.is_ident() || token.is(".") => {
+                // `[optional | repeated] Type name = 1;` — the label is read
+                // as a type first, but only a type is followed by a name
+                // and `=`.
+                let (ty, end) = dotted_name(&tokens, i);
+                let is_field = tokens.get(end).is_some_and(|t| t.is_ident())
+                    && tokens.get(end + 1).is_some_and(|t| t.is("="));
+                if is_field && !ty.is_empty() && ty != "option" {
+                    if !SCALARS.contains(&ty.as_str()) {
+                        file.references.push((qualify(&file.package, name), ty));
+                    }
+                    i = end;
+                    continue;
+                }
+            }
+            Some(Block::Service(service))
+                if token.is("rpc") && tokens.get(i + 1).is_some_and(|t| t.is_ident()) =>
+            {
+                let (symbol, types, end) = rpc(&tokens, i, service, src);
+                file.symbols.extend(symbol);
+                for ty in types {
+                    file.references.push((file.package.clone(), ty));
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// `rpc Name([stream] Request) returns ([stream] Response)` at `keyword`.
+/// Returns the function symbol, the request and response types, and the
+/// index just past the signature.
+fn rpc(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    service: &str,
+    src: &str,
+) -> (Option<Symbol>, Vec<String>, usize) {
+    let mut types = Vec::new();
+    let mut end = keyword + 2;
+    // The request list, then `returns` and the response list.
+    for _ in 0..2 {
+        if tokens.get(end).is_some_and(|t| t.is("returns")) {
+            end += 1;
+        }
+        if !tokens.get(end).is_some_and(|t| t.is("(")) {
+            return (None, types, end);
+        }
+        let close = matching(tokens, end);
+        let mut start = end + 1;
+        if tokens.get(start).is_some_and(|t| t.is("stream")) && start + 1 < close {
+            start += 1;
+        }
+        let (ty, _) = dotted_name(tokens, start);
+        if !ty.is_empty() {
+            types.push(ty);
+        }
+        end = close + 1;
+    }
+
+    let last = tokens[end - 1];
+    let symbol = Symbol {
+        name: format!("{service}.{}", tokens[keyword + 1].text),
+        kind: SymbolKind::Function,
+        visibility: Visibility::Public,
+        line: tokens[keyword].line,
+        cell: None,
+        doc: proto_doc(tokens, keyword),
+        signature: Some(collapse_whitespace(
+            &src[tokens[keyword].offset..last.offset + last.text.len()],
+        )),
+        members: Vec::new(),
+    };
+    (Some(symbol), types, end)
+}
+
+/// `a.b.C`, optionally fully qualified (`.a.b.C`), starting at `start`;
+/// returns the name and the index just past it.
+fn dotted_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        let expects_ident = name.is_empty() || name.ends_with('.');
+        if expects_ident && t.is_ident() {
+            name.push_str(t.text);
+        } else if t.is(".") && (name.is_empty() || !expects_ident) {
+            name.push('.');
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if name.ends_with('.') {
+        name.pop();
+        i -= 1;
+    }
+    (name, i)
+}
+
+/// Resolve a type reference made in `scope` the way protoc does: a leading
+/// `.` makes it fully qualified, otherwise every enclosing scope is tried,
+/// innermost first.
+fn resolve<'a>(types: &HashMap<String, &'a str>, scope: &str, name: &str) -> Option<&'a str> {
+    if let Some(absolute) = name.strip_prefix('.') {
+        return types.get(absolute).copied();
+    }
+    let mut scope = scope;
+    loop {
+        if let Some(id) = types.get(&qualify(scope, name)) {
+            return Some(id);
+        }
+        if scope.is_empty() {
+            return None;
+        }
+        scope = scope.rfind('.').map_or("", |p| &scope[..p]);
+    }
+}
+
+fn qualify(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_string()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+/// Last segment of a package that is not an API version
+/// (`acme.billing.v1beta1` → `billing`).
+fn display_name(package: &str) -> String {
+    let is_version = |s: &&str| {
+        s.strip_prefix('v')
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+    };
+    let segments: Vec<&str> = package.split('.').collect();
+    segments
+        .iter()
+        .rev()
+        .find(|s| !is_version(s))
+        .or(segments.last())
+        .unwrap_or(&package)
+        .to_string()
+}
+
+/// The comments directly above the declaration at `start` (a trailing
+/// comment on the line of the previous declaration does not count).
+fn proto_doc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    let previous_line = tokens[..start]
+        .iter()
+        .rfind(|t| t.kind != TokenKind::Doc)
+        .map(|t| t.line);
+    let first = tokens[..start]
+        .iter()
+        .rposition(|t| t.kind != TokenKind::Doc || Some(t.line) == previous_line)
+        .map_or(0, |p| p + 1);
+    let lines = tokens[first..start]
+        .iter()
+        .flat_map(|t| match t.text.strip_prefix("//") {
+            Some(line) => vec![line.trim().to_string()],
+            None => clean_block_comment(t.text)
+                .map(|doc| doc.lines().map(String::from).collect())
+                .unwrap_or_default(),
+        })
+        .collect();
+    join_doc(lines)
+}
+
+/// Google's well-known types ship with protoc; any other import comes from
+/// a dependency (a `buf.yaml` dep or a vendored include path).
+fn classify(import: &str) -> ExternalKind {
+    if import.starts_with("google/protobuf/") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
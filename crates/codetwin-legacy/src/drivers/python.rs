@@ -1,14 +1,38 @@
 //! Python driver (tree-sitter-based).
 //!
-//! TODO(Phase 1.b): port existing Python extraction; include imports,
-//!                  decorators, `__all__`, and type annotations.
+//! Every `.py` file becomes one [`Module`] whose id is its dotted import
+//! path. Packages are found by walking up through directories that contain
+//! an `__init__.py`, so both flat and `src/` layouts yield `pkg.sub.mod`.
+//! Scripts outside any package are named after their path below the common
+//! root (`tests.unit.conftest`), and their bare imports look next to them
+//! first, the way Python puts a script's directory on `sys.path`.
+//! Extension-less scripts whose `#!` line or modeline names Python
+//! (`bin/manage`) count as `.py` files.
+//!
+//! `import` / `from ... import` statements (absolute and relative) become
+//! [`EdgeKind::Import`] edges, and class bases that resolve to another
+//! module become [`EdgeKind::Extends`] edges.
+//!
+//! Jupyter notebooks (`.ipynb`) with a Python kernel are modules too: their
+//! code cells are concatenated, with IPython magics and shell escapes
+//! blanked out, and parsed like any other file. Symbol lines are mapped
+//! back to `cell N, line M` ([`Symbol::cell`]), and the notebook's imports
+//! make it a consumer of the package modules it uses.
 
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use rayon::prelude::*;
+use serde_json::Value;
+use tree_sitter::{Node, Parser};
 
-use super::Driver;
-use crate::ir::CodeModel;
+use super::common::{
+    EdgeSet, common_root, has_extension, header, line, normalize_path, parse_each, parse_source,
+    read_source, text,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Module, ModuleId, Symbol, SymbolKind, Visibility};
 
 /// Python driver (detects `pyproject.toml` or `setup.py`).
 #[derive(Default)]
@@ -25,9 +49,706 @@ impl Driver for PythonDriver {
             || project_root.join("setup.cfg").is_file()
     }
 
-    fn parse(&self, _paths: &[PathBuf]) -> Result<CodeModel> {
-        // TODO(Phase 1.b): extract classes, functions, imports, and
-        //                  build Edges via `import` statements.
-        Ok(CodeModel::new(self.name()))
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            interpreters: &["python"],
+            ..FileClaims::extensions(&["py", "ipynb"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let mut files = parse_each(
+            paths,
+            |p| {
+                (has_extension(p, &["py"]) || self.claims().owns_script(p))
+                    .then(tree_sitter_python::language)
+            },
+            |path, source, tree| Ok(extract_file(path, source.as_bytes(), tree.root_node())),
+        )?;
+        files.extend(parse_notebooks(paths)?);
+
+        // Qualify scripts with their directory so equally named ones
+        // (`tests/conftest.py`, `tests/unit/conftest.py`) stay apart.
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        for file in files.iter_mut().filter(|f| f.loose) {
+            let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+            let dir: Vec<String> = dir
+                .strip_prefix(&root)
+                .unwrap_or(&dir)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            if !dir.is_empty() {
+                let dir = dir.join(".");
+                file.id = join_dotted(&dir, &file.id);
+                file.dir = Some(dir);
+            }
+        }
+
+        let known: HashSet<&str> = files.iter().map(|f| f.id.as_str()).collect();
+        let mut edges = EdgeSet::default();
+        for file in &files {
+            let bindings = resolve_imports(file, &known, &mut edges);
+            for base in &file.bases {
+                if let Some(target) = resolve_base(base, &bindings, &known) {
+                    edges.push(&file.id, target, EdgeKind::Extends);
+                }
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        model.edges = edges.into_edges();
+        model.modules = files
+            .into_iter()
+            .map(|file| Module {
+                name: file.id.rsplit('.').next().unwrap_or(&file.id).to_string(),
+                id: ModuleId(file.id),
+                path: file.path,
+                symbols: file.symbols,
+                doc: file.doc,
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            })
+            .collect();
+
+        Ok(model)
+    }
+}
+
+/// Everything extracted from a single `.py` file (or notebook).
+struct ParsedFile {
+    path: PathBuf,
+    id: String,
+    /// `true` for `__init__.py`, whose relative imports start at itself.
+    is_package: bool,
+    /// `true` for a script outside any package and any `src/` directory.
+    loose: bool,
+    /// Dotted directory of a loose script below the common root, where its
+    /// bare imports resolve first.
+    dir: Option<String>,
+    doc: Option<String>,
+    symbols: Vec<Symbol>,
+    imports: Vec<Import>,
+    /// Dotted base-class expressions of top-level classes.
+    bases: Vec<String>,
+}
+
+/// One `import` / `from ... import` statement.
+enum Import {
+    /// `import a.b.c [as x]`.
+    Module { name: String, alias: Option<String> },
+    /// `from [.]*module import name [as x], ...` or `... import *`.
+    From {
+        level: usize,
+        module: Option<String>,
+        names: Vec<(String, Option<String>)>,
+    },
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let (id, is_package, loose) = module_id(path);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        id,
+        is_package,
+        loose,
+        dir: None,
+        doc: block_docstring(root, src),
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        bases: Vec::new(),
+    };
+    collect_imports(root, src, &mut file.imports);
+
+    let exported = dunder_all(root, src);
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        let (definition, decorators) = match node.kind() {
+            "decorated_definition" => {
+                let Some(def) = node.child_by_field_name("definition") else {
+                    continue;
+                };
+                let mut c = node.walk();
+                let decorators: Vec<String> = node
+                    .named_children(&mut c)
+                    .filter(|d| d.kind() == "decorator")
+                    .map(|d| text(d, src).trim().to_string())
+                    .collect();
+                (def, decorators)
+            }
+            _ => (node, Vec::new()),
+        };
+
+        let symbol = match definition.kind() {
+            "class_definition" => {
+                let bases = superclasses(definition, src);
+                let kind = class_kind(&bases);
+                file.bases.extend(bases);
+                definition_symbol(definition, kind, &decorators, src)
+            }
+            "function_definition" => {
+                definition_symbol(definition, SymbolKind::Function, &decorators, src)
+            }
+            "expression_statement" => assignment_symbol(definition, src),
+            _ => None,
+        };
+
+        if let Some(mut symbol) = symbol {
+            symbol.visibility = visibility(&symbol.name, exported.as_ref());
+            file.symbols.push(symbol);
+        }
+    }
+
+    file
+}
+
+/// Parse, in parallel, the notebooks among `paths` whose kernel is Python.
+fn parse_notebooks(paths: &[PathBuf]) -> Result<Vec<ParsedFile>> {
+    let files: Vec<Option<ParsedFile>> = paths
+        .par_iter()
+        .filter(|p| has_extension(p, &["ipynb"]))
+        .map_init(Parser::new, |parser, path| {
+            let Some(notebook) = Notebook::read(path)? else {
+                return Ok(None);
+            };
+            parser
+                .set_language(tree_sitter_python::language())
+                .map_err(|e| anyhow!("{e}"))?;
+            let tree = parse_source(parser, path, &notebook.source)?;
+            let mut file = extract_file(path, notebook.source.as_bytes(), tree.root_node());
+            for symbol in &mut file.symbols {
+                notebook.locate(symbol);
+            }
+            Ok(Some(file))
+        })
+        .collect::<Result<_>>()?;
+    Ok(files.into_iter().flatten().collect())
+}
+
+/// The Python source of a Jupyter notebook.
+struct Notebook {
+    /// Code cells, concatenated.
+    source: String,
+    /// `(cell number, first line in source)` of every code cell, both
+    /// 1-indexed. Markdown and raw cells count towards the numbering.
+    cells: Vec<(u32, u32)>,
+}
+
+/// Cell magics whose body is still Python.
+const PYTHON_CELL_MAGICS: &[&str] = &["time", "timeit", "capture", "prun", "debug"];
+
+impl Notebook {
+    /// Read an `.ipynb` file; `None` when its kernel is not Python. A
+    /// notebook that is not valid JSON is logged and read as empty.
+    fn read(path: &Path) -> Result<Option<Self>> {
+        let mut notebook = Self {
+            source: String::new(),
+            cells: Vec::new(),
+        };
+        let json: Value = match serde_json::from_str(&read_source(path)?) {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "skipping invalid notebook");
+                return Ok(Some(notebook));
+            }
+        };
+        let metadata = &json["metadata"];
+        let language = metadata["kernelspec"]["language"]
+            .as_str()
+            .or(metadata["language_info"]["name"].as_str());
+        if language.is_some_and(|l| !l.eq_ignore_ascii_case("python")) {
+            return Ok(None);
+        }
+
+        let mut line = 1;
+        let cells = json["cells"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for (number, cell) in (1..).zip(cells) {
+            if cell["cell_type"] != "code" {
+                continue;
+            }
+            // nbformat stores sources as a list of lines or as one string.
+            let source = match &cell["source"] {
+                Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+                Value::String(source) => source.clone(),
+                _ => continue,
+            };
+            let Some(code) = strip_magics(&source) else {
+                continue;
+            };
+            notebook.cells.push((number, line));
+            for code_line in code.lines() {
+                notebook.source.push_str(code_line);
+                notebook.source.push('\n');
+                line += 1;
+            }
+        }
+        Ok(Some(notebook))
+    }
+
+    /// Rewrite a line of [`Notebook::source`] into `cell N, line M`.
+    fn locate(&self, symbol: &mut Symbol) {
+        if let Some(&(cell, start)) = self.cells.iter().rev().find(|(_, s)| *s <= symbol.line) {
+            symbol.cell = Some(cell);
+            symbol.line = symbol.line - start + 1;
+        }
+    }
+}
+
+/// Blank out the IPython-only lines of a code cell, keeping line numbers:
+/// line magics (`%matplotlib inline`), shell escapes (`!pip install ...`),
+/// captures (`files = !ls`) and help queries (`?len`). Cells run by a cell
+/// magic (`%%bash`) are not Python and yield `None`, unless the magic only
+/// instruments the Python body (`%%time`).
+fn strip_magics(cell: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    for (i, line) in cell.lines().enumerate() {
+        let code = line.trim_start();
+        if let Some(magic) = code.strip_prefix("%%") {
+            let name = magic.split_whitespace().next().unwrap_or_default();
+            if i == 0 && !PYTHON_CELL_MAGICS.contains(&name) {
+                return None;
+            }
+            lines.push("");
+            continue;
+        }
+        let capture = code.split_once('=').is_some_and(|(target, value)| {
+            target
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | ',' | ' '))
+                && value.trim_start().starts_with(['!', '%'])
+        });
+        if code.starts_with(['%', '!', '?']) || capture {
+            lines.push("");
+        } else {
+            lines.push(line);
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// Dotted module id for `path`, whether it is a package `__init__`, and
+/// whether it is a loose script.
+///
+/// Directories are prepended for as long as they contain `__init__.py`.
+/// Files outside any package fall back to their path below the nearest
+/// `src/` directory (namespace packages), or else to their bare stem, which
+/// the caller qualifies with the directory.
+fn module_id(path: &Path) -> (String, bool, bool) {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let is_package = stem == "__init__";
+
+    let mut segments = Vec::new();
+    if !is_package {
+        segments.push(stem);
+    }
+    let mut dir = path.parent();
+    while let Some(d) = dir {
+        let Some(name) = d.file_name() else { break };
+        if !d.join("__init__.py").is_file() {
+            break;
+        }
+        segments.push(name.to_string_lossy().into_owned());
+        dir = d.parent();
+    }
+
+    let in_package = dir != path.parent();
+    let in_src = path.parent().is_some_and(|parent| {
+        parent
+            .ancestors()
+            .any(|a| a.file_name() == Some("src".as_ref()))
+    });
+    if let Some(parent) = path.parent().filter(|_| !in_package && in_src) {
+        segments.extend(
+            parent
+                .ancestors()
+                .take_while(|a| a.file_name() != Some("src".as_ref()))
+                .filter_map(|a| a.file_name().map(|n| n.to_string_lossy().into_owned())),
+        );
+    }
+
+    segments.reverse();
+    (segments.join("."), is_package, !in_package && !in_src)
+}
+
+/// Walk the whole tree (imports may sit inside functions, `try` blocks or
+/// `if TYPE_CHECKING:`) and record every import statement.
+fn collect_imports(node: Node<'_>, src: &[u8], out: &mut Vec<Import>) {
+    match node.kind() {
+        "import_statement" => {
+            let mut cursor = node.walk();
+            for name in node.children_by_field_name("name", &mut cursor) {
+                let (name, alias) = aliased(name, src);
+                out.push(Import::Module { name, alias });
+            }
+        }
+        "import_from_statement" => {
+            let Some(module) = node.child_by_field_name("module_name") else {
+                return;
+            };
+            let (level, module) = match module.kind() {
+                "relative_import" => {
+                    let mut c = module.walk();
+                    let children: Vec<_> = module.named_children(&mut c).collect();
+                    let level = children
+                        .iter()
+                        .find(|n| n.kind() == "import_prefix")
+                        .map_or(0, |n| text(*n, src).trim().len());
+                    let name = children
+                        .iter()
+                        .find(|n| n.kind() == "dotted_name")
+                        .map(|n| text(*n, src).to_string());
+                    (level, name)
+                }
+                _ => (0, Some(text(module, src).to_string())),
+            };
+            let mut cursor = node.walk();
+            let names = node
+                .children_by_field_name("name", &mut cursor)
+                .map(|n| aliased(n, src))
+                .collect();
+            out.push(Import::From {
+                level,
+                module,
+                names,
+            });
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_imports(child, src, out);
+            }
+        }
+    }
+}
+
+/// `(name, alias)` for a `dotted_name` or `aliased_import` node.
+fn aliased(node: Node<'_>, src: &[u8]) -> (String, Option<String>) {
+    match node.kind() {
+        "aliased_import" => (
+            node.child_by_field_name("name")
+                .map(|n| text(n, src).to_string())
+                .unwrap_or_default(),
+            node.child_by_field_name("alias")
+                .map(|n| text(n, src).to_string()),
+        ),
+        _ => (text(node, src).to_string(), None),
     }
 }
+
+/// Turn `file.imports` into edges and return the local-name → module
+/// bindings they introduce (used to resolve base classes).
+fn resolve_imports<'a>(
+    file: &ParsedFile,
+    known: &HashSet<&'a str>,
+    edges: &mut EdgeSet,
+) -> HashMap<String, &'a str> {
+    let mut bindings = HashMap::new();
+    for import in &file.imports {
+        match import {
+            Import::Module { name, alias } => {
+                let Some(target) = resolve_dotted(&absolute(file, name, known), known) else {
+                    continue;
+                };
+                edges.push(&file.id, target, EdgeKind::Import);
+                match alias {
+                    Some(alias) => {
+                        bindings.insert(alias.clone(), target);
+                    }
+                    None => {
+                        let head = name.split('.').next().unwrap_or(name);
+                        if let Some(&top) = known.get(absolute(file, head, known).as_str()) {
+                            bindings.insert(head.to_string(), top);
+                        }
+                    }
+                }
+            }
+            Import::From {
+                level,
+                module,
+                names,
+            } => {
+                let Some(mut base) = from_base(file, *level, module.as_deref()) else {
+                    continue;
+                };
+                if *level == 0 {
+                    base = absolute(file, &base, known);
+                }
+                let resolved_base = resolve_dotted(&base, known);
+                if names.is_empty() {
+                    // `from x import *`.
+                    if let Some(target) = resolved_base {
+                        edges.push(&file.id, target, EdgeKind::Import);
+                    }
+                }
+                for (name, alias) in names {
+                    let submodule = join_dotted(&base, name);
+                    let target = known.get(submodule.as_str()).copied().or(resolved_base);
+                    if let Some(target) = target {
+                        edges.push(&file.id, target, EdgeKind::Import);
+                        bindings.insert(alias.clone().unwrap_or_else(|| name.clone()), target);
+                    }
+                }
+            }
+        }
+    }
+    bindings
+}
+
+/// `name` as a bare import of `file` means it: a sibling of a loose script
+/// (`import util` in `scripts/main.py` is `scripts.util`), or else `name`.
+fn absolute(file: &ParsedFile, name: &str, known: &HashSet<&str>) -> String {
+    let head = name.split('.').next().unwrap_or(name);
+    match &file.dir {
+        Some(dir) if known.contains(join_dotted(dir, head).as_str()) => join_dotted(dir, name),
+        _ => name.to_string(),
+    }
+}
+
+/// Absolute dotted module named by a `from` clause, applying relative
+/// `level`s against the importing module's package.
+fn from_base(file: &ParsedFile, level: usize, module: Option<&str>) -> Option<String> {
+    if level == 0 {
+        return module.map(String::from);
+    }
+    let mut package: Vec<&str> = file.id.split('.').filter(|s| !s.is_empty()).collect();
+    if !file.is_package {
+        package.pop();
+    }
+    for _ in 1..level {
+        package.pop()?;
+    }
+    let package = package.join(".");
+    Some(match module {
+        Some(module) => join_dotted(&package, module),
+        None => package,
+    })
+}
+
+fn join_dotted(base: &str, name: &str) -> String {
+    if base.is_empty() {
+        name.to_string()
+    } else {
+        format!("{base}.{name}")
+    }
+}
+
+/// Longest dotted prefix of `name` that is a known module.
+fn resolve_dotted<'a>(name: &str, known: &HashSet<&'a str>) -> Option<&'a str> {
+    let segments: Vec<&str> = name.split('.').collect();
+    (1..=segments.len())
+        .rev()
+        .find_map(|n| known.get(segments[..n].join(".").as_str()).copied())
+}
+
+/// Resolve a base-class expression through the file's import bindings,
+/// falling back to treating it as an absolute dotted path.
+fn resolve_base<'a>(
+    base: &str,
+    bindings: &HashMap<String, &'a str>,
+    known: &HashSet<&'a str>,
+) -> Option<&'a str> {
+    let (head, rest) = base.split_once('.').unwrap_or((base, ""));
+    match bindings.get(head) {
+        Some(&module) if rest.is_empty() => Some(module),
+        Some(&module) => resolve_dotted(&join_dotted(module, rest), known).or(Some(module)),
+        None => resolve_dotted(base, known).filter(|_| !rest.is_empty()),
+    }
+}
+
+/// Positional superclasses of a class, with subscripts (`Generic[T]`)
+/// stripped. Keyword arguments such as `metaclass=` are skipped.
+fn superclasses(class: Node<'_>, src: &[u8]) -> Vec<String> {
+    let Some(args) = class.child_by_field_name("superclasses") else {
+        return Vec::new();
+    };
+    let mut cursor = args.walk();
+    args.named_children(&mut cursor)
+        .filter(|n| matches!(n.kind(), "identifier" | "attribute" | "subscript"))
+        .map(|n| {
+            let t = text(n, src);
+            t.split('[').next().unwrap_or(t).trim().to_string()
+        })
+        .collect()
+}
+
+/// Protocols and ABCs are traits, `Enum` subclasses are enums, everything
+/// else is a struct.
+fn class_kind(bases: &[String]) -> SymbolKind {
+    let last = |b: &String| b.rsplit('.').next().unwrap_or(b).to_string();
+    if bases
+        .iter()
+        .map(last)
+        .any(|b| matches!(b.as_str(), "Protocol" | "ABC"))
+    {
+        SymbolKind::Trait
+    } else if bases.iter().map(last).any(|b| {
+        matches!(
+            b.as_str(),
+            "Enum" | "IntEnum" | "StrEnum" | "Flag" | "IntFlag"
+        )
+    }) {
+        SymbolKind::Enum
+    } else {
+        SymbolKind::Struct
+    }
+}
+
+fn definition_symbol(
+    def: Node<'_>,
+    kind: SymbolKind,
+    decorators: &[String],
+    src: &[u8],
+) -> Option<Symbol> {
+    let name = def.child_by_field_name("name")?;
+    let body = def.child_by_field_name("body");
+    let mut signature = decorators.join(" ");
+    if !signature.is_empty() {
+        signature.push(' ');
+    }
+    signature.push_str(header(def, body, src).trim_end_matches([':', ' ']));
+
+    Some(Symbol {
+        name: text(name, src).to_string(),
+        kind,
+        visibility: Visibility::Public,
+        line: line(def),
+        cell: None,
+        doc: body.and_then(|b| block_docstring(b, src)),
+        signature: Some(signature),
+        members: Vec::new(),
+    })
+}
+
+/// Module-level `UPPER_CASE = ...` constants and `X: TypeAlias = ...`.
+fn assignment_symbol(stmt: Node<'_>, src: &[u8]) -> Option<Symbol> {
+    let assignment = stmt.named_child(0).filter(|n| n.kind() == "assignment")?;
+    let left = assignment
+        .child_by_field_name("left")
+        .filter(|n| n.kind() == "identifier")?;
+    let name = text(left, src);
+    let annotation = assignment.child_by_field_name("type");
+
+    let kind = if annotation.is_some_and(|t| text(t, src).ends_with("TypeAlias")) {
+        SymbolKind::TypeAlias
+    } else if is_constant_name(name) {
+        SymbolKind::Constant
+    } else {
+        return None;
+    };
+    let signature = header(assignment, assignment.child_by_field_name("right"), src)
+        .trim_end_matches(['=', ' '])
+        .to_string();
+
+    Some(Symbol {
+        name: name.to_string(),
+        kind,
+        visibility: Visibility::Public,
+        line: line(stmt),
+        cell: None,
+        doc: None,
+        signature: Some(signature),
+        members: Vec::new(),
+    })
+}
+
+fn is_constant_name(name: &str) -> bool {
+    let bare = name.trim_start_matches('_');
+    bare.chars().any(|c| c.is_ascii_uppercase())
+        && bare
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Names listed in a module-level `__all__`, if the module declares one.
+fn dunder_all(root: Node<'_>, src: &[u8]) -> Option<HashSet<String>> {
+    let mut names: Option<HashSet<String>> = None;
+    let mut cursor = root.walk();
+    for stmt in root.named_children(&mut cursor) {
+        let Some(assign) = stmt
+            .named_child(0)
+            .filter(|n| matches!(n.kind(), "assignment" | "augmented_assignment"))
+        else {
+            continue;
+        };
+        if assign
+            .child_by_field_name("left")
+            .is_none_or(|l| text(l, src) != "__all__")
+        {
+            continue;
+        }
+        let Some(value) = assign.child_by_field_name("right") else {
+            continue;
+        };
+        let set = names.get_or_insert_with(HashSet::new);
+        let mut c = value.walk();
+        for item in value.named_children(&mut c) {
+            if item.kind() == "string" {
+                set.insert(strip_quotes(text(item, src)).to_string());
+            }
+        }
+    }
+    names
+}
+
+/// `__all__` wins when present; otherwise a leading underscore (but not a
+/// dunder) marks a name private.
+fn visibility(name: &str, exported: Option<&HashSet<String>>) -> Visibility {
+    let public = match exported {
+        Some(all) => all.contains(name),
+        None => !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__")),
+    };
+    if public {
+        Visibility::Public
+    } else {
+        Visibility::Private
+    }
+}
+
+/// Docstring of a module or block: its first statement, if that is a bare
+/// string literal.
+fn block_docstring(block: Node<'_>, src: &[u8]) -> Option<String> {
+    let first = block.named_child(0)?;
+    let string = first
+        .named_child(0)
+        .filter(|n| first.kind() == "expression_statement" && n.kind() == "string")?;
+    clean_docstring(strip_quotes(text(string, src)))
+}
+
+fn strip_quotes(literal: &str) -> &str {
+    let body = literal.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    for quote in ["\"\"\"", "'''", "\"", "'"] {
+        if let Some(inner) = body.strip_prefix(quote).and_then(|b| b.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    body
+}
+
+/// Equivalent of `inspect.cleandoc`: trim the first line, dedent the rest by
+/// their common indentation, drop surrounding blank lines.
+fn clean_docstring(raw: &str) -> Option<String> {
+    let mut lines = raw.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    let rest: Vec<&str> = lines.collect();
+    let indent = rest
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    let mut out = vec![first.to_string()];
+    out.extend(
+        rest.iter()
+            .map(|l| l.get(indent..).unwrap_or("").trim_end().to_string()),
+    );
+    let doc = out.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
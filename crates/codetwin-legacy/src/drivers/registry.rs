@@ -2,7 +2,13 @@
 
 use std::path::Path;
 
-use super::{Driver, GoDriver, PythonDriver, RustDriver, TypeScriptDriver};
+use super::{
+    CSharpDriver, CppDriver, DockerDriver, Driver, ElixirDriver, ExternalDriver, GoDriver,
+    GraphqlDriver, JavaDriver, KotlinDriver, OpenApiDriver, PhpDriver, ProtobufDriver,
+    PythonDriver, RubyDriver, RustDriver, ScalaDriver, SqlDriver, SwiftDriver, TerraformDriver,
+    TypeScriptDriver, ZigDriver,
+};
+use crate::config::PluginDriverSpec;
 
 /// Ordered collection of drivers.
 ///
@@ -20,6 +26,22 @@ impl Default for DriverRegistry {
         r.register(Box::new(PythonDriver));
         r.register(Box::new(TypeScriptDriver));
         r.register(Box::new(GoDriver));
+        r.register(Box::new(JavaDriver));
+        r.register(Box::new(KotlinDriver));
+        r.register(Box::new(CSharpDriver));
+        r.register(Box::new(CppDriver));
+        r.register(Box::new(RubyDriver));
+        r.register(Box::new(PhpDriver));
+        r.register(Box::new(SwiftDriver));
+        r.register(Box::new(ElixirDriver));
+        r.register(Box::new(ScalaDriver));
+        r.register(Box::new(ZigDriver));
+        r.register(Box::new(ProtobufDriver));
+        r.register(Box::new(OpenApiDriver));
+        r.register(Box::new(SqlDriver));
+        r.register(Box::new(TerraformDriver));
+        r.register(Box::new(DockerDriver));
+        r.register(Box::new(GraphqlDriver));
         r
     }
 }
@@ -38,11 +60,31 @@ impl DriverRegistry {
         self.drivers.push(driver);
     }
 
+    /// The built-in drivers followed by one [`ExternalDriver`] per
+    /// `[[plugins.drivers]]` entry, run in `project_root`. A plugin whose
+    /// name is already registered is skipped with a warning.
+    pub fn with_plugins(plugins: &[PluginDriverSpec], project_root: &Path) -> Self {
+        let mut r = Self::default();
+        for spec in plugins {
+            if r.get(&spec.name).is_some() {
+                tracing::warn!(driver = %spec.name, "plugin name already registered; skipping");
+                continue;
+            }
+            r.register(Box::new(ExternalDriver::new(spec, project_root)));
+        }
+        r
+    }
+
     /// Names of every registered driver.
     pub fn names(&self) -> Vec<&'static str> {
         self.drivers.iter().map(|d| d.name()).collect()
     }
 
+    /// Every registered driver, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Driver> {
+        self.drivers.iter().map(|d| d.as_ref())
+    }
+
     /// Return the drivers whose [`Driver::detect`] returns `true` for
     /// `project_root`.
     pub fn detect_all<'a>(&'a self, project_root: &Path) -> Vec<&'a dyn Driver> {
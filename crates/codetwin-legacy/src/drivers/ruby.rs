@@ -0,0 +1,1051 @@
+//! Ruby driver (scanner-based).
+//!
+//! The only Ruby grammar available is built against `tree-sitter` 0.19, so
+//! this driver runs the shared [`scanner`](super::scanner) tokenizer over
+//! the source (with heredoc bodies, `=begin` blocks and `%w[]`-style
+//! literals blanked out first) and tracks `class` / `module` / `def` / ...
+//! `end` nesting itself.
+//!
+//! Every `.rb` file (and extension-less `#!/usr/bin/env ruby` script)
+//! becomes one [`Module`] with a path-derived id (`app/models/user`,
+//! `lib/acme/billing/invoice`). Modules, classes,
+//! constants and methods are extracted as symbols: instance methods as
+//! `Class#name`, singleton methods as `Class.name`. Methods follow the
+//! `private` / `protected` / `public` section they are defined in, and the
+//! `private :name` / `private def name` forms.
+//!
+//! Superclasses become [`EdgeKind::Extends`] edges and `include` /
+//! `extend` / `prepend` mixins [`EdgeKind::Implements`] edges. `require`
+//! and `require_relative` resolve to files in the model, and so do constant
+//! references, Zeitwerk style: below an autoload root (`lib/`, and every
+//! `app/*` directory of a Rails app) `acme/billing/invoice.rb` defines
+//! `Acme::Billing::Invoice`, so referencing that constant imports the file.
+//! Both become [`EdgeKind::Import`] edges; requires outside the model are
+//! kept as [`ExternalImport`]s.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{
+    EdgeSet, collapse_whitespace, common_root, has_extension, join_doc, normalize_path,
+    relative_module_id, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Ruby driver (detects a `Gemfile` or `*.gemspec`).
+#[derive(Default)]
+pub struct RubyDriver;
+
+impl Driver for RubyDriver {
+    fn name(&self) -> &'static str {
+        "ruby"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("Gemfile").is_file()
+            || std::fs::read_dir(project_root).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| has_extension(&e.path(), &["gemspec"]) && e.path().is_file())
+            })
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            interpreters: &["ruby"],
+            ..FileClaims::extensions(&["rb"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| has_extension(p, &["rb"]) || self.claims().owns_script(p),
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        let ids: Vec<String> = files
+            .iter()
+            .map(|f| relative_module_id(&f.path, &root))
+            .collect();
+
+        // Constant → defining module. Below an autoload root a file owns the
+        // constant its path names (plus whatever it nests inside it); other
+        // files own everything they define.
+        let mut constants: HashMap<String, &str> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            match autoload_constant(&file.path, &root) {
+                Some(expected) => {
+                    let nested = format!("{expected}::");
+                    for name in file.defined.iter().filter(|n| n.starts_with(&nested)) {
+                        constants.entry(name.clone()).or_insert(id);
+                    }
+                    constants.insert(expected, id);
+                }
+                None => {
+                    for name in &file.defined {
+                        constants.entry(name.clone()).or_insert(id);
+                    }
+                }
+            }
+        }
+        let by_path: HashMap<PathBuf, &str> = files
+            .iter()
+            .zip(&ids)
+            .map(|(f, id)| (normalize_path(&f.path), id.as_str()))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let mut external_imports = Vec::new();
+            let mut seen = HashSet::new();
+
+            for require in &file.requires {
+                match resolve_require(&file.path, require, &by_path) {
+                    Some(target) => edges.push(id, target, EdgeKind::Import),
+                    None if !require.relative && seen.insert(require.feature.as_str()) => {
+                        external_imports.push(ExternalImport {
+                            path: require.feature.clone(),
+                            kind: classify(&require.feature),
+                        });
+                    }
+                    None => {}
+                }
+            }
+            for reference in &file.references {
+                if let Some(target) =
+                    resolve_constant(&constants, &reference.name, &reference.nesting)
+                {
+                    edges.push(id, target, reference.kind);
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file.symbols.clone(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &["#"],
+    line_comments: &[],
+    block_comment: None,
+    doc_block_comment: None,
+    nested_block_comments: false,
+    quotes: &['"', '\'', '`'],
+    triple_quoted_strings: false,
+    verbatim_strings: false,
+    ident_chars: &['?', '!', '@', '$'],
+};
+
+/// Everything extracted from a single `.rb` file.
+struct ParsedFile {
+    path: PathBuf,
+    symbols: Vec<Symbol>,
+    /// Fully-qualified classes, modules and constants defined in the file.
+    defined: Vec<String>,
+    requires: Vec<Require>,
+    references: Vec<Reference>,
+}
+
+/// `require "feature"` / `require_relative "path"`.
+struct Require {
+    feature: String,
+    relative: bool,
+}
+
+/// A constant named in the file, with the lexical nesting it appears in
+/// (outermost first) and the edge it implies once resolved.
+struct Reference {
+    name: String,
+    nesting: Vec<String>,
+    kind: EdgeKind,
+}
+
+/// `end`-terminated scopes the scanner tracks.
+enum Block {
+    /// `class` / `module` body, or `class << self` (`singleton`) inside one.
+    Namespace {
+        name: String,
+        singleton: bool,
+        /// Visibility set by the last bare `private` / `protected` /
+        /// `public`.
+        visibility: Visibility,
+        /// `private :name`-style changes, applied when the body closes.
+        changes: Vec<(String, Visibility)>,
+    },
+    Def,
+    /// `do`, `if`, `begin`, `case`, ... blocks.
+    Other,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let masked = mask_literals(src);
+    let src = masked.as_str();
+    let tokens = tokenize(src, &SYNTAX);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        symbols: Vec::new(),
+        defined: Vec::new(),
+        requires: Vec::new(),
+        references: Vec::new(),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+    // Line of a `while` / `until` / `for` whose optional `do` is still to
+    // come.
+    let mut loop_line = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if token.kind == TokenKind::Str || token.kind == TokenKind::Doc {
+            i += 1;
+            continue;
+        }
+        let nesting: Vec<String> = stack
+            .iter()
+            .filter_map(|b| match b {
+                Block::Namespace {
+                    name,
+                    singleton: false,
+                    ..
+                } => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        let in_namespace = matches!(stack.last(), Some(Block::Namespace { .. }));
+        let at_top = stack.is_empty() || in_namespace;
+        let keyword = is_keyword(&tokens, i);
+
+        match token.text {
+            "class" if keyword && is_punct_pair(&tokens, i + 1, "<") => {
+                // `class << self`: singleton methods of the enclosing class.
+                stack.push(Block::Namespace {
+                    name: nesting.last().cloned().unwrap_or_default(),
+                    singleton: true,
+                    visibility: Visibility::Public,
+                    changes: Vec::new(),
+                });
+                i += 4;
+                continue;
+            }
+            "class" | "module" if keyword => {
+                let Some((name, mut end)) = constant_path(&tokens, i + 1) else {
+                    stack.push(Block::Other);
+                    i += 1;
+                    continue;
+                };
+                let full = match (name.strip_prefix("::"), nesting.last()) {
+                    (Some(absolute), _) => absolute.to_string(),
+                    (None, Some(outer)) => format!("{outer}::{name}"),
+                    (None, None) => name,
+                };
+                let is_class = token.text == "class";
+                if is_class
+                    && tokens.get(end).is_some_and(|t| t.is("<"))
+                    && let Some((superclass, after)) = constant_path(&tokens, end + 1)
+                {
+                    file.references.push(Reference {
+                        name: superclass,
+                        nesting: nesting.clone(),
+                        kind: EdgeKind::Extends,
+                    });
+                    end = after;
+                }
+                let signature_end = tokens[end - 1].offset + tokens[end - 1].text.len();
+                file.symbols.push(Symbol {
+                    name: full.clone(),
+                    kind: if is_class {
+                        SymbolKind::Struct
+                    } else {
+                        SymbolKind::Module
+                    },
+                    visibility: Visibility::Public,
+                    line: token.line,
+                    cell: None,
+                    doc: ruby_doc(&tokens, i),
+                    signature: Some(collapse_whitespace(&src[token.offset..signature_end])),
+                    members: Vec::new(),
+                });
+                file.defined.push(full.clone());
+                stack.push(Block::Namespace {
+                    name: full,
+                    singleton: false,
+                    visibility: Visibility::Public,
+                    changes: Vec::new(),
+                });
+                i = end;
+                continue;
+            }
+            "def" if keyword => {
+                let (symbol, end, endless) = method(&tokens, i, &stack, src);
+                if let Some(symbol) = symbol.filter(|_| at_top) {
+                    file.symbols.push(symbol);
+                }
+                if !endless {
+                    stack.push(Block::Def);
+                }
+                i = end;
+                continue;
+            }
+            "if" | "unless" | "while" | "until" if keyword && opens_block(&tokens, i) => {
+                if matches!(token.text, "while" | "until") {
+                    loop_line = Some(token.line);
+                }
+                stack.push(Block::Other);
+            }
+            "for" if keyword => {
+                loop_line = Some(token.line);
+                stack.push(Block::Other);
+            }
+            // The optional `do` of `while cond do` opens nothing.
+            "do" if keyword && loop_line.take() != Some(token.line) => stack.push(Block::Other),
+            "begin" | "case" if keyword => stack.push(Block::Other),
+            "end" if keyword => {
+                if let Some(Block::Namespace { changes, .. }) = stack.pop() {
+                    for (name, visibility) in changes {
+                        for symbol in file.symbols.iter_mut().filter(|s| s.name == name) {
+                            symbol.visibility = visibility;
+                        }
+                    }
+                }
+            }
+            "private" | "protected" | "public" | "private_class_method" | "private_constant"
+                if keyword && in_namespace && starts_statement(&tokens, i) =>
+            {
+                let names = symbol_args(&tokens, i + 1);
+                let bare = tokens
+                    .get(i + 1)
+                    .is_none_or(|next| next.line != token.line || next.is(";"));
+                if let Some(Block::Namespace {
+                    name: owner,
+                    singleton,
+                    visibility,
+                    changes,
+                }) = stack.last_mut()
+                {
+                    let (target, separator) = match token.text {
+                        "private_class_method" => (Visibility::Private, "."),
+                        "private_constant" => (Visibility::Private, "::"),
+                        "private" => (Visibility::Private, if *singleton { "." } else { "#" }),
+                        "protected" => (Visibility::Protected, if *singleton { "." } else { "#" }),
+                        _ => (Visibility::Public, if *singleton { "." } else { "#" }),
+                    };
+                    if bare && matches!(token.text, "private" | "protected" | "public") {
+                        *visibility = target;
+                    }
+                    changes.extend(
+                        names
+                            .into_iter()
+                            .map(|name| (format!("{owner}{separator}{name}"), target)),
+                    );
+                }
+            }
+            "include" | "extend" | "prepend" if keyword && in_namespace => {
+                let mut j = i + 1;
+                if tokens.get(j).is_some_and(|t| t.is("(")) {
+                    j += 1;
+                }
+                while let Some((name, end)) = constant_path(&tokens, j) {
+                    file.references.push(Reference {
+                        name,
+                        nesting: nesting.clone(),
+                        kind: EdgeKind::Implements,
+                    });
+                    j = end;
+                    if !tokens.get(j).is_some_and(|t| t.is(",")) {
+                        break;
+                    }
+                    j += 1;
+                }
+                i = j;
+                continue;
+            }
+            "require" | "require_relative" if keyword => {
+                let mut j = i + 1;
+                if tokens.get(j).is_some_and(|t| t.is("(")) {
+                    j += 1;
+                }
+                if let Some(feature) = tokens.get(j).and_then(string_literal) {
+                    file.requires.push(Require {
+                        feature,
+                        relative: token.text == "require_relative",
+                    });
+                }
+            }
+            text if is_constant(text) && !follows_dot_or_def(&tokens, i) => {
+                let Some((name, end)) = constant_path(&tokens, i) else {
+                    i += 1;
+                    continue;
+                };
+                let assigned = tokens.get(end).is_some_and(|t| t.is("="))
+                    && !tokens.get(end + 1).is_some_and(|n| {
+                        adjacent(&tokens[end], n) && (n.is("=") || n.is("~") || n.is(">"))
+                    });
+                if assigned && at_top && starts_statement(&tokens, i) && !name.contains("::") {
+                    let full = match nesting.last() {
+                        Some(outer) => format!("{outer}::{name}"),
+                        None => name,
+                    };
+                    file.symbols.push(Symbol {
+                        name: full.clone(),
+                        kind: SymbolKind::Constant,
+                        visibility: Visibility::Public,
+                        line: token.line,
+                        cell: None,
+                        doc: ruby_doc(&tokens, i),
+                        signature: Some(full.clone()),
+                        members: Vec::new(),
+                    });
+                    file.defined.push(full);
+                } else {
+                    file.references.push(Reference {
+                        name,
+                        nesting,
+                        kind: EdgeKind::Import,
+                    });
+                }
+                i = end;
+                continue;
+            }
+            // `::Foo` — an absolute constant reference.
+            ":" if is_punct_pair(&tokens, i, ":")
+                && tokens.get(i + 2).is_some_and(|t| is_constant(t.text)) =>
+            {
+                if let Some((name, end)) = constant_path(&tokens, i) {
+                    file.references.push(Reference {
+                        name,
+                        nesting,
+                        kind: EdgeKind::Import,
+                    });
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// Parse the method whose `def` is at `keyword`. Returns its symbol, the
+/// index just past its signature and whether it is an endless
+/// (`def name = expr`) method with no `end` to wait for.
+fn method(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    stack: &[Block],
+    src: &str,
+) -> (Option<Symbol>, usize, bool) {
+    let mut j = keyword + 1;
+    // `def self.name` / `def obj.name`.
+    let mut singleton = false;
+    if tokens.get(j + 1).is_some_and(|t| t.is(".")) && tokens.get(j).is_some_and(|t| t.is_ident()) {
+        singleton = true;
+        j += 2;
+    }
+    let Some(first) = tokens.get(j) else {
+        return (None, j, true);
+    };
+    // Operator and setter names span several adjacent tokens (`[]=`,
+    // `name=`, `<=>`).
+    let mut name = first.text.to_string();
+    let mut end = j + 1;
+    while let Some(t) = tokens.get(end) {
+        let operator = t.kind == TokenKind::Punct && "[]=<>+-*/%~^&|".contains(t.text);
+        if !operator || !adjacent(&tokens[end - 1], t) {
+            break;
+        }
+        name.push_str(t.text);
+        end += 1;
+    }
+    let signature_end = if tokens.get(end).is_some_and(|t| t.is("(")) {
+        end = matching(tokens, end) + 1;
+        end
+    } else {
+        // Parameters without parentheses run to the end of the line.
+        while tokens
+            .get(end)
+            .is_some_and(|t| t.line == first.line && !t.is(";") && !t.is("="))
+        {
+            end += 1;
+        }
+        end
+    };
+    let endless = tokens
+        .get(end)
+        .is_some_and(|t| t.is("=") && t.line == tokens[signature_end - 1].line);
+
+    // A modifier on the same line (`private def name`) wins over the
+    // section's visibility.
+    let modifier = keyword
+        .checked_sub(1)
+        .map(|p| tokens[p])
+        .filter(|p| p.line == tokens[keyword].line)
+        .and_then(|p| match p.text {
+            "private" | "private_class_method" => Some(Visibility::Private),
+            "protected" => Some(Visibility::Protected),
+            "public" => Some(Visibility::Public),
+            _ => None,
+        });
+    let start = if modifier.is_some() {
+        keyword - 1
+    } else {
+        keyword
+    };
+
+    let (name, visibility) = match stack.last() {
+        Some(Block::Namespace {
+            name: owner,
+            singleton: in_singleton_class,
+            visibility,
+            ..
+        }) => {
+            let section = if singleton && !in_singleton_class {
+                // `private` sections do not apply to `def self.name`.
+                Visibility::Public
+            } else {
+                *visibility
+            };
+            let separator = if singleton || *in_singleton_class {
+                "."
+            } else {
+                "#"
+            };
+            (
+                format!("{owner}{separator}{name}"),
+                modifier.unwrap_or(section),
+            )
+        }
+        // Top-level methods are private methods of `Object`.
+        _ => (name, modifier.unwrap_or(Visibility::Private)),
+    };
+
+    let last = tokens[signature_end - 1];
+    let symbol = Symbol {
+        name,
+        kind: SymbolKind::Function,
+        visibility,
+        line: tokens[keyword].line,
+        cell: None,
+        doc: ruby_doc(tokens, start),
+        signature: Some(collapse_whitespace(
+            &src[tokens[keyword].offset..last.offset + last.text.len()],
+        )),
+        members: Vec::new(),
+    };
+    (Some(symbol), end, endless)
+}
+
+/// `tokens[i]` is a keyword, not a method call (`x.class`), a method name
+/// (`def end`), a symbol (`:end`) or a label (`if:`).
+fn is_keyword(tokens: &[Token<'_>], i: usize) -> bool {
+    let token = tokens[i];
+    if !token.is_ident() {
+        return false;
+    }
+    if let Some(prev) = i.checked_sub(1).map(|p| tokens[p]) {
+        let symbol = prev.is(":")
+            && adjacent(&prev, &token)
+            && !i
+                .checked_sub(2)
+                .is_some_and(|pp| tokens[pp].is(":") && adjacent(&tokens[pp], &prev));
+        if prev.is(".") || prev.is("def") || symbol {
+            return false;
+        }
+    }
+    let label = tokens.get(i + 1).is_some_and(|next| {
+        next.is(":") && adjacent(&token, next) && !is_punct_pair(tokens, i + 1, ":")
+    });
+    !label
+}
+
+/// `if` / `unless` / `while` / `until` open a block unless they follow an
+/// expression on the same line (`return if done`).
+fn opens_block(tokens: &[Token<'_>], i: usize) -> bool {
+    let Some(prev) = i.checked_sub(1).map(|p| tokens[p]) else {
+        return true;
+    };
+    if prev.line < tokens[i].line || prev.kind == TokenKind::Doc {
+        return true;
+    }
+    match prev.kind {
+        TokenKind::Punct => !matches!(prev.text, ")" | "]" | "}"),
+        TokenKind::Ident => matches!(
+            prev.text,
+            "then" | "do" | "else" | "begin" | "and" | "or" | "not"
+        ),
+        _ => false,
+    }
+}
+
+fn starts_statement(tokens: &[Token<'_>], i: usize) -> bool {
+    i.checked_sub(1)
+        .map(|p| tokens[p])
+        .is_none_or(|prev| prev.line < tokens[i].line || prev.is(";"))
+}
+
+fn follows_dot_or_def(tokens: &[Token<'_>], i: usize) -> bool {
+    i.checked_sub(1).is_some_and(|p| {
+        let prev = tokens[p];
+        // `:Foo` is a symbol, `::Foo` a constant.
+        let symbol = prev.is(":")
+            && adjacent(&prev, &tokens[i])
+            && !p
+                .checked_sub(1)
+                .is_some_and(|pp| is_punct_pair(tokens, pp, ":"));
+        prev.is(".") || prev.is("def") || symbol
+    })
+}
+
+/// Two adjacent `text` punctuation tokens at `i` (`::`, `<<`).
+fn is_punct_pair(tokens: &[Token<'_>], i: usize, text: &str) -> bool {
+    match (tokens.get(i), tokens.get(i + 1)) {
+        (Some(a), Some(b)) => a.is(text) && b.is(text) && adjacent(a, b),
+        _ => false,
+    }
+}
+
+fn adjacent(a: &Token<'_>, b: &Token<'_>) -> bool {
+    a.offset + a.text.len() == b.offset
+}
+
+fn is_constant(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// `Foo`, `Foo::Bar` or `::Foo::Bar` starting at `start`; returns the path
+/// and the index just past it.
+fn constant_path(tokens: &[Token<'_>], start: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut i = start;
+    if is_punct_pair(tokens, i, ":") {
+        name.push_str("::");
+        i += 2;
+    }
+    loop {
+        let segment = tokens
+            .get(i)
+            .filter(|t| t.is_ident() && is_constant(t.text))?;
+        name.push_str(segment.text);
+        i += 1;
+        let continues =
+            is_punct_pair(tokens, i, ":") && tokens.get(i + 2).is_some_and(|t| is_constant(t.text));
+        if !continues {
+            return Some((name, i));
+        }
+        name.push_str("::");
+        i += 2;
+    }
+}
+
+/// `:name, :other` (optionally parenthesised) after a visibility keyword.
+fn symbol_args(tokens: &[Token<'_>], start: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut i = start;
+    if tokens.get(i).is_some_and(|t| t.is("(")) {
+        i += 1;
+    }
+    while let (Some(colon), Some(name)) = (tokens.get(i), tokens.get(i + 1)) {
+        if !colon.is(":") || !adjacent(colon, name) {
+            break;
+        }
+        match name.kind {
+            TokenKind::Ident => names.push(name.text.to_string()),
+            TokenKind::Str => names.extend(string_literal(name)),
+            _ => break,
+        }
+        i += 2;
+        if !tokens.get(i).is_some_and(|t| t.is(",")) {
+            break;
+        }
+        i += 1;
+    }
+    names
+}
+
+/// Contents of a string literal without interpolation.
+fn string_literal(token: &Token<'_>) -> Option<String> {
+    if token.kind != TokenKind::Str || token.text.contains("#{") {
+        return None;
+    }
+    let inner = token.text.get(1..token.text.len().saturating_sub(1))?;
+    (!inner.is_empty()).then(|| inner.to_string())
+}
+
+/// `#` comment lines directly above `tokens[start]`, magic comments
+/// (`# frozen_string_literal: true`) left out.
+fn ruby_doc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    const MAGIC: &[&str] = &[
+        "frozen_string_literal:",
+        "encoding:",
+        "coding:",
+        "warn_indent:",
+        "shareable_constant_value:",
+        "typed:",
+        "rubocop:",
+    ];
+    let mut lines = Vec::new();
+    let mut expected = tokens[start].line;
+    let mut i = start;
+    while let Some(prev) = i.checked_sub(1).map(|p| tokens[p]) {
+        if prev.kind != TokenKind::Doc || prev.line + 1 != expected {
+            break;
+        }
+        let body = prev.text.trim_start_matches('#');
+        let body = body.strip_prefix(' ').unwrap_or(body);
+        if MAGIC.iter().any(|m| body.trim_start().starts_with(m)) || body.starts_with('!') {
+            break;
+        }
+        lines.push(body.trim_end().to_string());
+        expected = prev.line;
+        i -= 1;
+    }
+    lines.reverse();
+    join_doc(lines)
+}
+
+/// Blank out (with spaces, keeping newlines and byte offsets) the parts of
+/// Ruby source the tokenizer cannot delimit: heredoc bodies, `=begin` /
+/// `=end` blocks, `%w[...]`-style literals and anything after `__END__`.
+fn mask_literals(src: &str) -> String {
+    let b = src.as_bytes();
+    let mut out = b.to_vec();
+    let mut blank = |from: usize, to: usize| {
+        for byte in &mut out[from..to.min(b.len())] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    };
+    let line_end = |from: usize| {
+        b[from..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(b.len(), |p| from + p)
+    };
+
+    // Heredoc terminators waiting for the end of the current line.
+    let mut heredocs: Vec<(String, bool)> = Vec::new();
+    let mut i = 0;
+    let mut at_line_start = true;
+    while i < b.len() {
+        if at_line_start {
+            at_line_start = false;
+            if b[i..].starts_with(b"=begin") {
+                let mut end = line_end(i);
+                while end < b.len() && !b[end + 1..].starts_with(b"=end") {
+                    end = line_end(end + 1);
+                }
+                let end = line_end((end + 1).min(b.len()));
+                blank(i, end);
+                i = end;
+                continue;
+            }
+            if b[i..].starts_with(b"__END__") && matches!(b.get(i + 7), None | Some(b'\n' | b'\r'))
+            {
+                blank(i, b.len());
+                break;
+            }
+        }
+        match b[i] {
+            b'\n' => {
+                i += 1;
+                at_line_start = true;
+                // Heredoc bodies start on the line after their opener.
+                for (terminator, indented) in std::mem::take(&mut heredocs) {
+                    while i < b.len() {
+                        let end = line_end(i);
+                        let line = String::from_utf8_lossy(&b[i..end]);
+                        let line = if indented {
+                            line.trim()
+                        } else {
+                            line.trim_end()
+                        };
+                        let done = line == terminator;
+                        blank(i, end);
+                        i = (end + 1).min(b.len());
+                        if done {
+                            break;
+                        }
+                    }
+                }
+            }
+            b'#' => i = line_end(i),
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1;
+                while i < b.len() && b[i] != quote {
+                    i += if b[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'<' if b[i..].starts_with(b"<<") => {
+                let mut j = i + 2;
+                let indented = matches!(b.get(j), Some(b'~' | b'-'));
+                if indented {
+                    j += 1;
+                }
+                let quote = b.get(j).filter(|c| matches!(c, b'\'' | b'"')).copied();
+                if quote.is_some() {
+                    j += 1;
+                }
+                let start = j;
+                while b
+                    .get(j)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+                {
+                    j += 1;
+                }
+                let is_heredoc = j > start && (b[start].is_ascii_uppercase() || quote.is_some());
+                if is_heredoc {
+                    heredocs.push((String::from_utf8_lossy(&b[start..j]).into_owned(), indented));
+                    i = j + usize::from(quote.is_some());
+                } else {
+                    i += 2;
+                }
+            }
+            b'%' if matches!(
+                b.get(i + 1),
+                Some(b'q' | b'Q' | b'w' | b'W' | b'i' | b'I' | b'r' | b's')
+            ) && b.get(i + 2).is_some_and(|c| b"([{<|!/^".contains(c)) =>
+            {
+                let open = b[i + 2];
+                let close = match open {
+                    b'(' => b')',
+                    b'[' => b']',
+                    b'{' => b'}',
+                    b'<' => b'>',
+                    other => other,
+                };
+                let mut depth = 1;
+                let mut j = i + 3;
+                while j < b.len() {
+                    if b[j] == b'\\' {
+                        j += 2;
+                        continue;
+                    }
+                    if b[j] == close {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else if b[j] == open && open != close {
+                        depth += 1;
+                    }
+                    j += 1;
+                }
+                blank(i + 3, j);
+                i = j + 1;
+            }
+            _ => i += 1,
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// The constant Zeitwerk expects `path` to define, when it lies below an
+/// autoload root: `lib/`, or an `app/*` directory (and its `concerns/`).
+fn autoload_constant(path: &Path, root: &Path) -> Option<String> {
+    let path = normalize_path(path);
+    let rel = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+    let segments: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let below = segments
+        .iter()
+        .enumerate()
+        .find_map(|(k, s)| match s.as_str() {
+            "lib" if segments.len() > k + 1 => Some(&segments[k + 1..]),
+            "app" if segments.len() > k + 2 => {
+                let below = &segments[k + 2..];
+                Some(match below {
+                    [concerns, rest @ ..] if concerns == "concerns" && !rest.is_empty() => rest,
+                    _ => below,
+                })
+            }
+            _ => None,
+        })?;
+    Some(
+        below
+            .iter()
+            .map(|s| camelize(s))
+            .collect::<Vec<_>>()
+            .join("::"),
+    )
+}
+
+/// `user_profile` → `UserProfile`.
+fn camelize(segment: &str) -> String {
+    segment
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Resolve a constant reference through its lexical nesting (innermost
+/// first), then at the top level.
+fn resolve_constant<'a>(
+    constants: &HashMap<String, &'a str>,
+    name: &str,
+    nesting: &[String],
+) -> Option<&'a str> {
+    if let Some(absolute) = name.strip_prefix("::") {
+        return lookup_constant(constants, absolute, 0);
+    }
+    nesting
+        .iter()
+        .rev()
+        .find_map(|scope| lookup_constant(constants, &format!("{scope}::{name}"), scope.len()))
+        .or_else(|| lookup_constant(constants, name, 0))
+}
+
+/// Longest `::` prefix of `name` that is a known constant, never shorter
+/// than the segment following the first `skip` bytes.
+fn lookup_constant<'a>(
+    constants: &HashMap<String, &'a str>,
+    name: &str,
+    skip: usize,
+) -> Option<&'a str> {
+    let mut candidate = name;
+    loop {
+        if let Some(id) = constants.get(candidate) {
+            return Some(id);
+        }
+        let (head, _) = candidate.rsplit_once("::")?;
+        if head.len() <= skip {
+            return None;
+        }
+        candidate = head;
+    }
+}
+
+/// The module a `require` / `require_relative` names, if it is in the
+/// model. Plain requires match any file whose path ends with the feature,
+/// preferring one below a `lib/` directory.
+fn resolve_require<'a>(
+    from: &Path,
+    require: &Require,
+    by_path: &HashMap<PathBuf, &'a str>,
+) -> Option<&'a str> {
+    let feature = require.feature.trim_end_matches(".rb");
+    if require.relative {
+        let dir = from.parent().unwrap_or(Path::new(""));
+        let target = normalize_path(&dir.join(format!("{feature}.rb")));
+        return by_path.get(&target).copied();
+    }
+    let feature = Path::new(feature);
+    let mut matches: Vec<(&PathBuf, &&str)> = by_path
+        .iter()
+        .filter(|(path, _)| path.with_extension("").ends_with(feature))
+        .collect();
+    matches.sort();
+    matches
+        .iter()
+        .find(|(path, _)| {
+            path.with_extension("")
+                .ancestors()
+                .nth(feature.components().count())
+                .is_some_and(|dir| dir.ends_with("lib"))
+        })
+        .or(matches.first())
+        .map(|(_, id)| **id)
+}
+
+/// Libraries that ship with Ruby; every other require comes from a gem.
+fn classify(feature: &str) -> ExternalKind {
+    const STDLIB: &[&str] = &[
+        "abbrev",
+        "base64",
+        "benchmark",
+        "bigdecimal",
+        "cgi",
+        "coverage",
+        "csv",
+        "date",
+        "delegate",
+        "digest",
+        "English",
+        "erb",
+        "etc",
+        "fiber",
+        "fileutils",
+        "find",
+        "forwardable",
+        "io",
+        "ipaddr",
+        "json",
+        "logger",
+        "monitor",
+        "net",
+        "observer",
+        "open-uri",
+        "open3",
+        "openssl",
+        "optparse",
+        "ostruct",
+        "pathname",
+        "pp",
+        "prettyprint",
+        "pstore",
+        "psych",
+        "racc",
+        "rbconfig",
+        "readline",
+        "resolv",
+        "ripper",
+        "securerandom",
+        "set",
+        "shellwords",
+        "singleton",
+        "socket",
+        "stringio",
+        "strscan",
+        "tempfile",
+        "time",
+        "timeout",
+        "tmpdir",
+        "tsort",
+        "uri",
+        "weakref",
+        "yaml",
+        "zlib",
+    ];
+    let root = feature.split('/').next().unwrap_or(feature);
+    if STDLIB.contains(&root) {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
@@ -1,16 +1,39 @@
 //! Rust driver (tree-sitter-based).
 //!
-//! TODO(Phase 1.b): port the existing tree-sitter extraction to the new
-//!                  [`crate::ir::CodeModel`] contract. Until then this
-//!                  driver detects Rust projects but produces an empty
-//!                  model so the pipeline still runs end-to-end.
+//! Every `.rs` file becomes one [`Module`]. Module ids follow the `mod` tree:
+//! starting from each crate root (`lib.rs` / `main.rs`), `mod foo;`
+//! declarations are resolved to `foo.rs` or `foo/mod.rs` (honouring
+//! `#[path = "..."]`) and named `<crate>::foo`. Files the `mod` tree does
+//! not reach fall back to an id derived from their path under `src/`.
+//!
+//! Each file belongs to the package of the nearest `Cargo.toml` above it.
+//! Crate roots are named after that package's crate (`codetwin_ir::ir::symbol`),
+//! so ids stay the same whether one package or a whole workspace is
+//! scanned, and modules are grouped by it ([`Module::group`]); a binary next
+//! to a library is `<crate>::main`. Files no `Cargo.toml` owns use `crate`
+//! and stay ungrouped. The member directories of
+//! a root `[workspace]` are reported as [`Driver::source_dirs`], so a
+//! workspace root is scanned without any config.
+//!
+//! `use` declarations become [`EdgeKind::Import`] edges when they resolve to
+//! another module in the model — through `crate`/`self`/`super`, or through
+//! the name of a crate in the model the package depends on (by `path`, or
+//! `workspace = true` naming a path dependency of the workspace); other
+//! external crates are dropped. Each such dependency also links the two
+//! crate roots.
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use tree_sitter::Node;
 
-use super::Driver;
-use crate::ir::CodeModel;
+use super::common::{
+    EdgeSet, clean_block_comment, collapse_whitespace, has_extension, header, join_doc, line,
+    normalize_path, parse_each, text,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Module, ModuleId, Symbol, SymbolKind, Visibility};
 
 /// Rust driver (detects `Cargo.toml`).
 #[derive(Default)]
@@ -25,9 +48,635 @@ impl Driver for RustDriver {
         project_root.join("Cargo.toml").is_file()
     }
 
-    fn parse(&self, _paths: &[PathBuf]) -> Result<CodeModel> {
-        // TODO(Phase 1.b): load tree-sitter-rust grammar, walk each path,
-        //                  extract modules/symbols/edges, return the model.
-        Ok(CodeModel::new(self.name()))
+    fn source_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        workspace_members(project_root)
+            .into_iter()
+            .map(|member| member.join("src"))
+            .filter(|src| project_root.join(src).is_dir())
+            .collect()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["rs"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_each(
+            paths,
+            |p| has_extension(p, &["rs"]).then(tree_sitter_rust::language),
+            |path, source, tree| Ok(extract_file(path, source.as_bytes(), tree.root_node())),
+        )?;
+
+        let mut manifests = Manifests::default();
+        let owners: Vec<Option<usize>> = files.iter().map(|f| manifests.owner(&f.path)).collect();
+        let prefixes: Vec<&str> = owners
+            .iter()
+            .map(|owner| match owner {
+                Some(p) => manifests.packages[*p].ident.as_str(),
+                None => "crate",
+            })
+            .collect();
+
+        let (ids, is_root) = assign_module_ids(&files, &prefixes);
+        let known: HashSet<&str> = ids.iter().map(String::as_str).collect();
+
+        // What `crate::` may mean, and each package's library (or else
+        // binary) root.
+        let mut roots: HashSet<&str> = prefixes.iter().copied().collect();
+        let mut package_roots: HashMap<usize, &str> = HashMap::new();
+        for (i, file) in files.iter().enumerate() {
+            if !is_root[i] {
+                continue;
+            }
+            roots.insert(&ids[i]);
+            if let Some(p) = owners[i]
+                && (file.path.ends_with("lib.rs") || !package_roots.contains_key(&p))
+            {
+                package_roots.insert(p, &ids[i]);
+            }
+        }
+
+        let mut edges = EdgeSet::default();
+        for ((file, id), owner) in files.iter().zip(&ids).zip(&owners) {
+            // Crate names this file may `use`: its own and its dependencies.
+            let mut externs: HashMap<&str, &str> = HashMap::new();
+            if let Some(p) = owner {
+                let package = &manifests.packages[*p];
+                for (name, dir) in &package.dependencies {
+                    if let Some(root) = manifests.at(dir).and_then(|q| package_roots.get(&q)) {
+                        externs.insert(name, root);
+                    }
+                }
+                if let Some(root) = package_roots.get(p) {
+                    externs.insert(&package.ident, root);
+                }
+            }
+            for path in &file.uses {
+                if let Some(target) = resolve_use(id, path, &known, &roots, &externs) {
+                    edges.push(id, target, EdgeKind::Import);
+                }
+            }
+        }
+        let mut linked: Vec<(&usize, &&str)> = package_roots.iter().collect();
+        linked.sort();
+        for (p, from) in linked {
+            for (_, dir) in &manifests.packages[*p].dependencies {
+                if let Some(to) = manifests.at(dir).and_then(|q| package_roots.get(&q)) {
+                    edges.push(from, to, EdgeKind::Import);
+                }
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        model.edges = edges.into_edges();
+        model.modules = files
+            .into_iter()
+            .zip(ids)
+            .zip(&owners)
+            .map(|((file, id), owner)| Module {
+                name: id.rsplit("::").next().unwrap_or(&id).to_string(),
+                id: ModuleId(id),
+                path: file.path,
+                symbols: file.symbols,
+                doc: file.doc,
+                external_imports: Vec::new(),
+                group: owner.map(|p| manifests.packages[p].ident.clone()),
+                container: None,
+            })
+            .collect();
+
+        Ok(model)
+    }
+}
+
+/// Everything extracted from a single `.rs` file before module ids are known.
+struct ParsedFile {
+    path: PathBuf,
+    doc: Option<String>,
+    symbols: Vec<Symbol>,
+    /// Out-of-line `mod foo;` declarations, with an optional `#[path]`.
+    mods: Vec<(String, Option<String>)>,
+    /// Every path named by a `use` declaration, split on `::`.
+    uses: Vec<Vec<String>>,
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        doc: inner_doc(root, src),
+        symbols: Vec::new(),
+        mods: Vec::new(),
+        uses: Vec::new(),
+    };
+
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "use_declaration" => {
+                if let Some(arg) = node.child_by_field_name("argument") {
+                    collect_use_paths(arg, src, &[], &mut file.uses);
+                }
+            }
+            "mod_item" if node.child_by_field_name("body").is_none() => {
+                if let Some(name) = node.child_by_field_name("name") {
+                    file.mods
+                        .push((text(name, src).to_string(), path_attribute(node, src)));
+                }
+                file.symbols.extend(symbol(node, src));
+            }
+            _ => file.symbols.extend(symbol(node, src)),
+        }
+    }
+
+    file
+}
+
+/// Build a [`Symbol`] for a top-level item, or `None` for items we skip
+/// (`impl` blocks, macros, `extern crate`, ...).
+fn symbol(node: Node<'_>, src: &[u8]) -> Option<Symbol> {
+    let kind = match node.kind() {
+        "function_item" => SymbolKind::Function,
+        "struct_item" | "union_item" => SymbolKind::Struct,
+        "enum_item" => SymbolKind::Enum,
+        "trait_item" => SymbolKind::Trait,
+        "const_item" | "static_item" => SymbolKind::Constant,
+        "type_item" => SymbolKind::TypeAlias,
+        "mod_item" => SymbolKind::Module,
+        _ => return None,
+    };
+    let name = node.child_by_field_name("name")?;
+
+    // The signature is the item header: everything before the body (or the
+    // initializer for consts/statics).
+    let end = node
+        .child_by_field_name("body")
+        .or_else(|| node.child_by_field_name("value"));
+    let signature = header(node, end, src)
+        .trim_end_matches([';', '=', ' '])
+        .to_string();
+
+    Some(Symbol {
+        name: text(name, src).to_string(),
+        kind,
+        visibility: visibility(node, src),
+        line: line(node),
+        cell: None,
+        doc: outer_doc(node, src),
+        signature: Some(signature),
+        members: Vec::new(),
+    })
+}
+
+fn visibility(node: Node<'_>, src: &[u8]) -> Visibility {
+    let mut cursor = node.walk();
+    let modifier = node
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == "visibility_modifier");
+    match modifier.map(|m| collapse_whitespace(text(m, src))) {
+        None => Visibility::Private,
+        Some(m) if m == "pub" => Visibility::Public,
+        Some(m) if m == "pub(self)" => Visibility::Private,
+        // `pub(crate)`, `pub(super)`, `pub(in path)`.
+        Some(_) => Visibility::Internal,
+    }
+}
+
+/// `///` / `/** */` comments directly above `node`, skipping attributes.
+fn outer_doc(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut prev = node.prev_sibling();
+    while let Some(sib) = prev {
+        let t = text(sib, src);
+        match sib.kind() {
+            "attribute_item" => {}
+            "line_comment" if t.starts_with("///") && !t.starts_with("////") => {
+                lines.push(strip_line_doc(&t[3..]));
+            }
+            "block_comment" if t.starts_with("/**") && !t.starts_with("/***") => {
+                lines.extend(clean_block_comment(t));
+            }
+            _ => break,
+        }
+        prev = sib.prev_sibling();
+    }
+    lines.reverse();
+    join_doc(lines)
+}
+
+/// Leading `//!` / `/*! */` comments of a file.
+fn inner_doc(root: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.children(&mut cursor) {
+        let t = text(node, src);
+        match node.kind() {
+            "line_comment" if t.starts_with("//!") => lines.push(strip_line_doc(&t[3..])),
+            "block_comment" if t.starts_with("/*!") => lines.extend(clean_block_comment(t)),
+            "line_comment" | "block_comment" | "inner_attribute_item" | "shebang" => {}
+            _ => break,
+        }
     }
+    join_doc(lines)
+}
+
+fn strip_line_doc(rest: &str) -> String {
+    let rest = rest.trim_end();
+    rest.strip_prefix(' ').unwrap_or(rest).to_string()
+}
+
+/// The `path` of a `#[path = "..."]` attribute above a `mod` item.
+fn path_attribute(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let mut prev = node.prev_sibling();
+    while let Some(sib) = prev {
+        match sib.kind() {
+            "attribute_item" => {
+                let attr = text(sib, src);
+                let inner = attr.trim_start_matches("#[").trim_end_matches(']').trim();
+                if let Some(value) = inner.strip_prefix("path") {
+                    let value = value.trim_start().strip_prefix('=')?.trim();
+                    return Some(value.trim_matches('"').to_string());
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        prev = sib.prev_sibling();
+    }
+    None
+}
+
+/// Flatten a `use` tree into one segment list per imported path.
+///
+/// `use crate::a::{b, c::D, self}` yields `crate::a::b`, `crate::a::c::D`
+/// and `crate::a`.
+fn collect_use_paths(node: Node<'_>, src: &[u8], prefix: &[String], out: &mut Vec<Vec<String>>) {
+    let join = |path: Node<'_>| -> Vec<String> {
+        let mut segments = prefix.to_vec();
+        segments.extend(
+            text(path, src)
+                .split("::")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        );
+        segments
+    };
+
+    match node.kind() {
+        "self" if !prefix.is_empty() => out.push(prefix.to_vec()),
+        "identifier" | "crate" | "self" | "super" | "scoped_identifier" => out.push(join(node)),
+        "use_as_clause" => {
+            if let Some(path) = node.child_by_field_name("path") {
+                collect_use_paths(path, src, prefix, out);
+            }
+        }
+        "use_wildcard" => {
+            if let Some(path) = node.named_child(0) {
+                out.push(join(path));
+            }
+        }
+        "scoped_use_list" => {
+            let prefix = node
+                .child_by_field_name("path")
+                .map_or_else(|| prefix.to_vec(), join);
+            if let Some(list) = node.child_by_field_name("list") {
+                collect_use_paths(list, src, &prefix, out);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_use_paths(child, src, prefix, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Compute a module id for every file, in the same order as `files`, and
+/// whether each file is a crate root. `prefixes` names the crate of each
+/// file (`crate` outside any package).
+fn assign_module_ids(files: &[ParsedFile], prefixes: &[&str]) -> (Vec<String>, Vec<bool>) {
+    let by_path: HashMap<&Path, usize> = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.path.as_path(), i))
+        .collect();
+    let mut ids: Vec<Option<String>> = vec![None; files.len()];
+    let mut queue = VecDeque::new();
+
+    for (i, file) in files.iter().enumerate() {
+        let Some(root) = crate_root_id(&file.path, prefixes[i], &by_path) else {
+            continue;
+        };
+        ids[i] = Some(root);
+        queue.push_back(i);
+    }
+    let is_root = ids.iter().map(Option::is_some).collect();
+
+    while let Some(i) = queue.pop_front() {
+        let parent = &files[i];
+        let parent_id = ids[i].clone().unwrap_or_default();
+        let dir = parent.path.parent().unwrap_or(Path::new(""));
+        let child_dir = if owns_directory(&parent.path) {
+            dir.to_path_buf()
+        } else {
+            dir.join(file_stem(&parent.path))
+        };
+
+        for (name, attr) in &parent.mods {
+            let candidates = match attr {
+                Some(p) => vec![dir.join(p)],
+                None => vec![
+                    child_dir.join(format!("{name}.rs")),
+                    child_dir.join(name).join("mod.rs"),
+                ],
+            };
+            let Some(&child) = candidates.iter().find_map(|c| by_path.get(c.as_path())) else {
+                continue;
+            };
+            if ids[child].is_none() {
+                ids[child] = Some(format!("{parent_id}::{name}"));
+                queue.push_back(child);
+            }
+        }
+    }
+
+    let ids = ids
+        .into_iter()
+        .zip(files)
+        .zip(prefixes)
+        .map(|((id, file), prefix)| id.unwrap_or_else(|| path_module_id(&file.path, prefix)))
+        .collect();
+    (ids, is_root)
+}
+
+/// `prefix` for a library root, `<prefix>::main` (`main` outside any
+/// package) for a binary root that sits next to a `lib.rs` (so the two crates do not
+/// collide), else `None`.
+fn crate_root_id(path: &Path, prefix: &str, files: &HashMap<&Path, usize>) -> Option<String> {
+    match path.file_name()?.to_str()? {
+        "main.rs" if files.contains_key(path.with_file_name("lib.rs").as_path()) => {
+            Some(if prefix == "crate" {
+                "main".to_string()
+            } else {
+                format!("{prefix}::main")
+            })
+        }
+        "lib.rs" | "main.rs" => Some(prefix.to_string()),
+        _ => None,
+    }
+}
+
+/// `mod.rs`, `lib.rs` and `main.rs` resolve child modules in their own
+/// directory; every other file resolves them in a directory named after it.
+fn owns_directory(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("mod.rs" | "lib.rs" | "main.rs")
+    )
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Fallback id for files outside the `mod` tree, derived from the path
+/// below the nearest `src/` directory, under the crate `prefix`.
+fn path_module_id(path: &Path, prefix: &str) -> String {
+    let components: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let start = components
+        .iter()
+        .rposition(|c| c == "src")
+        .map_or(components.len().saturating_sub(1), |i| i + 1);
+
+    let dirs = &components[start.min(components.len())..components.len().saturating_sub(1)];
+
+    let mut segments = vec![prefix.to_string()];
+    segments.extend(dirs.iter().cloned());
+    if !owns_directory(path) {
+        segments.push(file_stem(path));
+    }
+    segments.join("::")
+}
+
+/// Resolve a `use` path written in module `from` to the longest matching
+/// module id in `known`. `roots` are the crate root ids (the longest one
+/// prefixing `from` is its `crate`), `externs` maps the crate names `from`
+/// may use to their root ids. Other external crates resolve to `None`.
+fn resolve_use<'a>(
+    from: &str,
+    path: &[String],
+    known: &HashSet<&'a str>,
+    roots: &HashSet<&str>,
+    externs: &HashMap<&str, &str>,
+) -> Option<&'a str> {
+    let current: Vec<&str> = from.split("::").collect();
+    let mut absolute: Vec<String> = Vec::new();
+    let mut rest = path.iter().map(String::as_str).peekable();
+
+    match rest.peek().copied()? {
+        "crate" => {
+            rest.next();
+            let depth = (1..=current.len())
+                .rev()
+                .find(|&n| roots.contains(current[..n].join("::").as_str()))
+                .unwrap_or(1);
+            absolute.extend(current[..depth].iter().map(|s| s.to_string()));
+        }
+        "self" => {
+            rest.next();
+            absolute.extend(current.iter().map(|s| s.to_string()));
+        }
+        "super" => {
+            absolute.extend(current.iter().map(|s| s.to_string()));
+            while rest.next_if_eq(&"super").is_some() {
+                absolute.pop();
+            }
+            if absolute.is_empty() {
+                return None;
+            }
+        }
+        // Uniform paths: a bare name may refer to a child module.
+        first if known.contains(format!("{from}::{first}").as_str()) => {
+            absolute.extend(current.iter().map(|s| s.to_string()));
+        }
+        first => {
+            let root = externs.get(first)?;
+            rest.next();
+            absolute.extend(root.split("::").map(String::from));
+        }
+    }
+    absolute.extend(rest.map(String::from));
+
+    (1..=absolute.len())
+        .rev()
+        .find_map(|n| known.get(absolute[..n].join("::").as_str()).copied())
+}
+
+/// Directories (relative to `project_root`) of the members of the
+/// `[workspace]` in its `Cargo.toml`, with `members` globs expanded and
+/// `exclude`d directories dropped.
+fn workspace_members(project_root: &Path) -> Vec<PathBuf> {
+    let Some(manifest) = read_manifest(project_root) else {
+        return Vec::new();
+    };
+    let Some(workspace) = manifest.get("workspace").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+    let strings = |key: &str| -> Vec<&str> {
+        workspace
+            .get(key)
+            .and_then(toml::Value::as_array)
+            .map(|a| a.iter().filter_map(toml::Value::as_str).collect())
+            .unwrap_or_default()
+    };
+    let excluded: Vec<PathBuf> = strings("exclude")
+        .into_iter()
+        .map(|e| normalize_path(Path::new(e)))
+        .collect();
+
+    let root = glob::Pattern::escape(&project_root.to_string_lossy());
+    let mut members = Vec::new();
+    for pattern in strings("members") {
+        let Ok(matches) = glob::glob(&format!("{root}/{pattern}")) else {
+            continue;
+        };
+        for dir in matches.flatten() {
+            let Ok(rel) = dir.strip_prefix(project_root) else {
+                continue;
+            };
+            let rel = normalize_path(rel);
+            if dir.join("Cargo.toml").is_file() && !excluded.contains(&rel) {
+                members.push(rel);
+            }
+        }
+    }
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// A `[package]` and the crates in the model it may depend on.
+struct Package {
+    dir: PathBuf,
+    /// Crate name as written in paths (`codetwin_ir`).
+    ident: String,
+    /// `(name as written in paths, directory)` of every path dependency.
+    dependencies: Vec<(String, PathBuf)>,
+}
+
+/// Packages found so far, and a per-directory memo of the nearest one.
+#[derive(Default)]
+struct Manifests {
+    packages: Vec<Package>,
+    by_dir: HashMap<PathBuf, Option<usize>>,
+}
+
+impl Manifests {
+    /// Index of the package owning the file at `path`.
+    fn owner(&mut self, path: &Path) -> Option<usize> {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        if let Some(&found) = self.by_dir.get(dir) {
+            return found;
+        }
+        let found = dir.ancestors().find_map(|a| {
+            if let Some(i) = self.at(a) {
+                return Some(i);
+            }
+            let package = read_package(a)?;
+            self.packages.push(package);
+            Some(self.packages.len() - 1)
+        });
+        self.by_dir.insert(dir.to_path_buf(), found);
+        found
+    }
+
+    /// Index of the package at `dir`, if it owns any file.
+    fn at(&self, dir: &Path) -> Option<usize> {
+        let dir = normalize_path(dir);
+        self.packages.iter().position(|p| p.dir == dir)
+    }
+}
+
+fn read_manifest(dir: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn read_package(dir: &Path) -> Option<Package> {
+    let manifest = read_manifest(dir)?;
+    let name = manifest.get("package")?.get("name")?.as_str()?;
+    let ident = manifest
+        .get("lib")
+        .and_then(|lib| lib.get("name"))
+        .and_then(toml::Value::as_str)
+        .unwrap_or(name)
+        .replace('-', "_");
+
+    // `[dependencies]` and friends, also under `[target.'cfg(..)']`.
+    let mut tables: Vec<&toml::Value> = Vec::new();
+    let sections = ["dependencies", "dev-dependencies", "build-dependencies"];
+    let targets = manifest.get("target").and_then(toml::Value::as_table);
+    for scope in std::iter::once(&manifest).chain(
+        targets
+            .into_iter()
+            .flat_map(|t| t.values().filter_map(toml::Value::as_table)),
+    ) {
+        tables.extend(sections.iter().filter_map(|s| scope.get(*s)));
+    }
+
+    let mut workspace: Option<Option<(PathBuf, toml::Table)>> = None;
+    let mut dependencies = Vec::new();
+    for (name, spec) in tables
+        .into_iter()
+        .filter_map(toml::Value::as_table)
+        .flatten()
+    {
+        let path = if let Some(path) = spec.get("path").and_then(toml::Value::as_str) {
+            dir.join(path)
+        } else if spec.get("workspace").and_then(toml::Value::as_bool) == Some(true) {
+            let Some((root, manifest)) = workspace.get_or_insert_with(|| workspace_root(dir))
+            else {
+                continue;
+            };
+            let Some(path) = manifest
+                .get("workspace")
+                .and_then(|w| w.get("dependencies"))
+                .and_then(|d| d.get(name))
+                .and_then(|d| d.get("path"))
+                .and_then(toml::Value::as_str)
+            else {
+                continue;
+            };
+            root.join(path)
+        } else {
+            continue;
+        };
+        dependencies.push((name.replace('-', "_"), normalize_path(&path)));
+    }
+
+    Some(Package {
+        dir: normalize_path(dir),
+        ident,
+        dependencies,
+    })
+}
+
+/// The nearest directory at or above `dir` whose `Cargo.toml` declares a
+/// `[workspace]`, with that manifest.
+fn workspace_root(dir: &Path) -> Option<(PathBuf, toml::Table)> {
+    dir.ancestors().find_map(|a| {
+        let manifest = read_manifest(a)?;
+        manifest
+            .contains_key("workspace")
+            .then(|| (a.to_path_buf(), manifest))
+    })
 }
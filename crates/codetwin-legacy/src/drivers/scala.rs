@@ -0,0 +1,807 @@
+//! Scala driver (scanner-based).
+//!
+//! There is no Scala grammar for the `tree-sitter` 0.20 line the workspace
+//! is pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer. Template bodies are tracked by braces, or by indentation for
+//! Scala 3's `class A:` style, and method bodies are skipped.
+//!
+//! Module ids follow the [Java driver](super::java): every `.scala` file
+//! becomes one [`Module`] whose id is its package (chained `package a` /
+//! `package b` clauses joined) plus the file stem (`com.acme.billing.Invoice`).
+//! Both drivers resolve references against each other's types, so a mixed
+//! sbt project forms one polyglot model.
+//!
+//! Classes and case classes, objects, traits, enums, type aliases and
+//! `def`s are extracted as symbols; package objects are modules, nested
+//! types are named `Outer.Inner` and members `Owner.name`. `private` and
+//! `protected` map directly, while a qualified `private[pkg]` opens the
+//! member to a whole package and becomes [`Visibility::Internal`]. Imports
+//! — including wildcard (`_` / `*`) and renaming (`{A => B}` / `A as B`)
+//! selectors — become [`EdgeKind::Import`] edges. The first parent of an
+//! `extends ... with ...` chain is [`EdgeKind::Extends`] unless it is a
+//! trait; mixed-in traits are [`EdgeKind::Implements`].
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{EdgeSet, clean_block_comment, collapse_whitespace, has_extension, scan_each};
+use super::java::{self, Import, JvmType, Scope, lookup_prefix, qualify};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Scala driver (detects `build.sbt`).
+#[derive(Default)]
+pub struct ScalaDriver;
+
+impl Driver for ScalaDriver {
+    fn name(&self) -> &'static str {
+        "scala"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("build.sbt").is_file()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims {
+            reads: &["*.java"],
+            ..FileClaims::extensions(&["scala"])
+        }
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_files(paths)?;
+        let ids: Vec<String> = files.iter().map(module_id).collect();
+        let java_types = java::declared_types(paths)?;
+
+        // Fully-qualified name → declaring module, the names that are
+        // traits (or Java interfaces), and package → modules.
+        let mut names: HashMap<String, &str> = HashMap::new();
+        let mut traits: HashSet<String> = HashSet::new();
+        let mut packages: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (file, id) in files.iter().zip(&ids) {
+            for decl in file.decls.iter().filter(|d| d.is_type) {
+                let name = qualify(&file.package, &decl.symbol.name);
+                if decl.symbol.kind == SymbolKind::Trait {
+                    traits.insert(name.clone());
+                }
+                names.entry(name).or_insert(id);
+            }
+            packages.entry(file.package.as_str()).or_default().push(id);
+        }
+        for ty in &java_types {
+            if ty.interface {
+                traits.insert(ty.name.clone());
+            }
+            names.entry(ty.name.clone()).or_insert(&ty.module);
+            let modules = packages.entry(ty.package.as_str()).or_default();
+            if !modules.contains(&ty.module.as_str()) {
+                modules.push(&ty.module);
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let scope = Scope::new(&file.package, &file.imports, &names);
+            let mut external_imports = Vec::new();
+            let mut seen = HashSet::new();
+
+            for import in &file.imports {
+                let targets: Vec<&str> = match packages.get(import.path.as_str()) {
+                    Some(modules) if import.wildcard => modules.clone(),
+                    _ => lookup_prefix(&names, &import.path).into_iter().collect(),
+                };
+                if targets.is_empty() {
+                    if seen.insert(import.path.as_str()) {
+                        external_imports.push(ExternalImport {
+                            path: import.path.clone(),
+                            kind: classify(&import.path),
+                        });
+                    }
+                    continue;
+                }
+                for target in targets {
+                    edges.push(id, target, EdgeKind::Import);
+                }
+            }
+
+            for decl in &file.decls {
+                let is_trait = decl.symbol.kind == SymbolKind::Trait;
+                for (position, parent) in decl.parents.iter().enumerate() {
+                    let name = scope.qualified(parent);
+                    let Some(target) = lookup_prefix(&names, &name) else {
+                        continue;
+                    };
+                    let mixin = traits.contains(&name) && !is_trait;
+                    let kind = if mixin || (position > 0 && !is_trait) {
+                        EdgeKind::Implements
+                    } else {
+                        EdgeKind::Extends
+                    };
+                    edges.push(id, target, kind);
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file.decls.iter().map(|d| d.symbol.clone()).collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// Types declared by the `.scala` files among `paths`, for the Java driver.
+pub(super) fn declared_types(paths: &[PathBuf]) -> Result<Vec<JvmType>> {
+    let files = parse_files(paths)?;
+    Ok(files
+        .iter()
+        .flat_map(|file| {
+            let module = module_id(file);
+            file.decls
+                .iter()
+                .filter(|d| d.is_type)
+                .map(move |d| JvmType {
+                    name: qualify(&file.package, &d.symbol.name),
+                    package: file.package.clone(),
+                    module: module.clone(),
+                    interface: d.symbol.kind == SymbolKind::Trait,
+                })
+        })
+        .collect())
+}
+
+fn parse_files(paths: &[PathBuf]) -> Result<Vec<ParsedFile>> {
+    scan_each(
+        paths,
+        |p| has_extension(p, &["scala"]),
+        |path, source| Ok(extract_file(path, source)),
+    )
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &[],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/**"),
+    nested_block_comments: true,
+    // Character literals (`'{'`) and backtick-quoted identifiers
+    // (`` `type` ``) are read as strings so their contents are not taken for
+    // code.
+    quotes: &['"', '\'', '`'],
+    triple_quoted_strings: true,
+    verbatim_strings: false,
+    ident_chars: &[],
+};
+
+/// Modifiers that may precede a declaration.
+const MODIFIERS: &[&str] = &[
+    "private",
+    "protected",
+    "final",
+    "sealed",
+    "abstract",
+    "implicit",
+    "lazy",
+    "override",
+    "case",
+    "inline",
+    "opaque",
+    "open",
+    "transparent",
+    "infix",
+];
+
+/// Everything extracted from a single `.scala` file.
+struct ParsedFile {
+    path: PathBuf,
+    /// Declared package; empty for the default package.
+    package: String,
+    imports: Vec<Import>,
+    decls: Vec<Decl>,
+}
+
+/// A declaration plus the (unresolved) parents of its `extends` clause.
+struct Decl {
+    symbol: Symbol,
+    /// A class, object, trait or enum (as opposed to a member).
+    is_type: bool,
+    parents: Vec<String>,
+}
+
+/// Scopes the scanner tracks.
+enum Block {
+    /// A `package a.b { ... }` block.
+    Package,
+    Type {
+        name: String,
+        /// Column of the declaration for an indentation-based (`class A:`)
+        /// body, which ends at the next line that is not indented further;
+        /// `None` for a braced body.
+        indent: Option<usize>,
+    },
+    /// Method bodies, initializers, anonymous classes — skipped wholesale.
+    Other,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let tokens = tokenize(src, &SYNTAX);
+    let column = |t: &Token<'_>| t.offset - src[..t.offset].rfind('\n').map_or(0, |p| p + 1);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        package: String::new(),
+        imports: Vec::new(),
+        decls: Vec::new(),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Block::Other)) {
+            if token.is("{") {
+                stack.push(Block::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        if i == 0 || tokens[i - 1].line != token.line {
+            while let Some(Block::Type {
+                indent: Some(indent),
+                ..
+            }) = stack.last()
+            {
+                if column(&token) > *indent {
+                    break;
+                }
+                stack.pop();
+            }
+        }
+        let outer = match stack.last() {
+            Some(Block::Type { name, .. }) => Some(name.as_str()),
+            _ => None,
+        };
+
+        match token.text {
+            "{" => stack.push(Block::Other),
+            "}" => {
+                stack.pop();
+            }
+            "package" if outer.is_none() && !tokens.get(i + 1).is_some_and(|t| t.is("object")) => {
+                let (name, end) = dotted_name(&tokens, i + 1);
+                // Chained clauses (`package a` then `package b`) nest.
+                file.package = qualify(&file.package, &name);
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Block::Package);
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                continue;
+            }
+            "import" => {
+                let end = line_end(&tokens, i);
+                file.imports.extend(parse_import(&tokens[i + 1..end]));
+                i = end;
+                continue;
+            }
+            "class" | "trait" | "object" | "enum"
+                if tokens.get(i + 1).is_some_and(|t| t.is_ident()) =>
+            {
+                let (decl, end) = type_declaration(&tokens, i, outer, src);
+                let name = decl.symbol.name.clone();
+                file.decls.push(decl);
+                match tokens.get(end) {
+                    Some(t) if t.is("{") => {
+                        stack.push(Block::Type { name, indent: None });
+                        i = end + 1;
+                    }
+                    // Scala 3: `class A:` followed by an indented body.
+                    Some(t) if t.is(":") => {
+                        let start = modifiers_start(&tokens, i);
+                        stack.push(Block::Type {
+                            name,
+                            indent: Some(column(&tokens[start])),
+                        });
+                        i = end + 1;
+                    }
+                    _ => i = end,
+                }
+                continue;
+            }
+            "def" => {
+                let (decl, end) = function(&tokens, i, outer, src);
+                file.decls.extend(decl);
+                i = match tokens.get(end) {
+                    Some(t) if t.is("=") => skip_expression(
+                        &tokens,
+                        end + 1,
+                        column(&tokens[modifiers_start(&tokens, i)]),
+                        column,
+                    ),
+                    Some(t) if t.is("{") => {
+                        // Procedure syntax: `def run() { ... }`.
+                        stack.push(Block::Other);
+                        end + 1
+                    }
+                    _ => end,
+                };
+                continue;
+            }
+            "type" if tokens.get(i + 1).is_some_and(|t| t.is_ident()) => {
+                let (decl, end) = type_alias(&tokens, i, outer, src);
+                file.decls.push(decl);
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// Parse a class, trait, object or enum whose keyword is at `keyword`.
+/// Returns the declaration and the index just past its header (the `{` or
+/// `:` opening its body, when there is one).
+fn type_declaration(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    src: &str,
+) -> (Decl, usize) {
+    let start = modifiers_start(tokens, keyword);
+    let is_package_object = keyword > 0 && tokens[keyword - 1].is("package");
+    let name = tokens[keyword + 1].text;
+    let header_line = tokens[keyword].line;
+
+    // Type parameters, constructor modifiers and parameter lists.
+    let mut end = keyword + 2;
+    while let Some(t) = tokens.get(end) {
+        if t.is("[") || t.is("(") {
+            end = matching(tokens, end) + 1;
+        } else if t.is_ident() && MODIFIERS.contains(&t.text) {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+
+    let mut parents = Vec::new();
+    if tokens.get(end).is_some_and(|t| t.is("extends")) {
+        end += 1;
+        loop {
+            let (parent, after) = dotted_name(tokens, end);
+            if parent.is_empty() {
+                break;
+            }
+            parents.push(parent);
+            end = after;
+            // Type and constructor arguments.
+            while tokens.get(end).is_some_and(|t| t.is("[") || t.is("(")) {
+                end = matching(tokens, end) + 1;
+            }
+            match tokens.get(end) {
+                Some(t) if t.is("with") || t.is(",") => end += 1,
+                _ => break,
+            }
+        }
+    }
+    // Scala 3 `derives Eq, Show`.
+    if tokens.get(end).is_some_and(|t| t.is("derives")) {
+        end += 1;
+        while let Some(t) = tokens.get(end) {
+            if !(t.is_ident() || t.is(".") || t.is(",")) || t.line != header_line {
+                break;
+            }
+            end += 1;
+        }
+    }
+
+    let kind = match tokens[keyword].text {
+        _ if is_package_object => SymbolKind::Module,
+        "trait" => SymbolKind::Trait,
+        "enum" => SymbolKind::Enum,
+        _ => SymbolKind::Struct,
+    };
+    let name = match outer {
+        Some(outer) => format!("{outer}.{name}"),
+        None => name.to_string(),
+    };
+    let symbol = Symbol {
+        name,
+        kind: kind.clone(),
+        visibility: visibility(&tokens[start..keyword]),
+        line: tokens[start].line,
+        cell: None,
+        doc: scaladoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    (
+        Decl {
+            symbol,
+            is_type: kind != SymbolKind::Module,
+            parents,
+        },
+        end,
+    )
+}
+
+/// Parse the `def` at `keyword`. Returns the declaration (when it has a
+/// name) and the index of the `=` or `{` starting its body, or just past
+/// an abstract declaration.
+fn function(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    src: &str,
+) -> (Option<Decl>, usize) {
+    let start = modifiers_start(tokens, keyword);
+    // Auxiliary constructors (`def this(...)`) are not listed.
+    let Some(name) = tokens.get(keyword + 1).filter(|t| !t.is("this")) else {
+        return (None, keyword + 1);
+    };
+    // Operators (`def +(that: Money)`) are made of punctuation tokens.
+    let mut end = keyword + 2;
+    let mut name_text = name.text.to_string();
+    if name.kind == TokenKind::Punct {
+        while let Some(t) = tokens
+            .get(end)
+            .filter(|t| t.kind == TokenKind::Punct && !t.is("(") && !t.is("[") && !t.is(":"))
+        {
+            name_text.push_str(t.text);
+            end += 1;
+        }
+    }
+
+    // Signature: parameter lists and the return type, up to `=` (but not
+    // `=>`), `{`, or the end of the declaration's last line.
+    let mut line = tokens[end - 1].line;
+    while let Some(t) = tokens.get(end) {
+        let arrow = t.is("=") && tokens.get(end + 1).is_some_and(|n| n.is(">"));
+        if (t.is("=") && !arrow) || t.is("{") || t.line != line {
+            break;
+        }
+        if t.is("(") || t.is("[") {
+            end = matching(tokens, end);
+        } else if arrow {
+            end += 1;
+        }
+        line = tokens[end].line;
+        end += 1;
+    }
+
+    let symbol = Symbol {
+        name: match outer {
+            Some(outer) => format!("{outer}.{name_text}"),
+            None => name_text,
+        },
+        kind: SymbolKind::Function,
+        visibility: visibility(&tokens[start..keyword]),
+        line: tokens[start].line,
+        cell: None,
+        doc: scaladoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    let decl = Decl {
+        symbol,
+        is_type: false,
+        parents: Vec::new(),
+    };
+    (Some(decl), end)
+}
+
+/// `type Name[T] = Target` (or an abstract `type Name`) at `keyword`.
+fn type_alias(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    src: &str,
+) -> (Decl, usize) {
+    let start = modifiers_start(tokens, keyword);
+    let name = tokens[keyword + 1].text;
+    let end = line_end(tokens, keyword);
+    let symbol = Symbol {
+        name: match outer {
+            Some(outer) => format!("{outer}.{name}"),
+            None => name.to_string(),
+        },
+        kind: SymbolKind::TypeAlias,
+        visibility: visibility(&tokens[start..keyword]),
+        line: tokens[start].line,
+        cell: None,
+        doc: scaladoc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    (
+        Decl {
+            symbol,
+            is_type: false,
+            parents: Vec::new(),
+        },
+        end,
+    )
+}
+
+/// Index past an expression body starting at `start` for a declaration at
+/// column `indent`: everything up to the next line that is not indented
+/// further, jumping over brackets.
+fn skip_expression(
+    tokens: &[Token<'_>],
+    start: usize,
+    indent: usize,
+    column: impl Fn(&Token<'_>) -> usize,
+) -> usize {
+    let mut end = start;
+    while let Some(t) = tokens.get(end) {
+        if end > start && t.line != tokens[end - 1].line && column(t) <= indent {
+            break;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            end = matching(tokens, end);
+        }
+        end += 1;
+    }
+    end
+}
+
+/// `a.b.C`, `a.b._`, `a.b.{C, D => E, F as G, _}` and comma-separated
+/// lists of those; renames become aliases, and `X => _` hides `X`.
+fn parse_import(tokens: &[Token<'_>]) -> Vec<Import> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let (path, end) = dotted_name(tokens, i);
+        i = end;
+        if path.is_empty() {
+            i += 1;
+            continue;
+        }
+        let import = |path: &str, alias: Option<String>| {
+            let (path, wildcard) = match path.strip_suffix("._").or(path.strip_suffix(".*")) {
+                Some(prefix) => (prefix.to_string(), true),
+                None => (path.to_string(), false),
+            };
+            Import {
+                path,
+                alias,
+                is_static: false,
+                wildcard,
+            }
+        };
+        if tokens.get(i).is_some_and(|t| t.is(".")) && tokens.get(i + 1).is_some_and(|t| t.is("{"))
+        {
+            let close = matching(tokens, i + 1).min(tokens.len() - 1);
+            // Empty when the file ends inside the braces (`import a.{`).
+            let selectors = tokens.get(i + 2..close).unwrap_or_default();
+            for selector in selectors.split(|t| t.is(",")) {
+                let Some(first) = selector.first() else {
+                    continue;
+                };
+                if first.is("given") {
+                    continue;
+                }
+                let member = if first.is("_") || first.is("*") {
+                    "_"
+                } else {
+                    first.text
+                };
+                // `A => B` / `A as B`; `A => _` hides `A`.
+                let rename = match selector {
+                    [_, arrow, gt, to, ..] if arrow.is("=") && gt.is(">") => Some(to.text),
+                    [_, r#as, to, ..] if r#as.is("as") => Some(to.text),
+                    _ => None,
+                };
+                match rename {
+                    Some("_") => {}
+                    Some(alias) => {
+                        out.push(import(&format!("{path}.{member}"), Some(alias.to_string())))
+                    }
+                    None => out.push(import(&format!("{path}.{member}"), None)),
+                }
+            }
+            i = close + 1;
+            continue;
+        }
+        // Scala 3 `import a.B as C`.
+        let alias = match (tokens.get(i), tokens.get(i + 1)) {
+            (Some(r#as), Some(alias)) if r#as.is("as") && alias.is_ident() => {
+                i += 2;
+                Some(alias.text.to_string())
+            }
+            _ => None,
+        };
+        out.push(import(&path, alias));
+    }
+    out
+}
+
+/// Index of the first modifier or annotation in front of the keyword at
+/// `keyword`, walking backwards over `private[pkg]` qualifiers and
+/// `@annotation(...)`s.
+fn modifiers_start(tokens: &[Token<'_>], keyword: usize) -> usize {
+    let mut start = keyword;
+    while start > 0 {
+        let prev = tokens[start - 1];
+        if prev.is_ident() && MODIFIERS.contains(&prev.text) {
+            start -= 1;
+        } else if prev.is("]") {
+            // `private[pkg]` / `protected[this]`.
+            let open = (0..start - 1).rev().find(|&j| tokens[j].is("["));
+            match open {
+                Some(open)
+                    if open > 0
+                        && (tokens[open - 1].is("private") || tokens[open - 1].is("protected")) =>
+                {
+                    start = open - 1;
+                }
+                _ => break,
+            }
+        } else if let Some(at) = annotation_start(tokens, start) {
+            start = at;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Start of the annotation (`@tailrec`, `@deprecated("...", "1.0")`) that
+/// ends just before `end`, if there is one.
+fn annotation_start(tokens: &[Token<'_>], end: usize) -> Option<usize> {
+    let mut i = end.checked_sub(1)?;
+    if tokens[i].is(")") {
+        let mut depth = 0usize;
+        loop {
+            if tokens[i].is(")") {
+                depth += 1;
+            } else if tokens[i].is("(") {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            i = i.checked_sub(1)?;
+        }
+        i = i.checked_sub(1)?;
+    }
+    loop {
+        if !tokens[i].is_ident() {
+            return None;
+        }
+        let before = i.checked_sub(1)?;
+        if tokens[before].is("@") {
+            return Some(before);
+        }
+        if !tokens[before].is(".") {
+            return None;
+        }
+        i = before.checked_sub(1)?;
+    }
+}
+
+/// Scala members are public unless marked otherwise; a `private[pkg]` or
+/// `protected[pkg]` qualifier widens access to a package.
+fn visibility(modifiers: &[Token<'_>]) -> Visibility {
+    for (i, t) in modifiers.iter().enumerate() {
+        if !t.is("private") && !t.is("protected") {
+            continue;
+        }
+        let qualifier = modifiers
+            .get(i + 1)
+            .filter(|b| b.is("["))
+            .and_then(|_| modifiers.get(i + 2));
+        return match qualifier {
+            Some(q) if !q.is("this") => Visibility::Internal,
+            _ if t.is("private") => Visibility::Private,
+            _ => Visibility::Protected,
+        };
+    }
+    Visibility::Public
+}
+
+/// Index of the first token after the line `at` is on, jumping over
+/// brackets that span lines.
+fn line_end(tokens: &[Token<'_>], at: usize) -> usize {
+    let line = tokens[at].line;
+    let mut end = at;
+    while let Some(t) = tokens.get(end) {
+        if t.line != line || t.is(";") {
+            break;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            end = matching(tokens, end);
+        }
+        end += 1;
+    }
+    end
+}
+
+/// `a.b.C` (or `a.b._` / `a.b.*`) starting at `start`; returns the name and
+/// the index just past it.
+fn dotted_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        let expects_ident = name.is_empty() || name.ends_with('.');
+        let wildcard = (t.is("_") || t.is("*")) && !name.is_empty();
+        if expects_ident && (t.is_ident() || wildcard) {
+            name.push_str(t.text);
+            if wildcard {
+                i += 1;
+                break;
+            }
+        } else if !expects_ident && t.is(".") {
+            name.push('.');
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if name.ends_with('.') {
+        name.pop();
+        i -= 1;
+    }
+    (name, i)
+}
+
+/// The `/** ... */` block directly in front of the declaration at `start`.
+fn scaladoc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    let doc = tokens.get(start.checked_sub(1)?)?;
+    if doc.kind != TokenKind::Doc {
+        return None;
+    }
+    clean_block_comment(doc.text)
+}
+
+/// Source text of `tokens[start..end]`, whitespace collapsed.
+fn signature(tokens: &[Token<'_>], start: usize, end: usize, src: &str) -> String {
+    let last = tokens[end.clamp(start + 1, tokens.len()) - 1];
+    collapse_whitespace(&src[tokens[start].offset..last.offset + last.text.len()])
+}
+
+/// `com/acme/Invoice.scala` in `package com.acme` → `com.acme.Invoice`.
+fn module_id(file: &ParsedFile) -> String {
+    let stem = file
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    qualify(&file.package, &stem)
+}
+
+/// The Scala and Java standard libraries; everything else is a library
+/// dependency.
+fn classify(path: &str) -> ExternalKind {
+    let root = path.split('.').next().unwrap_or(path);
+    if matches!(root, "scala" | "java" | "javax" | "jdk") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
@@ -0,0 +1,291 @@
+//! A small tokenizer for drivers whose language has no grammar compatible
+//! with the workspace's pinned `tree-sitter` 0.20.
+//!
+//! The scanner only knows about comments, string literals, identifiers,
+//! numbers and single-character punctuation — enough for a driver to find
+//! declarations and imports without being confused by braces inside strings
+//! or commented-out code. Each driver describes its language with a
+//! [`Syntax`] and does its own (shallow) parsing over the token stream.
+
+/// Lexical conventions of one language.
+pub(super) struct Syntax {
+    /// Prefixes that start a doc comment running to the end of the line
+    /// (`///`). Checked before [`Syntax::line_comments`].
+    pub doc_line_comments: &'static [&'static str],
+    /// Prefixes that start an ordinary comment running to the end of the
+    /// line (`//`, `#`).
+    pub line_comments: &'static [&'static str],
+    /// Block comment delimiters (`/*`, `*/`).
+    pub block_comment: Option<(&'static str, &'static str)>,
+    /// Block comments opened with this prefix (`/**`) are doc comments.
+    pub doc_block_comment: Option<&'static str>,
+    /// Block comments nest (Swift, Kotlin, Scala).
+    pub nested_block_comments: bool,
+    /// String delimiters. A backslash escapes the next character.
+    pub quotes: &'static [char],
+    /// `"""` opens a string closed by the next `"""`.
+    pub triple_quoted_strings: bool,
+    /// `@"..."` strings where `""` is the only escape (C#).
+    pub verbatim_strings: bool,
+    /// Characters, besides `_`, that may appear in identifiers (`$`, `?`).
+    pub ident_chars: &'static [char],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TokenKind {
+    Ident,
+    Number,
+    Str,
+    Punct,
+    /// A doc comment, delimiters included.
+    Doc,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte offset of the first character.
+    pub offset: usize,
+    /// 1-indexed line of the first character.
+    pub line: u32,
+}
+
+impl Token<'_> {
+    pub(super) fn is(&self, text: &str) -> bool {
+        self.kind != TokenKind::Str && self.kind != TokenKind::Doc && self.text == text
+    }
+
+    pub(super) fn is_ident(&self) -> bool {
+        self.kind == TokenKind::Ident
+    }
+}
+
+/// Split `src` into tokens, dropping whitespace and non-doc comments.
+pub(super) fn tokenize<'a>(src: &'a str, syntax: &Syntax) -> Vec<Token<'a>> {
+    let mut lexer = Lexer {
+        src,
+        bytes: src.as_bytes(),
+        pos: 0,
+        line: 1,
+        syntax,
+    };
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Index of the bracket closing the one at `open`, or the last index when
+/// the input ends first.
+pub(super) fn matching(tokens: &[Token<'_>], open: usize) -> usize {
+    let (inc, dec) = match tokens[open].text {
+        "(" => ("(", ")"),
+        "[" => ("[", "]"),
+        "<" => ("<", ">"),
+        _ => ("{", "}"),
+    };
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.is(inc) {
+            depth += 1;
+        } else if token.is(dec) {
+            depth -= 1;
+            if depth == 0 {
+                return i;
+            }
+        }
+    }
+    tokens.len().saturating_sub(1)
+}
+
+struct Lexer<'a, 's> {
+    src: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    line: u32,
+    syntax: &'s Syntax,
+}
+
+impl<'a> Lexer<'a, '_> {
+    fn next_token(&mut self) -> Option<Token<'a>> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.src.get(self.pos..)?;
+            let c = rest.chars().next()?;
+            let (start, line) = (self.pos, self.line);
+
+            if let Some(prefix) = self
+                .syntax
+                .doc_line_comments
+                .iter()
+                .find(|p| rest.starts_with(**p))
+            {
+                // `////` is a separator line, not documentation.
+                if !rest[prefix.len()..].starts_with(&prefix[prefix.len() - 1..]) {
+                    self.skip_line();
+                    return Some(self.token(TokenKind::Doc, start, line));
+                }
+            }
+            if self
+                .syntax
+                .line_comments
+                .iter()
+                .any(|p| rest.starts_with(*p))
+                || self
+                    .syntax
+                    .doc_line_comments
+                    .iter()
+                    .any(|p| rest.starts_with(*p))
+            {
+                self.skip_line();
+                continue;
+            }
+            if let Some((open, close)) = self.syntax.block_comment
+                && rest.starts_with(open)
+            {
+                let is_doc = self
+                    .syntax
+                    .doc_block_comment
+                    .is_some_and(|d| rest.starts_with(d) && !rest[d.len()..].starts_with(close));
+                self.skip_block_comment(open, close);
+                if is_doc {
+                    return Some(self.token(TokenKind::Doc, start, line));
+                }
+                continue;
+            }
+
+            if self.syntax.verbatim_strings && rest.starts_with("@\"") {
+                self.pos += 2;
+                self.skip_verbatim_string();
+                return Some(self.token(TokenKind::Str, start, line));
+            }
+            if self.syntax.quotes.contains(&c) {
+                if self.syntax.triple_quoted_strings && rest.starts_with("\"\"\"") {
+                    self.skip_triple_quoted_string();
+                } else {
+                    self.pos += c.len_utf8();
+                    self.skip_string(c);
+                }
+                return Some(self.token(TokenKind::Str, start, line));
+            }
+
+            let syntax = self.syntax;
+            let is_ident =
+                |c: char| c.is_alphanumeric() || c == '_' || syntax.ident_chars.contains(&c);
+            if c.is_ascii_digit() {
+                self.bump_while(|c, next| {
+                    c.is_alphanumeric()
+                        || c == '_'
+                        || (c == '.' && next.is_some_and(|n| n.is_ascii_digit()))
+                });
+                return Some(self.token(TokenKind::Number, start, line));
+            }
+            if is_ident(c) {
+                self.bump_while(|c, _| is_ident(c));
+                return Some(self.token(TokenKind::Ident, start, line));
+            }
+
+            self.pos += c.len_utf8();
+            return Some(self.token(TokenKind::Punct, start, line));
+        }
+    }
+
+    fn token(&self, kind: TokenKind, start: usize, line: u32) -> Token<'a> {
+        Token {
+            kind,
+            text: &self.src[start..self.pos],
+            offset: start,
+            line,
+        }
+    }
+
+    /// Advance while `keep(current, next)` holds.
+    fn bump_while(&mut self, keep: impl Fn(char, Option<char>) -> bool) {
+        let src = self.src;
+        let mut chars = src[self.pos..].chars().peekable();
+        while let Some(c) = chars.next() {
+            if !keep(c, chars.peek().copied()) {
+                break;
+            }
+            self.advance(c);
+        }
+    }
+
+    fn advance(&mut self, c: char) {
+        if c == '\n' {
+            self.line += 1;
+        }
+        self.pos += c.len_utf8();
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.bump_while(|c, _| c.is_whitespace());
+    }
+
+    fn skip_line(&mut self) {
+        self.bump_while(|c, _| c != '\n');
+    }
+
+    fn skip_block_comment(&mut self, open: &str, close: &str) {
+        self.pos += open.len();
+        let mut depth = 1;
+        while self.pos < self.bytes.len() {
+            let rest = &self.src[self.pos..];
+            if rest.starts_with(close) {
+                self.pos += close.len();
+                depth -= 1;
+                if depth == 0 || !self.syntax.nested_block_comments {
+                    return;
+                }
+            } else if self.syntax.nested_block_comments && rest.starts_with(open) {
+                self.pos += open.len();
+                depth += 1;
+            } else {
+                let c = rest.chars().next().unwrap_or_default();
+                self.advance(c);
+            }
+        }
+    }
+
+    fn skip_string(&mut self, quote: char) {
+        while let Some(c) = self.src[self.pos..].chars().next() {
+            self.advance(c);
+            if c == '\\' {
+                if let Some(escaped) = self.src[self.pos..].chars().next() {
+                    self.advance(escaped);
+                }
+            } else if c == quote {
+                return;
+            }
+        }
+    }
+
+    fn skip_verbatim_string(&mut self) {
+        while let Some(c) = self.src[self.pos..].chars().next() {
+            self.advance(c);
+            if c == '"' {
+                if self.src[self.pos..].starts_with('"') {
+                    self.pos += 1;
+                } else {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn skip_triple_quoted_string(&mut self) {
+        self.pos += 3;
+        while self.pos < self.bytes.len() {
+            if self.src[self.pos..].starts_with("\"\"\"") {
+                self.pos += 3;
+                // Closing runs may carry extra quotes (`""""`).
+                self.bump_while(|c, _| c == '"');
+                return;
+            }
+            let c = self.src[self.pos..].chars().next().unwrap_or_default();
+            self.advance(c);
+        }
+    }
+}
@@ -0,0 +1,691 @@
+//! SQL schema / migrations driver.
+//!
+//! `.sql` files are tokenized with `sqlparser` and parsed one statement at
+//! a time with its generic dialect; statements it cannot parse (procedural
+//! function bodies, vendor extensions) are skipped rather than failing the
+//! file, and a file that does not even tokenize (an unterminated string)
+//! adds nothing. Files are then replayed in natural path order — `V2__` before
+//! `V10__`, timestamped migration directories oldest first — so that
+//! `CREATE TABLE`, `ALTER TABLE`, `DROP TABLE` and `COMMENT ON` add up to
+//! the final schema. Down / undo migrations (`*.down.sql`, `down.sql`,
+//! Flyway `U1__*.sql`) and the down half of dbmate and goose files are left
+//! out.
+//!
+//! Foreign keys are relationships between tables, and [`Edge`]s connect
+//! modules, so every table is a [`Module`] of its own (id `auth.users`,
+//! unquoted identifiers lower-cased) holding one [`SymbolKind::Struct`]
+//! whose [`members`](Symbol::members) are the columns with their type and
+//! constraints. Each foreign key becomes an [`EdgeKind::Uses`] edge to the
+//! referenced table; an unqualified reference matches a table of that name
+//! in any schema when there is only one.
+//!
+//! [`Edge`]: crate::ir::Edge
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, CommentDef, CommentObject,
+    DataType, Ident, ObjectName, ObjectType, Statement, TableConstraint,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use super::common::{EdgeSet, collapse_whitespace, has_extension, join_doc, scan_each};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Member, Module, ModuleId, Symbol, SymbolKind, Visibility};
+
+/// Directories migration tools keep their scripts in, relative to the
+/// project root.
+const MIGRATION_DIRS: &[&str] = &["migrations", "db/migrate", "db/migrations"];
+
+/// SQL driver (detects `.sql` files at the root or in a migrations
+/// directory).
+#[derive(Default)]
+pub struct SqlDriver;
+
+impl Driver for SqlDriver {
+    fn name(&self) -> &'static str {
+        "sql"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        // Migration directories may hold one directory per migration
+        // (diesel's `migrations/<version>/up.sql`).
+        has_sql(project_root, 0)
+            || MIGRATION_DIRS
+                .iter()
+                .any(|dir| has_sql(&project_root.join(dir), 1))
+    }
+
+    fn detects_loose_sources(&self, _project_root: &Path) -> bool {
+        true
+    }
+
+    fn source_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        MIGRATION_DIRS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|dir| has_sql(&project_root.join(dir), 1))
+            .collect()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["sql"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let mut files = scan_each(
+            paths,
+            |p| has_extension(p, &["sql"]) && !is_down_migration(p),
+            read_file,
+        )?;
+        files.sort_by(|a, b| natural_cmp(&a.path.to_string_lossy(), &b.path.to_string_lossy()));
+
+        let mut schema = Schema::default();
+        for file in &files {
+            for statement in &file.statements {
+                schema.apply(&file.path, statement);
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for table in &schema.tables {
+            for foreign_key in &table.foreign_keys {
+                if let Some(target) = schema.find(&foreign_key.target) {
+                    edges.push(&table.key, &schema.tables[target].key, EdgeKind::Uses);
+                }
+            }
+            model.modules.push(Module {
+                id: ModuleId(table.key.clone()),
+                name: table.name.clone(),
+                path: table.path.clone(),
+                symbols: vec![Symbol {
+                    name: table.name.clone(),
+                    kind: SymbolKind::Struct,
+                    visibility: Visibility::Public,
+                    line: table.line,
+                    cell: None,
+                    doc: table.doc.clone(),
+                    signature: Some(format!("CREATE TABLE {}", table.written)),
+                    members: table
+                        .columns
+                        .iter()
+                        .map(|c| Member {
+                            name: c.name.clone(),
+                            ty: Some(c.ty.clone()),
+                            modifiers: c.modifiers.clone(),
+                            doc: c.doc.clone(),
+                        })
+                        .collect(),
+                }],
+                doc: table.doc.clone(),
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+        Ok(model)
+    }
+}
+
+/// Whether `dir` holds a `.sql` file, looking `depth` directories deeper.
+fn has_sql(dir: &Path, depth: usize) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                depth > 0 && has_sql(&path, depth - 1)
+            } else {
+                has_extension(&path, &["sql"])
+            }
+        })
+    })
+}
+
+/// `down.sql`, `*.down.sql`, `*_down.sql` and Flyway undo scripts
+/// (`U2__drop_users.sql`) revert a migration instead of applying it.
+fn is_down_migration(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let stem = stem.to_ascii_lowercase();
+    let flyway_undo = stem
+        .strip_prefix('u')
+        .and_then(|s| s.split_once("__"))
+        .is_some_and(|(version, _)| {
+            !version.is_empty()
+                && version
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || c == '.' || c == '_')
+        });
+    stem == "down" || stem.ends_with(".down") || stem.ends_with("_down") || flyway_undo
+}
+
+/// Compare paths so that runs of digits order numerically (`V2__` before
+/// `V10__`).
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (mut a, mut b) = (a, b);
+    while let (Some(x), Some(y)) = (a.chars().next(), b.chars().next()) {
+        let ordering = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (m, n) = (digits(a), digits(b));
+            let (p, q) = (
+                a[..m].trim_start_matches('0'),
+                b[..n].trim_start_matches('0'),
+            );
+            (a, b) = (&a[m..], &b[n..]);
+            p.len().cmp(&q.len()).then_with(|| p.cmp(q))
+        } else {
+            (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+            x.cmp(&y)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+struct SqlFile {
+    path: PathBuf,
+    statements: Vec<Located>,
+}
+
+/// A parsed statement with the line it starts on and the `--` comment
+/// block directly above it.
+struct Located {
+    statement: Statement,
+    line: u32,
+    doc: Option<String>,
+}
+
+fn read_file(path: &Path, source: &str) -> Result<SqlFile> {
+    let source = up_section(source);
+    let dialect = GenericDialect {};
+    let tokens = match Tokenizer::new(&dialect, source).tokenize_with_location() {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "skipping malformed SQL file");
+            Vec::new()
+        }
+    };
+    let lines: Vec<&str> = source.lines().collect();
+
+    let mut statements = Vec::new();
+    for chunk in tokens.split(|t| t.token == Token::SemiColon) {
+        let Some(first) = chunk
+            .iter()
+            .find(|t| !matches!(t.token, Token::Whitespace(_)))
+        else {
+            continue;
+        };
+        let line = first.span.start.line as u32;
+        let Ok(statement) = Parser::new(&dialect)
+            .with_tokens_with_locations(chunk.to_vec())
+            .parse_statement()
+        else {
+            continue;
+        };
+        statements.push(Located {
+            statement,
+            line,
+            doc: leading_doc(&lines, line),
+        });
+    }
+    Ok(SqlFile {
+        path: path.to_path_buf(),
+        statements,
+    })
+}
+
+/// The part of a dbmate (`-- migrate:down`) or goose (`-- +goose Down`)
+/// migration that applies it; other files are returned whole.
+fn up_section(source: &str) -> &str {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        if let Some(marker) = line.trim().strip_prefix("--") {
+            let marker = collapse_whitespace(marker).to_ascii_lowercase();
+            if marker == "migrate:down" || marker == "+goose down" {
+                return &source[..offset];
+            }
+        }
+        offset += line.len();
+    }
+    source
+}
+
+/// `--` comment lines directly above `line` (1-indexed), skipping the
+/// section markers of migration tools.
+fn leading_doc(lines: &[&str], line: u32) -> Option<String> {
+    let mut doc = Vec::new();
+    for text in lines[..(line as usize).saturating_sub(1)].iter().rev() {
+        let Some(text) = text.trim().strip_prefix("--") else {
+            break;
+        };
+        let text = text.trim();
+        if text.starts_with("migrate:") || text.starts_with("+goose") {
+            break;
+        }
+        doc.push(text.to_string());
+    }
+    doc.reverse();
+    join_doc(doc)
+}
+
+/// Identifier as the database sees it: unquoted names are case-insensitive.
+fn ident_key(ident: &Ident) -> String {
+    match ident.quote_style {
+        Some(_) => ident.value.clone(),
+        None => ident.value.to_lowercase(),
+    }
+}
+
+fn object_key(name: &ObjectName) -> String {
+    name.0.iter().map(ident_key).collect::<Vec<_>>().join(".")
+}
+
+fn unqualified(key: &str) -> &str {
+    key.rsplit_once('.').map_or(key, |(_, name)| name)
+}
+
+/// Schema state built up by replaying statements.
+#[derive(Default)]
+struct Schema {
+    /// Tables in creation order.
+    tables: Vec<Table>,
+}
+
+struct Table {
+    key: String,
+    /// Unqualified name, as written.
+    name: String,
+    /// Qualified name, as written.
+    written: String,
+    path: PathBuf,
+    line: u32,
+    doc: Option<String>,
+    columns: Vec<Column>,
+    foreign_keys: Vec<ForeignKey>,
+}
+
+struct Column {
+    key: String,
+    name: String,
+    ty: String,
+    modifiers: Vec<String>,
+    doc: Option<String>,
+}
+
+struct ForeignKey {
+    /// Constraint name, when the key was declared with `CONSTRAINT name`.
+    name: Option<String>,
+    columns: Vec<String>,
+    /// Referenced table, as written (see [`Schema::find`]).
+    target: String,
+    /// `REFERENCES ...` modifier added to the key's columns.
+    modifier: String,
+}
+
+impl Schema {
+    /// Table a (possibly unqualified) name refers to: an exact match, or
+    /// else the only table with the same unqualified name.
+    fn find(&self, key: &str) -> Option<usize> {
+        if let Some(i) = self.tables.iter().position(|t| t.key == key) {
+            return Some(i);
+        }
+        let qualified = key.contains('.');
+        let mut candidates = self.tables.iter().enumerate().filter(|(_, t)| {
+            unqualified(&t.key) == unqualified(key) && (!qualified || !t.key.contains('.'))
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some((i, _)), None) => Some(i),
+            _ => None,
+        }
+    }
+
+    fn apply(&mut self, path: &Path, located: &Located) {
+        match &located.statement {
+            Statement::CreateTable(create) => {
+                let key = object_key(&create.name);
+                if let Some(i) = self.tables.iter().position(|t| t.key == key) {
+                    if create.if_not_exists {
+                        return;
+                    }
+                    self.tables.remove(i);
+                }
+                let mut table = Table {
+                    key,
+                    name: create
+                        .name
+                        .0
+                        .last()
+                        .map(|i| i.value.clone())
+                        .unwrap_or_default(),
+                    written: create.name.to_string(),
+                    path: path.to_path_buf(),
+                    line: located.line,
+                    doc: located.doc.clone(),
+                    columns: Vec::new(),
+                    foreign_keys: Vec::new(),
+                };
+                for column in &create.columns {
+                    table.add_column(column);
+                }
+                for constraint in &create.constraints {
+                    table.add_constraint(constraint);
+                }
+                if let Some(
+                    CommentDef::WithEq(comment)
+                    | CommentDef::WithoutEq(comment)
+                    | CommentDef::AfterColumnDefsWithoutEq(comment),
+                ) = &create.comment
+                {
+                    table.doc = Some(comment.clone());
+                }
+                self.tables.push(table);
+            }
+            Statement::AlterTable {
+                name, operations, ..
+            } => {
+                let Some(i) = self.find(&object_key(name)) else {
+                    return;
+                };
+                for operation in operations {
+                    match operation {
+                        AlterTableOperation::RenameTable { table_name } => {
+                            self.rename(i, table_name);
+                        }
+                        operation => self.tables[i].alter(operation),
+                    }
+                }
+            }
+            Statement::Drop {
+                object_type: ObjectType::Table,
+                names,
+                ..
+            } => {
+                for name in names {
+                    if let Some(i) = self.find(&object_key(name)) {
+                        self.tables.remove(i);
+                    }
+                }
+            }
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                ..
+            } => match object_type {
+                CommentObject::Table => {
+                    if let Some(i) = self.find(&object_key(object_name)) {
+                        self.tables[i].doc = comment.clone();
+                    }
+                }
+                CommentObject::Column => {
+                    let Some((column, table)) = object_name.0.split_last() else {
+                        return;
+                    };
+                    let table = object_key(&ObjectName(table.to_vec()));
+                    if let Some(i) = self.find(&table)
+                        && let Some(column) = self.tables[i].column_mut(column)
+                    {
+                        column.doc = comment.clone();
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// `ALTER TABLE ... RENAME TO`: an unqualified new name stays in the
+    /// table's schema, and foreign keys pointing at the table follow it.
+    fn rename(&mut self, i: usize, new_name: &ObjectName) {
+        let new_key = match (new_name.0.len(), self.tables[i].key.rsplit_once('.')) {
+            (1, Some((schema, _))) => format!("{schema}.{}", object_key(new_name)),
+            _ => object_key(new_name),
+        };
+        let retarget: Vec<(usize, usize)> = self
+            .tables
+            .iter()
+            .enumerate()
+            .flat_map(|(t, table)| {
+                table
+                    .foreign_keys
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, fk)| self.find(&fk.target) == Some(i))
+                    .map(move |(k, _)| (t, k))
+            })
+            .collect();
+        for (t, k) in retarget {
+            self.tables[t].foreign_keys[k].target = new_key.clone();
+        }
+        let table = &mut self.tables[i];
+        table.key = new_key;
+        table.name = new_name
+            .0
+            .last()
+            .map(|i| i.value.clone())
+            .unwrap_or_default();
+        table.written = new_name.to_string();
+    }
+}
+
+impl Table {
+    fn column_mut(&mut self, name: &Ident) -> Option<&mut Column> {
+        let key = ident_key(name);
+        self.columns.iter_mut().find(|c| c.key == key)
+    }
+
+    fn add_column(&mut self, def: &ColumnDef) {
+        let key = ident_key(&def.name);
+        if self.columns.iter().any(|c| c.key == key) {
+            return;
+        }
+        let mut column = Column {
+            key,
+            name: def.name.value.clone(),
+            ty: def.data_type.to_string(),
+            modifiers: Vec::new(),
+            doc: None,
+        };
+        self.apply_options(
+            &mut column,
+            def.options.iter().map(|o| (o.name.as_ref(), &o.option)),
+        );
+        self.columns.push(column);
+    }
+
+    /// Record column options as modifiers; inline `REFERENCES` also adds a
+    /// foreign key and `COMMENT` sets the column doc.
+    fn apply_options<'a>(
+        &mut self,
+        column: &mut Column,
+        options: impl IntoIterator<Item = (Option<&'a Ident>, &'a ColumnOption)>,
+    ) {
+        for (name, option) in options {
+            match option {
+                ColumnOption::Comment(comment) => column.doc = Some(comment.clone()),
+                ColumnOption::ForeignKey { foreign_table, .. } => {
+                    let modifier = option.to_string();
+                    column.modifiers.push(modifier.clone());
+                    self.foreign_keys.push(ForeignKey {
+                        name: name.map(ident_key),
+                        columns: vec![column.key.clone()],
+                        target: object_key(foreign_table),
+                        modifier,
+                    });
+                }
+                option => column.modifiers.push(option.to_string()),
+            }
+        }
+    }
+
+    fn add_constraint(&mut self, constraint: &TableConstraint) {
+        match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => self.mark(columns, "PRIMARY KEY"),
+            TableConstraint::Unique { columns, .. } if columns.len() == 1 => {
+                self.mark(columns, "UNIQUE");
+            }
+            TableConstraint::ForeignKey {
+                name,
+                columns,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+                characteristics,
+            } => {
+                // Same rendering as an inline `REFERENCES` column option.
+                let modifier = ColumnOption::ForeignKey {
+                    foreign_table: foreign_table.clone(),
+                    referred_columns: referred_columns.clone(),
+                    on_delete: *on_delete,
+                    on_update: *on_update,
+                    characteristics: *characteristics,
+                }
+                .to_string();
+                self.mark(columns, &modifier);
+                self.foreign_keys.push(ForeignKey {
+                    name: name.as_ref().map(ident_key),
+                    columns: columns.iter().map(ident_key).collect(),
+                    target: object_key(foreign_table),
+                    modifier,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Add `modifier` to each of `columns` that does not carry it yet.
+    fn mark(&mut self, columns: &[Ident], modifier: &str) {
+        for name in columns {
+            if let Some(column) = self.column_mut(name)
+                && !column.modifiers.iter().any(|m| m == modifier)
+            {
+                column.modifiers.push(modifier.to_string());
+            }
+        }
+    }
+
+    fn alter(&mut self, operation: &AlterTableOperation) {
+        match operation {
+            AlterTableOperation::AddColumn { column_def, .. } => self.add_column(column_def),
+            AlterTableOperation::DropColumn { column_name, .. } => {
+                let key = ident_key(column_name);
+                self.columns.retain(|c| c.key != key);
+                self.foreign_keys.retain(|fk| !fk.columns.contains(&key));
+            }
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => self.rename_column(old_column_name, new_column_name),
+            AlterTableOperation::AddConstraint(constraint) => self.add_constraint(constraint),
+            AlterTableOperation::DropConstraint { name, .. } => {
+                let key = ident_key(name);
+                let Some(i) = self
+                    .foreign_keys
+                    .iter()
+                    .position(|fk| fk.name.as_ref() == Some(&key))
+                else {
+                    return;
+                };
+                let foreign_key = self.foreign_keys.remove(i);
+                for column in &mut self.columns {
+                    if foreign_key.columns.contains(&column.key) {
+                        column.modifiers.retain(|m| *m != foreign_key.modifier);
+                    }
+                }
+            }
+            AlterTableOperation::DropPrimaryKey => {
+                for column in &mut self.columns {
+                    column.modifiers.retain(|m| m != "PRIMARY KEY");
+                }
+            }
+            AlterTableOperation::AlterColumn { column_name, op } => {
+                let Some(column) = self.column_mut(column_name) else {
+                    return;
+                };
+                match op {
+                    AlterColumnOperation::SetNotNull
+                        if !column.modifiers.iter().any(|m| m == "NOT NULL") =>
+                    {
+                        column.modifiers.push("NOT NULL".to_string());
+                    }
+                    AlterColumnOperation::DropNotNull => {
+                        column.modifiers.retain(|m| m != "NOT NULL");
+                    }
+                    AlterColumnOperation::SetDefault { value } => {
+                        column.modifiers.retain(|m| !m.starts_with("DEFAULT "));
+                        column.modifiers.push(format!("DEFAULT {value}"));
+                    }
+                    AlterColumnOperation::DropDefault => {
+                        column.modifiers.retain(|m| !m.starts_with("DEFAULT "));
+                    }
+                    AlterColumnOperation::SetDataType { data_type, .. } => {
+                        column.ty = data_type.to_string();
+                    }
+                    _ => {}
+                }
+            }
+            AlterTableOperation::ChangeColumn {
+                old_name,
+                new_name,
+                data_type,
+                options,
+                ..
+            } => {
+                self.rename_column(old_name, new_name);
+                self.redefine_column(new_name, data_type, options);
+            }
+            AlterTableOperation::ModifyColumn {
+                col_name,
+                data_type,
+                options,
+                ..
+            } => self.redefine_column(col_name, data_type, options),
+            _ => {}
+        }
+    }
+
+    fn rename_column(&mut self, old: &Ident, new: &Ident) {
+        let (old_key, new_key) = (ident_key(old), ident_key(new));
+        if let Some(column) = self.column_mut(old) {
+            column.key = new_key.clone();
+            column.name = new.value.clone();
+        }
+        for column in self
+            .foreign_keys
+            .iter_mut()
+            .flat_map(|fk| &mut fk.columns)
+            .filter(|c| **c == old_key)
+        {
+            column.clone_from(&new_key);
+        }
+    }
+
+    /// MySQL `CHANGE` / `MODIFY COLUMN`: a new type and options replace the
+    /// old ones, while foreign key constraints on the column stay.
+    fn redefine_column(&mut self, name: &Ident, data_type: &DataType, options: &[ColumnOption]) {
+        let key = ident_key(name);
+        let Some(i) = self.columns.iter().position(|c| c.key == key) else {
+            return;
+        };
+        let mut column = self.columns.remove(i);
+        column.ty = data_type.to_string();
+        column.modifiers = self
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.columns.contains(&key))
+            .map(|fk| fk.modifier.clone())
+            .collect();
+        self.apply_options(&mut column, options.iter().map(|o| (None, o)));
+        self.columns.insert(i, column);
+    }
+}
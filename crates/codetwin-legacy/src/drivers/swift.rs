@@ -0,0 +1,913 @@
+//! Swift driver (scanner-based).
+//!
+//! There is no Swift grammar for the `tree-sitter` 0.20 line the workspace
+//! is pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer and tracks type scopes by brace depth.
+//!
+//! SwiftPM targets are the groups: `Package.swift` is scanned for its
+//! `.target(...)`, `.executableTarget(...)`, `.testTarget(...)` (and macro
+//! and plugin) entries, and every `.swift` file under a target's directory
+//! becomes one [`Module`] with the target as its [`Module::group`] and id
+//! `Target/relative/path/Stem` (`Networking/HTTP/Client`).
+//!
+//! Classes, actors, structs, enums, protocols, type aliases, top-level
+//! `let` constants and functions are extracted as symbols; nested types are
+//! named `Outer.Inner`, and the functions of an `extension Foo` are named
+//! `Foo.name`. Conformances to protocols in the package become
+//! [`EdgeKind::Implements`] edges (from type declarations and extensions
+//! alike) and superclasses [`EdgeKind::Extends`]. Files of one target see
+//! each other without imports; `import` of a sibling target becomes
+//! [`EdgeKind::Import`] edges to the files declaring the exported names the
+//! importing file uses, and any other `import` an [`ExternalImport`].
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{
+    EdgeSet, collapse_whitespace, common_root, has_extension, join_doc, normalize_path,
+    read_source, relative_module_id, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Swift driver (detects `Package.swift`).
+#[derive(Default)]
+pub struct SwiftDriver;
+
+impl Driver for SwiftDriver {
+    fn name(&self) -> &'static str {
+        "swift"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("Package.swift").is_file()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["swift"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| has_extension(p, &["swift"]) && !is_manifest(p),
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+        let targets = read_targets(paths)?;
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+
+        // Containing target (index into `targets`) and id of every file.
+        let placed: Vec<(Option<usize>, String)> = files
+            .iter()
+            .map(|file| {
+                let path = normalize_path(&file.path);
+                let target = targets
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, t)| path.starts_with(&t.dir))
+                    .max_by_key(|(_, t)| t.dir.components().count())
+                    .map(|(i, _)| i);
+                let id = match target {
+                    Some(t) => format!(
+                        "{}/{}",
+                        targets[t].name,
+                        relative_module_id(&path, &targets[t].dir)
+                    ),
+                    None => relative_module_id(&path, &root),
+                };
+                (target, id)
+            })
+            .collect();
+
+        // (target, type name) → declaring module and whether the type is a
+        // protocol.
+        let mut types: HashMap<(Option<usize>, &str), (&str, bool)> = HashMap::new();
+        for (file, (target, id)) in files.iter().zip(&placed) {
+            for decl in file.decls.iter().filter(|d| d.is_type) {
+                types
+                    .entry((*target, decl.symbol.name.as_str()))
+                    .or_insert((id, decl.protocol));
+            }
+        }
+        let target_index: HashMap<&str, usize> = targets
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.name.as_str(), i))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, (target, id)) in files.iter().zip(&placed) {
+            let mut external_imports = Vec::new();
+            let mut visible = vec![*target];
+
+            for import in &file.imports {
+                let top = import.path.split('.').next().unwrap_or_default();
+                let Some(&sibling) = target_index.get(top) else {
+                    if !external_imports
+                        .iter()
+                        .any(|e: &ExternalImport| e.path == import.path)
+                    {
+                        external_imports.push(ExternalImport {
+                            path: import.path.clone(),
+                            kind: classify(top),
+                        });
+                    }
+                    continue;
+                };
+                visible.push(Some(sibling));
+                for to in exporting_modules(&files, &placed, sibling, file, import.testable) {
+                    edges.push(id, to, EdgeKind::Import);
+                }
+            }
+
+            let resolve = |name: &str| {
+                let (qualifier, rest) = name.split_once('.').unwrap_or(("", name));
+                match target_index.get(qualifier) {
+                    Some(&t) => types.get(&(Some(t), rest)).copied(),
+                    None => visible.iter().find_map(|t| types.get(&(*t, name)).copied()),
+                }
+            };
+            for decl in &file.decls {
+                for supertype in &decl.supers {
+                    let Some((to, protocol)) = resolve(supertype) else {
+                        continue;
+                    };
+                    let kind = if protocol {
+                        EdgeKind::Implements
+                    } else {
+                        EdgeKind::Extends
+                    };
+                    edges.push(id, to, kind);
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file
+                    .decls
+                    .iter()
+                    .filter(|d| d.listed)
+                    .map(|d| d.symbol.clone())
+                    .collect(),
+                doc: None,
+                external_imports,
+                group: target.map(|t| targets[t].name.clone()),
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+/// Modules of target `target` that declare a name `file` uses and may see:
+/// `public`/`open` declarations, plus `internal` ones through a
+/// `@testable import`. When `file` names none of them, all modules
+/// exporting something are returned — the import still depends on them.
+fn exporting_modules<'a>(
+    files: &[ParsedFile],
+    placed: &'a [(Option<usize>, String)],
+    target: usize,
+    file: &ParsedFile,
+    testable: bool,
+) -> Vec<&'a str> {
+    let exported = |d: &Decl| {
+        d.listed
+            && !d.symbol.name.contains('.')
+            && (d.symbol.visibility == Visibility::Public
+                || (testable && d.symbol.visibility == Visibility::Internal))
+    };
+    let candidates: Vec<(&ParsedFile, &str)> = files
+        .iter()
+        .zip(placed)
+        .filter(|(_, (t, _))| *t == Some(target))
+        .map(|(f, (_, id))| (f, id.as_str()))
+        .filter(|(f, _)| f.decls.iter().any(exported))
+        .collect();
+    let used: Vec<&str> = candidates
+        .iter()
+        .filter(|(f, _)| {
+            f.decls
+                .iter()
+                .any(|d| exported(d) && file.idents.contains(&d.symbol.name))
+        })
+        .map(|(_, id)| *id)
+        .collect();
+    if used.is_empty() {
+        candidates.into_iter().map(|(_, id)| id).collect()
+    } else {
+        used
+    }
+}
+
+/// `Package.swift`, or a version-specific `Package@swift-5.9.swift`.
+fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy())
+        .is_some_and(|n| n == "Package.swift" || n.starts_with("Package@swift"))
+}
+
+/// A SwiftPM target and the directory holding its sources.
+struct Target {
+    name: String,
+    dir: PathBuf,
+}
+
+/// Targets of every `Package.swift` among `paths`, or of the nearest one
+/// above them when the manifest itself is not being parsed.
+fn read_targets(paths: &[PathBuf]) -> Result<Vec<Target>> {
+    let mut manifests: Vec<PathBuf> = paths
+        .iter()
+        .filter(|p| p.file_name().is_some_and(|n| n == "Package.swift"))
+        .cloned()
+        .collect();
+    if manifests.is_empty() {
+        let root = common_root(paths.iter().map(PathBuf::as_path));
+        manifests.extend(
+            root.ancestors()
+                .map(|a| a.join("Package.swift"))
+                .find(|m| m.is_file()),
+        );
+    }
+
+    let mut targets = Vec::new();
+    for manifest in manifests {
+        let source = read_source(&manifest)?;
+        let package = normalize_path(manifest.parent().unwrap_or(Path::new("")));
+        targets.extend(manifest_targets(&source, &package));
+    }
+    Ok(targets)
+}
+
+/// `.target(name: "Core", path: "Sources/CoreKit")` and friends. Targets
+/// without a `path:` live in SwiftPM's default directories.
+fn manifest_targets(source: &str, package: &Path) -> Vec<Target> {
+    let tokens = tokenize(source, &SYNTAX);
+    let mut out = Vec::new();
+    for i in 0..tokens.len().saturating_sub(2) {
+        let default_dir = match tokens[i + 1].text {
+            "target" | "executableTarget" | "systemLibrary" | "binaryTarget" | "macro" => "Sources",
+            "testTarget" => "Tests",
+            "plugin" => "Plugins",
+            _ => continue,
+        };
+        if !tokens[i].is(".") || !tokens[i + 2].is("(") {
+            continue;
+        }
+        let close = matching(&tokens, i + 2);
+        let mut depth = 0usize;
+        let (mut name, mut path) = (None, None);
+        for j in i + 2..close {
+            let t = tokens[j];
+            if t.is("(") || t.is("[") {
+                depth += 1;
+            } else if t.is(")") || t.is("]") {
+                depth -= 1;
+            } else if depth == 1
+                && t.is_ident()
+                && tokens.get(j + 1).is_some_and(|c| c.is(":"))
+                && let Some(value) = tokens.get(j + 2).filter(|v| v.kind == TokenKind::Str)
+            {
+                let value = value.text.trim_matches('"').to_string();
+                match t.text {
+                    "name" => name = Some(value),
+                    "path" => path = Some(value),
+                    _ => {}
+                }
+            }
+        }
+        if let Some(name) = name {
+            let dir = match path {
+                Some(path) => package.join(path),
+                None => package.join(default_dir).join(&name),
+            };
+            out.push(Target {
+                name,
+                dir: normalize_path(&dir),
+            });
+        }
+    }
+    out
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &["///"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    doc_block_comment: Some("/**"),
+    nested_block_comments: true,
+    quotes: &['"'],
+    triple_quoted_strings: true,
+    verbatim_strings: false,
+    ident_chars: &[],
+};
+
+/// Modifiers that may precede a declaration.
+const MODIFIERS: &[&str] = &[
+    "public",
+    "open",
+    "package",
+    "internal",
+    "fileprivate",
+    "private",
+    "final",
+    "static",
+    "override",
+    "mutating",
+    "nonmutating",
+    "convenience",
+    "required",
+    "lazy",
+    "weak",
+    "unowned",
+    "dynamic",
+    "indirect",
+    "nonisolated",
+    "distributed",
+    "optional",
+    "prefix",
+    "postfix",
+    "infix",
+];
+
+/// Declaration kinds an `import` may name (`import struct Foo.Bar`).
+const IMPORT_KINDS: &[&str] = &[
+    "typealias",
+    "struct",
+    "class",
+    "enum",
+    "protocol",
+    "let",
+    "var",
+    "func",
+];
+
+/// Keywords that can follow `class` when it is a modifier (`class func`).
+const MEMBER_KEYWORDS: &[&str] = &["func", "var", "let", "subscript", "init", "typealias"];
+
+/// Everything extracted from a single `.swift` file.
+struct ParsedFile {
+    path: PathBuf,
+    imports: Vec<Import>,
+    decls: Vec<Decl>,
+    /// Every identifier in the file, to tell which imported names it uses.
+    idents: HashSet<String>,
+}
+
+/// `import Foo`, `@testable import Foo`, `import struct Foo.Bar`.
+struct Import {
+    path: String,
+    testable: bool,
+}
+
+/// A declaration plus the (unresolved) supertypes or conformances it
+/// names.
+struct Decl {
+    symbol: Symbol,
+    is_type: bool,
+    protocol: bool,
+    /// Extensions contribute edges but are not symbols themselves.
+    listed: bool,
+    supers: Vec<String>,
+}
+
+/// Brace scopes the scanner tracks.
+enum Block {
+    Type {
+        name: String,
+        /// Default visibility of members for an `extension`; `None` for type
+        /// bodies, whose members are skipped.
+        extension: Option<Visibility>,
+    },
+    /// Function bodies, initializers, computed properties — skipped
+    /// wholesale.
+    Other,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let tokens = tokenize(src, &SYNTAX);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        imports: Vec::new(),
+        decls: Vec::new(),
+        idents: tokens
+            .iter()
+            .filter(|t| t.is_ident())
+            .map(|t| t.text.to_string())
+            .collect(),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Block::Other)) {
+            if token.is("{") {
+                stack.push(Block::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let (outer, extension) = match stack.last() {
+            Some(Block::Type { name, extension }) => (Some(name.as_str()), *extension),
+            _ => (None, None),
+        };
+
+        match token.text {
+            _ if token.kind != TokenKind::Ident && token.kind != TokenKind::Punct => {}
+            "{" => stack.push(Block::Other),
+            "}" => {
+                stack.pop();
+            }
+            "import" if stack.is_empty() => {
+                let testable = i >= 2 && tokens[i - 2].is("@") && tokens[i - 1].is("testable");
+                let mut j = i + 1;
+                // `import struct Foo.Bar`
+                if tokens
+                    .get(j)
+                    .is_some_and(|t| IMPORT_KINDS.contains(&t.text))
+                {
+                    j += 1;
+                }
+                let (path, end) = dotted_name(&tokens, j);
+                if !path.is_empty() {
+                    file.imports.push(Import { path, testable });
+                }
+                i = end;
+                continue;
+            }
+            "class" | "struct" | "enum" | "protocol" | "actor" | "extension"
+                if tokens.get(i + 1).is_some_and(|t| {
+                    t.is_ident()
+                        && !MEMBER_KEYWORDS.contains(&t.text)
+                        && !MODIFIERS.contains(&t.text)
+                }) =>
+            {
+                let (decl, end) = type_declaration(&tokens, i, outer, extension, src);
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Block::Type {
+                        name: decl.symbol.name.clone(),
+                        extension: token.is("extension").then_some(decl.symbol.visibility),
+                    });
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                file.decls.push(decl);
+                continue;
+            }
+            "func" => {
+                let (decl, end) = function(&tokens, i, outer, extension, src);
+                // Members of type bodies are skipped; top-level and extension
+                // functions are symbols.
+                if outer.is_none() || extension.is_some() {
+                    file.decls.extend(decl);
+                }
+                if tokens.get(end).is_some_and(|t| t.is("{")) {
+                    stack.push(Block::Other);
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+                continue;
+            }
+            "typealias" if outer.is_none() || extension.is_some() => {
+                if let Some((decl, end)) =
+                    simple_declaration(&tokens, i, SymbolKind::TypeAlias, outer, extension, src)
+                {
+                    file.decls.push(decl);
+                    i = end;
+                    continue;
+                }
+            }
+            "let" if outer.is_none() => {
+                if let Some((decl, end)) =
+                    simple_declaration(&tokens, i, SymbolKind::Constant, None, None, src)
+                {
+                    file.decls.push(decl);
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// Parse a type declaration or extension whose keyword is at `keyword`.
+/// Returns the declaration and the index just past its header (the `{` of
+/// the body, when there is one).
+fn type_declaration(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    extension: Option<Visibility>,
+    src: &str,
+) -> (Decl, usize) {
+    let start = modifiers_start(tokens, keyword);
+    let is_extension = tokens[keyword].is("extension");
+    let (name, mut end) = dotted_name(tokens, keyword + 1);
+    end = skip_generics(tokens, end);
+
+    let mut supers = Vec::new();
+    if tokens.get(end).is_some_and(|t| t.is(":")) {
+        end += 1;
+        while let Some(t) = tokens.get(end) {
+            if t.is("{") || t.is("where") {
+                break;
+            }
+            if t.is("@") {
+                // `@unchecked Sendable`
+                end += 2;
+                continue;
+            }
+            if t.is_ident() {
+                let (name, after) = dotted_name(tokens, end);
+                supers.push(name);
+                end = skip_generics(tokens, after);
+                continue;
+            }
+            end += 1;
+        }
+    }
+    end = skip_where_clause(tokens, end);
+
+    let kind = match tokens[keyword].text {
+        "protocol" => SymbolKind::Trait,
+        "enum" => SymbolKind::Enum,
+        _ => SymbolKind::Struct,
+    };
+    let symbol = Symbol {
+        name: match outer {
+            Some(outer) if !is_extension => format!("{outer}.{name}"),
+            _ => name,
+        },
+        kind,
+        visibility: visibility(&modifiers(&tokens[start..keyword]), extension),
+        line: tokens[start].line,
+        cell: None,
+        doc: swift_doc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    (
+        Decl {
+            symbol,
+            is_type: !is_extension,
+            protocol: tokens[keyword].is("protocol"),
+            listed: !is_extension,
+            supers,
+        },
+        end,
+    )
+}
+
+/// Parse the function whose `func` keyword is at `keyword`. Returns the
+/// declaration and the index just past its header — the `{` of its body,
+/// when there is one.
+fn function(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    outer: Option<&str>,
+    extension: Option<Visibility>,
+    src: &str,
+) -> (Option<Decl>, usize) {
+    let start = modifiers_start(tokens, keyword);
+    // `name`, or an operator (`==`, `<*>`).
+    let mut j = keyword + 1;
+    let name = match tokens.get(j) {
+        Some(t) if t.is_ident() => {
+            j += 1;
+            t.text.to_string()
+        }
+        _ => {
+            let mut op = String::new();
+            while let Some(t) = tokens
+                .get(j)
+                .filter(|t| t.kind == TokenKind::Punct && !t.is("("))
+            {
+                op.push_str(t.text);
+                j += 1;
+            }
+            op
+        }
+    };
+    if name.is_empty() {
+        return (None, j);
+    }
+    j = skip_generics(tokens, j);
+    if !tokens.get(j).is_some_and(|t| t.is("(")) {
+        return (None, j);
+    }
+
+    // Effects and the return type run to the body, or to the end of the
+    // line.
+    let mut end = matching(tokens, j) + 1;
+    let mut line = tokens[end - 1].line;
+    while let Some(t) = tokens.get(end) {
+        if t.is("{") || (t.line != line && !tokens[end - 1].is(">") && !t.is("->")) {
+            break;
+        }
+        if t.is("(") || t.is("[") {
+            end = matching(tokens, end);
+        }
+        line = tokens[end].line;
+        end += 1;
+    }
+
+    let symbol = Symbol {
+        name: match outer {
+            Some(outer) => format!("{outer}.{name}"),
+            None => name,
+        },
+        kind: SymbolKind::Function,
+        visibility: visibility(&modifiers(&tokens[start..keyword]), extension),
+        line: tokens[start].line,
+        cell: None,
+        doc: swift_doc(tokens, start),
+        signature: Some(signature(tokens, start, end, src)),
+        members: Vec::new(),
+    };
+    let decl = Decl {
+        symbol,
+        is_type: false,
+        protocol: false,
+        listed: true,
+        supers: Vec::new(),
+    };
+    (Some(decl), end)
+}
+
+/// `typealias Name<T> = Target` or a top-level `let NAME: Type = value` at
+/// `keyword`; the signature stops before the initializer.
+fn simple_declaration(
+    tokens: &[Token<'_>],
+    keyword: usize,
+    kind: SymbolKind,
+    outer: Option<&str>,
+    extension: Option<Visibility>,
+    src: &str,
+) -> Option<(Decl, usize)> {
+    let start = modifiers_start(tokens, keyword);
+    let name = tokens.get(keyword + 1).filter(|t| t.is_ident())?;
+    let end = line_end(tokens, keyword);
+    let header_end = match kind {
+        SymbolKind::TypeAlias => end,
+        _ => tokens[keyword..end]
+            .iter()
+            .position(|t| t.is("="))
+            .map_or(end, |p| keyword + p),
+    };
+    let symbol = Symbol {
+        name: match outer {
+            Some(outer) => format!("{outer}.{}", name.text),
+            None => name.text.to_string(),
+        },
+        kind,
+        visibility: visibility(&modifiers(&tokens[start..keyword]), extension),
+        line: tokens[start].line,
+        cell: None,
+        doc: swift_doc(tokens, start),
+        signature: Some(signature(tokens, start, header_end, src)),
+        members: Vec::new(),
+    };
+    Some((
+        Decl {
+            symbol,
+            is_type: false,
+            protocol: false,
+            listed: true,
+            supers: Vec::new(),
+        },
+        end,
+    ))
+}
+
+/// Index of the first modifier or attribute in front of the keyword at
+/// `keyword`. Swift needs no `;` between declarations, so the start is
+/// found by walking backwards rather than from the previous statement.
+fn modifiers_start(tokens: &[Token<'_>], keyword: usize) -> usize {
+    let mut start = keyword;
+    while start > 0 {
+        let prev = tokens[start - 1];
+        let is_modifier = prev.is_ident()
+            && (MODIFIERS.contains(&prev.text)
+                || (prev.text == "class" && MEMBER_KEYWORDS.contains(&tokens[keyword].text)));
+        if is_modifier {
+            start -= 1;
+        } else if prev.is(")") {
+            // `private(set)`, `@available(iOS 15, *)`.
+            let open = open_paren(tokens, start - 1);
+            match open.checked_sub(1).map(|n| tokens[n]) {
+                Some(t) if t.is_ident() && MODIFIERS.contains(&t.text) => start = open - 1,
+                Some(t) if t.is_ident() && open >= 2 && tokens[open - 2].is("@") => {
+                    start = open - 2
+                }
+                _ => break,
+            }
+        } else if prev.is_ident() && start >= 2 && tokens[start - 2].is("@") {
+            start -= 2;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Index of the `(` matching the `)` at `close`.
+fn open_paren(tokens: &[Token<'_>], close: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = close;
+    loop {
+        if tokens[i].is(")") {
+            depth += 1;
+        } else if tokens[i].is("(") {
+            depth -= 1;
+            if depth == 0 || i == 0 {
+                return i;
+            }
+        }
+        if i == 0 {
+            return 0;
+        }
+        i -= 1;
+    }
+}
+
+/// Index past a `<...>` list at `at`, or `at` when there is none.
+fn skip_generics(tokens: &[Token<'_>], at: usize) -> usize {
+    if tokens.get(at).is_some_and(|t| t.is("<")) {
+        matching(tokens, at) + 1
+    } else {
+        at
+    }
+}
+
+/// Index of the `{` ending a `where T: Bound, U == V` clause at `at`.
+fn skip_where_clause(tokens: &[Token<'_>], at: usize) -> usize {
+    if !tokens.get(at).is_some_and(|t| t.is("where")) {
+        return at;
+    }
+    tokens[at..]
+        .iter()
+        .position(|t| t.is("{"))
+        .map_or(tokens.len(), |p| at + p)
+}
+
+/// Index of the first token after the line `at` is on, jumping over
+/// brackets that span lines.
+fn line_end(tokens: &[Token<'_>], at: usize) -> usize {
+    let line = tokens[at].line;
+    let mut end = at;
+    while let Some(t) = tokens.get(end) {
+        if t.line != line || t.is(";") {
+            break;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            end = matching(tokens, end);
+        }
+        end += 1;
+    }
+    end
+}
+
+/// `A.B.C` starting at `start`; returns the name and the index just past
+/// it.
+fn dotted_name(tokens: &[Token<'_>], start: usize) -> (String, usize) {
+    let mut name = String::new();
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        let expects_ident = name.is_empty() || name.ends_with('.');
+        if expects_ident && t.is_ident() {
+            name.push_str(t.text);
+        } else if !expects_ident && t.is(".") {
+            name.push('.');
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    if name.ends_with('.') {
+        name.pop();
+        i -= 1;
+    }
+    (name, i)
+}
+
+/// Modifier keywords in `tokens`, skipping the setter-only access of
+/// `private(set)`.
+fn modifiers<'a>(tokens: &[Token<'a>]) -> Vec<&'a str> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, t)| {
+            t.is_ident()
+                && MODIFIERS.contains(&t.text)
+                && !tokens.get(i + 1).is_some_and(|n| n.is("("))
+        })
+        .map(|(_, t)| t.text)
+        .collect()
+}
+
+/// Swift declarations are `internal` unless marked otherwise; members of
+/// an extension default to the extension's own access level.
+fn visibility(modifiers: &[&str], default: Option<Visibility>) -> Visibility {
+    if modifiers.contains(&"public") || modifiers.contains(&"open") {
+        Visibility::Public
+    } else if modifiers.contains(&"private") || modifiers.contains(&"fileprivate") {
+        Visibility::Private
+    } else if modifiers.contains(&"internal") || modifiers.contains(&"package") {
+        Visibility::Internal
+    } else {
+        default.unwrap_or(Visibility::Internal)
+    }
+}
+
+/// The `///` lines or `/** ... */` block directly in front of the
+/// declaration at `start`, up to its `- Parameter` / `- Returns` callouts.
+fn swift_doc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    let first = tokens[..start]
+        .iter()
+        .rposition(|t| t.kind != TokenKind::Doc)
+        .map_or(0, |p| p + 1);
+    let lines = tokens[first..start]
+        .iter()
+        .flat_map(|t| t.text.lines())
+        .map(|l| {
+            let l = l.trim();
+            let l = l.strip_prefix("///").unwrap_or(l);
+            let l = l.trim_start_matches("/**").trim_end_matches("*/").trim();
+            l.strip_prefix('*').unwrap_or(l).trim().to_string()
+        })
+        .take_while(|l| {
+            !["- Parameter", "- Returns", "- Throws"]
+                .iter()
+                .any(|callout| l.starts_with(callout))
+        })
+        .collect();
+    join_doc(lines)
+}
+
+/// Source text of `tokens[start..end]`, whitespace collapsed.
+fn signature(tokens: &[Token<'_>], start: usize, end: usize, src: &str) -> String {
+    let last = tokens[end.clamp(start + 1, tokens.len()) - 1];
+    collapse_whitespace(&src[tokens[start].offset..last.offset + last.text.len()])
+}
+
+/// Apple's system modules; anything else is a package dependency.
+const SYSTEM_MODULES: &[&str] = &[
+    "Swift",
+    "Foundation",
+    "FoundationEssentials",
+    "Dispatch",
+    "Darwin",
+    "Glibc",
+    "Musl",
+    "WinSDK",
+    "ObjectiveC",
+    "os",
+    "XCTest",
+    "Testing",
+    "Combine",
+    "Observation",
+    "SwiftUI",
+    "UIKit",
+    "AppKit",
+    "CoreData",
+    "CoreGraphics",
+    "CoreFoundation",
+    "CoreLocation",
+    "QuartzCore",
+    "Security",
+    "CryptoKit",
+    "Network",
+    "RegexBuilder",
+    "Synchronization",
+];
+
+fn classify(module: &str) -> ExternalKind {
+    if SYSTEM_MODULES.contains(&module) || module.starts_with('_') {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
@@ -0,0 +1,333 @@
+//! Terraform driver.
+//!
+//! `.tf` files are parsed with `hcl-rs`'s lossless [`hcl::edit`] parser,
+//! which keeps the byte spans and comments the plain `hcl::Body` drops.
+//! Terraform treats every directory of `.tf` files as one module, so each
+//! directory becomes a [`Module`] (id relative to the common root, the
+//! root directory itself named after its last path component).
+//!
+//! Declarations become symbols named the way Terraform expressions refer
+//! to them:
+//!
+//! | Block              | Symbol     | Kind                     | Visibility |
+//! | ------------------ | ---------- | ------------------------ | ---------- |
+//! | `resource "t" "n"` | `t.n`      | [`SymbolKind::Struct`]   | private    |
+//! | `data "t" "n"`     | `data.t.n` | [`SymbolKind::Struct`]   | private    |
+//! | `module "n"`       | `module.n` | [`SymbolKind::Module`]   | private    |
+//! | `variable "n"`     | `var.n`    | [`SymbolKind::Constant`] | public     |
+//! | `output "n"`       | `output.n` | [`SymbolKind::Constant`] | public     |
+//!
+//! Variables and outputs are a module's interface, hence public. Docs come
+//! from the `description` argument, else from the `#` / `//` comment lines
+//! directly above the block.
+//!
+//! `module` blocks whose `source` is a local path (`./modules/network`)
+//! become [`EdgeKind::Import`] edges to that directory's module; other
+//! sources (registry, git, ...) and `required_providers` are recorded as
+//! [`ExternalImport`]s. Expressions referring to a child module
+//! (`module.network.vpc_id`, `depends_on = [module.network]`) become
+//! [`EdgeKind::Uses`] edges to it. Resource references
+//! (`aws_s3_bucket.logs.arn`) always resolve inside the module declaring
+//! them, so they add no edge of their own.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use hcl::edit::expr::{Expression, Traversal, TraversalOperator};
+use hcl::edit::structure::{Block, Body};
+use hcl::edit::visit::{Visit, visit_traversal};
+use hcl::edit::{Decorate, Span};
+
+use super::common::{EdgeSet, common_root, has_extension, join_doc, normalize_path, scan_each};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Conventional directories holding a project's Terraform code.
+const TF_DIRS: &[&str] = &["terraform", "infra"];
+
+/// Terraform driver (detects `.tf` files at the root or in `terraform/` or
+/// `infra/`).
+#[derive(Default)]
+pub struct TerraformDriver;
+
+impl Driver for TerraformDriver {
+    fn name(&self) -> &'static str {
+        "terraform"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        has_tf(project_root) || !self.source_dirs(project_root).is_empty()
+    }
+
+    fn detects_loose_sources(&self, _project_root: &Path) -> bool {
+        true
+    }
+
+    fn source_dirs(&self, project_root: &Path) -> Vec<PathBuf> {
+        TF_DIRS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|dir| has_tf(&project_root.join(dir)))
+            .collect()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["tf"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(paths, |p| has_extension(p, &["tf"]), extract_file)?;
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+
+        // Files of one directory form one module, in discovery order.
+        let mut dirs: Vec<(PathBuf, Vec<&TfFile>)> = Vec::new();
+        for file in &files {
+            let dir = normalize_path(file.path.parent().unwrap_or(Path::new("")));
+            match dirs.iter_mut().find(|(d, _)| *d == dir) {
+                Some((_, files)) => files.push(file),
+                None => dirs.push((dir, vec![file])),
+            }
+        }
+        let ids: HashMap<&Path, String> = dirs
+            .iter()
+            .map(|(dir, _)| (dir.as_path(), module_id(dir, &root)))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (dir, files) in &dirs {
+            let id = &ids[dir.as_path()];
+            let mut external_imports: Vec<ExternalImport> = Vec::new();
+            let mut external = |path: &str| {
+                if !external_imports.iter().any(|e| e.path == path) {
+                    external_imports.push(ExternalImport {
+                        path: path.to_string(),
+                        kind: ExternalKind::ThirdParty,
+                    });
+                }
+            };
+
+            // `module` block name → module id of its local source.
+            let mut children: HashMap<&str, &str> = HashMap::new();
+            for (name, source) in files.iter().flat_map(|f| &f.calls) {
+                if !(source.starts_with("./") || source.starts_with("../")) {
+                    external(source);
+                    continue;
+                }
+                if let Some(to) = ids.get(normalize_path(&dir.join(source)).as_path()) {
+                    children.insert(name, to);
+                    edges.push(id, to, EdgeKind::Import);
+                }
+            }
+            for provider in files.iter().flat_map(|f| &f.providers) {
+                external(provider);
+            }
+            for name in files.iter().flat_map(|f| &f.references) {
+                if let Some(to) = children.get(name.as_str()) {
+                    edges.push(id, to, EdgeKind::Uses);
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: id.rsplit('/').next().unwrap_or(id).to_string(),
+                path: dir.clone(),
+                symbols: files.iter().flat_map(|f| f.symbols.clone()).collect(),
+                doc: None,
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+        Ok(model)
+    }
+}
+
+fn has_tf(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| has_extension(&e.path(), &["tf"]) && e.path().is_file())
+    })
+}
+
+/// `dir` relative to `root`, `/`-separated; the root directory itself is
+/// named after its last component.
+fn module_id(dir: &Path, root: &Path) -> String {
+    let rel = dir.strip_prefix(root).unwrap_or(dir);
+    let id = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    if !id.is_empty() {
+        return id;
+    }
+    dir.file_name()
+        .map_or_else(|| "root".to_string(), |n| n.to_string_lossy().into_owned())
+}
+
+struct TfFile {
+    path: PathBuf,
+    symbols: Vec<Symbol>,
+    /// `(name, source)` of every `module` block.
+    calls: Vec<(String, String)>,
+    /// `source` of every provider in `required_providers`.
+    providers: Vec<String>,
+    /// Names of the child modules expressions refer to (`module.<name>`).
+    references: Vec<String>,
+}
+
+/// Extract the blocks of one file; a file that fails to parse is logged
+/// and contributes nothing to its module.
+fn extract_file(path: &Path, source: &str) -> Result<TfFile> {
+    let mut file = TfFile {
+        path: path.to_path_buf(),
+        symbols: Vec::new(),
+        calls: Vec::new(),
+        providers: Vec::new(),
+        references: Vec::new(),
+    };
+    let body: Body = match hcl::edit::parser::parse_body(source) {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "skipping malformed Terraform file");
+            return Ok(file);
+        }
+    };
+
+    for block in body.blocks() {
+        let labels: Vec<&str> = block.labels.iter().map(|l| l.as_str()).collect();
+        let (name, kind, visibility) = match (block.ident.as_str(), labels.as_slice()) {
+            ("resource", [ty, name]) => (
+                format!("{ty}.{name}"),
+                SymbolKind::Struct,
+                Visibility::Private,
+            ),
+            ("data", [ty, name]) => (
+                format!("data.{ty}.{name}"),
+                SymbolKind::Struct,
+                Visibility::Private,
+            ),
+            ("module", [name]) => {
+                if let Some(source) = string_attribute(&block.body, "source") {
+                    file.calls.push((name.to_string(), source));
+                }
+                (
+                    format!("module.{name}"),
+                    SymbolKind::Module,
+                    Visibility::Private,
+                )
+            }
+            ("variable", [name]) => (
+                format!("var.{name}"),
+                SymbolKind::Constant,
+                Visibility::Public,
+            ),
+            ("output", [name]) => (
+                format!("output.{name}"),
+                SymbolKind::Constant,
+                Visibility::Public,
+            ),
+            ("terraform", []) => {
+                for providers in block.body.get_blocks("required_providers") {
+                    file.providers.extend(provider_sources(&providers.body));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let line = block.span().map_or(1, |span| {
+            source[..span.start].matches('\n').count() as u32 + 1
+        });
+        file.symbols.push(Symbol {
+            name,
+            kind,
+            visibility,
+            line,
+            cell: None,
+            doc: string_attribute(&block.body, "description").or_else(|| block_doc(block)),
+            signature: Some(signature(block)),
+            members: Vec::new(),
+        });
+    }
+
+    let mut references = ModuleReferences(Vec::new());
+    references.visit_body(&body);
+    file.references = references.0;
+    Ok(file)
+}
+
+/// Header of a block as written: `resource "aws_s3_bucket" "logs"`.
+fn signature(block: &Block) -> String {
+    let mut signature = block.ident.as_str().to_string();
+    for label in &block.labels {
+        signature.push_str(&format!(" \"{}\"", label.as_str()));
+    }
+    signature
+}
+
+/// Value of the attribute `key` when it is a plain string literal.
+fn string_attribute(body: &Body, key: &str) -> Option<String> {
+    match &body.get_attribute(key)?.value {
+        Expression::String(s) => Some(s.value().to_string()),
+        _ => None,
+    }
+}
+
+/// `source` of every provider listed in a `required_providers` block
+/// (`aws = { source = "hashicorp/aws" }`).
+fn provider_sources(body: &Body) -> Vec<String> {
+    body.attributes()
+        .filter_map(|attr| match &attr.value {
+            Expression::Object(object) => object.iter().find_map(|(key, value)| {
+                match (key.as_ident().map(|k| k.as_str()), value.expr()) {
+                    (Some("source"), Expression::String(s)) => Some(s.value().to_string()),
+                    _ => None,
+                }
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `#` / `//` comment lines directly above `block`.
+fn block_doc(block: &Block) -> Option<String> {
+    let prefix = block.decor().prefix().map_or("", |p| &**p);
+    // Drop the indentation before the block, then the newline ending the
+    // line above it.
+    let prefix = prefix.trim_end_matches([' ', '\t']);
+    let prefix = prefix.strip_suffix('\n').unwrap_or(prefix);
+    let mut lines = Vec::new();
+    for line in prefix.lines().rev() {
+        let line = line.trim();
+        let Some(text) = line.strip_prefix('#').or_else(|| line.strip_prefix("//")) else {
+            break;
+        };
+        lines.push(text.trim().to_string());
+    }
+    lines.reverse();
+    join_doc(lines)
+}
+
+/// Collects `<name>` from every `module.<name>...` traversal.
+struct ModuleReferences(Vec<String>);
+
+impl Visit for ModuleReferences {
+    fn visit_traversal(&mut self, node: &Traversal) {
+        if let Expression::Variable(root) = &node.expr
+            && root.as_str() == "module"
+            && let Some(TraversalOperator::GetAttr(name)) =
+                node.operators.first().map(|o| o.value())
+            && !self.0.iter().any(|n| n == name.as_str())
+        {
+            self.0.push(name.as_str().to_string());
+        }
+        visit_traversal(self, node);
+    }
+}
@@ -1,11 +1,28 @@
-//! TypeScript driver — NEW_ROADMAP Phase 5.a.
+//! TypeScript driver (tree-sitter-based) — NEW_ROADMAP Phase 5.a.
+//!
+//! Every `.ts` / `.tsx` file becomes one [`Module`] whose id is its path
+//! (without extension, `/`-separated) relative to the common root of all
+//! TypeScript sources.
+//!
+//! Import specifiers are resolved the way `tsc` would: relative paths,
+//! `compilerOptions.paths` aliases and `baseUrl` from the nearest
+//! `tsconfig.json` (following relative `extends`), with `.ts` / `.tsx` /
+//! `index.ts` probing. Bare specifiers that resolve nowhere are treated as
+//! `node_modules` packages and dropped.
 
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use serde_json::Value;
+use tree_sitter::{Language, Node};
 
-use super::Driver;
-use crate::ir::CodeModel;
+use super::common::{
+    EdgeSet, clean_block_comment, common_root, header, line, normalize_path, parse_each,
+    relative_module_id, text,
+};
+use super::{Driver, FileClaims};
+use crate::ir::{CodeModel, EdgeKind, Module, ModuleId, Symbol, SymbolKind, Visibility};
 
 /// TypeScript driver (detects `tsconfig.json` or `package.json` with a
 /// TypeScript dependency).
@@ -18,14 +35,621 @@ impl Driver for TypeScriptDriver {
     }
 
     fn detect(&self, project_root: &Path) -> bool {
-        // TODO(Phase 5.a): also inspect package.json for a `typescript`
-        //                  entry in dependencies / devDependencies.
         project_root.join("tsconfig.json").is_file()
+            || package_json_has_typescript(&project_root.join("package.json"))
     }
 
-    fn parse(&self, _paths: &[PathBuf]) -> Result<CodeModel> {
-        // TODO(Phase 5.a): use `tree-sitter-typescript` to extract
-        //                  classes, interfaces, functions, exports.
-        Ok(CodeModel::new(self.name()))
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["ts", "mts", "cts", "tsx"])
     }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = parse_each(paths, language_for, |path, source, tree| {
+            Ok(extract_file(path, source.as_bytes(), tree.root_node()))
+        })?;
+
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        let ids: Vec<String> = files
+            .iter()
+            .map(|f| relative_module_id(&f.path, &root))
+            .collect();
+        let by_path: HashMap<PathBuf, &str> = files
+            .iter()
+            .zip(&ids)
+            .map(|(f, id)| (normalize_path(&f.path), id.as_str()))
+            .collect();
+
+        let mut configs = TsConfigCache::default();
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let dir = file.path.parent().unwrap_or(Path::new(""));
+            let config = configs.nearest(dir);
+            let mut bindings = HashMap::new();
+            for import in &file.imports {
+                let Some(target) = resolve_specifier(&import.specifier, dir, config, &by_path)
+                else {
+                    continue;
+                };
+                edges.push(id, target, EdgeKind::Import);
+                for name in &import.bindings {
+                    bindings.insert(name.as_str(), target);
+                }
+            }
+            for (expr, kind) in &file.heritage {
+                let head = expr.split(['.', '<']).next().unwrap_or(expr).trim();
+                if let Some(target) = bindings.get(head) {
+                    edges.push(id, target, *kind);
+                }
+            }
+        }
+
+        let mut model = CodeModel::new(self.name());
+        model.edges = edges.into_edges();
+        model.modules = files
+            .into_iter()
+            .zip(ids)
+            .map(|(file, id)| Module {
+                name: id.rsplit('/').next().unwrap_or(&id).to_string(),
+                id: ModuleId(id),
+                path: file.path,
+                symbols: file.symbols,
+                doc: file.doc,
+                external_imports: Vec::new(),
+                group: None,
+                container: None,
+            })
+            .collect();
+
+        Ok(model)
+    }
+}
+
+/// `true` when `package.json` lists `typescript` in `dependencies` or
+/// `devDependencies`.
+fn package_json_has_typescript(path: &Path) -> bool {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return false;
+    };
+    let Ok(manifest) = serde_json::from_str::<Value>(&text) else {
+        return false;
+    };
+    ["dependencies", "devDependencies"]
+        .iter()
+        .any(|section| manifest[section].get("typescript").is_some())
+}
+
+/// `.tsx` needs the TSX grammar; declaration files (`.d.ts`) are skipped.
+fn language_for(path: &Path) -> Option<Language> {
+    let name = path.file_name()?.to_str()?;
+    if name.ends_with(".d.ts") {
+        return None;
+    }
+    match path.extension()?.to_str()? {
+        "ts" | "mts" | "cts" => Some(tree_sitter_typescript::language_typescript()),
+        "tsx" => Some(tree_sitter_typescript::language_tsx()),
+        _ => None,
+    }
+}
+
+/// Everything extracted from a single file.
+struct ParsedFile {
+    path: PathBuf,
+    doc: Option<String>,
+    symbols: Vec<Symbol>,
+    imports: Vec<Import>,
+    /// `extends` / `implements` targets, as written.
+    heritage: Vec<(String, EdgeKind)>,
+}
+
+/// An import, re-export, `require` or dynamic `import()`.
+struct Import {
+    specifier: String,
+    /// Local names the statement binds (`import A, { b as c } from` → `A`, `c`).
+    bindings: Vec<String>,
+}
+
+fn extract_file(path: &Path, src: &[u8], root: Node<'_>) -> ParsedFile {
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        doc: file_doc(root, src),
+        symbols: Vec::new(),
+        imports: Vec::new(),
+        heritage: Vec::new(),
+    };
+    collect_imports(root, src, &mut file.imports);
+
+    let mut exported = HashSet::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            "export_statement" => {
+                if let Some(decl) = node.child_by_field_name("declaration") {
+                    let doc = jsdoc(node, src);
+                    declaration(decl, Visibility::Public, doc, src, &mut file);
+                }
+                let mut c = node.walk();
+                for clause in node.named_children(&mut c) {
+                    if clause.kind() == "export_clause"
+                        && node.child_by_field_name("source").is_none()
+                    {
+                        let mut cc = clause.walk();
+                        for spec in clause.named_children(&mut cc) {
+                            if let Some(name) = spec.child_by_field_name("name") {
+                                exported.insert(text(name, src).to_string());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => declaration(node, Visibility::Private, jsdoc(node, src), src, &mut file),
+        }
+    }
+
+    // `export { a, b }` after the fact.
+    for symbol in &mut file.symbols {
+        if exported.contains(&symbol.name) {
+            symbol.visibility = Visibility::Public;
+        }
+    }
+
+    file
+}
+
+fn declaration(
+    node: Node<'_>,
+    visibility: Visibility,
+    doc: Option<String>,
+    src: &[u8],
+    file: &mut ParsedFile,
+) {
+    let kind = match node.kind() {
+        "class_declaration" | "abstract_class_declaration" => {
+            file.heritage.extend(class_heritage(node, src));
+            SymbolKind::Struct
+        }
+        "interface_declaration" => {
+            file.heritage.extend(interface_heritage(node, src));
+            SymbolKind::Trait
+        }
+        "function_declaration" | "generator_function_declaration" | "function_signature" => {
+            SymbolKind::Function
+        }
+        "enum_declaration" => SymbolKind::Enum,
+        "type_alias_declaration" => SymbolKind::TypeAlias,
+        "internal_module" | "module" => SymbolKind::Module,
+        "lexical_declaration" => {
+            const_declarators(node, visibility, doc, src, file);
+            return;
+        }
+        "ambient_declaration" | "expression_statement" => {
+            if let Some(inner) = node.named_child(0) {
+                declaration(inner, visibility, doc, src, file);
+            }
+            return;
+        }
+        _ => return,
+    };
+    let Some(name) = node.child_by_field_name("name") else {
+        return;
+    };
+    let end = node
+        .child_by_field_name("body")
+        .or_else(|| node.child_by_field_name("value"));
+
+    file.symbols.push(Symbol {
+        name: text(name, src).trim_matches(['"', '\'']).to_string(),
+        kind,
+        visibility,
+        line: line(node),
+        cell: None,
+        doc,
+        signature: Some(
+            header(node, end, src)
+                .trim_end_matches(['=', ';', ' '])
+                .to_string(),
+        ),
+        members: Vec::new(),
+    });
+}
+
+/// `const` declarations: arrow functions / function expressions are
+/// functions, everything else is a constant. `let` bindings are skipped.
+fn const_declarators(
+    node: Node<'_>,
+    visibility: Visibility,
+    doc: Option<String>,
+    src: &[u8],
+    file: &mut ParsedFile,
+) {
+    if node
+        .child_by_field_name("kind")
+        .is_none_or(|k| text(k, src) != "const")
+    {
+        return;
+    }
+    let mut cursor = node.walk();
+    for declarator in node.named_children(&mut cursor) {
+        let Some(name) = declarator
+            .child_by_field_name("name")
+            .filter(|n| n.kind() == "identifier")
+        else {
+            continue;
+        };
+        let value = declarator.child_by_field_name("value");
+        let kind = match value.map(|v| v.kind()) {
+            Some("arrow_function" | "function" | "function_expression") => SymbolKind::Function,
+            _ => SymbolKind::Constant,
+        };
+        let signature = format!(
+            "const {}",
+            header(declarator, value, src).trim_end_matches(['=', ' '])
+        );
+        file.symbols.push(Symbol {
+            name: text(name, src).to_string(),
+            kind,
+            visibility,
+            line: line(declarator),
+            cell: None,
+            doc: doc.clone(),
+            signature: Some(signature),
+            members: Vec::new(),
+        });
+    }
+}
+
+fn class_heritage(class: Node<'_>, src: &[u8]) -> Vec<(String, EdgeKind)> {
+    let mut out = Vec::new();
+    let mut cursor = class.walk();
+    for heritage in class
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "class_heritage")
+    {
+        let mut c = heritage.walk();
+        for clause in heritage.named_children(&mut c) {
+            let kind = match clause.kind() {
+                "extends_clause" => EdgeKind::Extends,
+                "implements_clause" => EdgeKind::Implements,
+                _ => continue,
+            };
+            let mut cc = clause.walk();
+            for ty in clause.named_children(&mut cc) {
+                if ty.kind() != "type_arguments" {
+                    out.push((text(ty, src).to_string(), kind));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn interface_heritage(interface: Node<'_>, src: &[u8]) -> Vec<(String, EdgeKind)> {
+    let mut out = Vec::new();
+    let mut cursor = interface.walk();
+    for clause in interface
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == "extends_type_clause")
+    {
+        let mut c = clause.walk();
+        for ty in clause.children_by_field_name("type", &mut c) {
+            out.push((text(ty, src).to_string(), EdgeKind::Extends));
+        }
+    }
+    out
+}
+
+/// Static imports, `export ... from`, `import x = require()` and dynamic
+/// `import("...")` calls anywhere in the file.
+fn collect_imports(node: Node<'_>, src: &[u8], out: &mut Vec<Import>) {
+    match node.kind() {
+        "import_statement" => {
+            let mut bindings = Vec::new();
+            let mut specifier = node.child_by_field_name("source");
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                match child.kind() {
+                    "import_clause" => import_clause_bindings(child, src, &mut bindings),
+                    "import_require_clause" => {
+                        specifier = child.child_by_field_name("source");
+                        let mut c = child.walk();
+                        bindings.extend(
+                            child
+                                .named_children(&mut c)
+                                .filter(|n| n.kind() == "identifier")
+                                .map(|n| text(n, src).to_string()),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(specifier) = specifier {
+                out.push(Import {
+                    specifier: string_value(specifier, src),
+                    bindings,
+                });
+            }
+        }
+        "export_statement" => {
+            if let Some(source) = node.child_by_field_name("source") {
+                out.push(Import {
+                    specifier: string_value(source, src),
+                    bindings: Vec::new(),
+                });
+            }
+            if let Some(decl) = node.child_by_field_name("declaration") {
+                collect_imports(decl, src, out);
+            }
+        }
+        "call_expression"
+            if node
+                .child_by_field_name("function")
+                .is_some_and(|f| f.kind() == "import") =>
+        {
+            let arg = node
+                .child_by_field_name("arguments")
+                .and_then(|a| a.named_child(0))
+                .filter(|a| a.kind() == "string");
+            if let Some(arg) = arg {
+                out.push(Import {
+                    specifier: string_value(arg, src),
+                    bindings: Vec::new(),
+                });
+            }
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_imports(child, src, out);
+            }
+        }
+    }
+}
+
+fn import_clause_bindings(clause: Node<'_>, src: &[u8], out: &mut Vec<String>) {
+    let mut cursor = clause.walk();
+    for child in clause.named_children(&mut cursor) {
+        match child.kind() {
+            "identifier" => out.push(text(child, src).to_string()),
+            "namespace_import" => {
+                if let Some(ident) = child.named_child(0) {
+                    out.push(text(ident, src).to_string());
+                }
+            }
+            "named_imports" => {
+                let mut c = child.walk();
+                for spec in child.named_children(&mut c) {
+                    let local = spec
+                        .child_by_field_name("alias")
+                        .or_else(|| spec.child_by_field_name("name"));
+                    if let Some(local) = local {
+                        out.push(text(local, src).to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn string_value(node: Node<'_>, src: &[u8]) -> String {
+    text(node, src).trim_matches(['"', '\'', '`']).to_string()
+}
+
+/// The `/** ... */` block directly above `node`.
+fn jsdoc(node: Node<'_>, src: &[u8]) -> Option<String> {
+    let comment = node.prev_sibling().filter(|c| c.kind() == "comment")?;
+    let t = text(comment, src);
+    // Only attached comments count: no blank line in between.
+    if !t.starts_with("/**") || comment.end_position().row + 1 < node.start_position().row {
+        return None;
+    }
+    clean_block_comment(t)
+}
+
+/// A leading `/** ... */` block that is separated from the first statement
+/// by a blank line describes the file rather than a declaration.
+fn file_doc(root: Node<'_>, src: &[u8]) -> Option<String> {
+    let first = root.child(0).filter(|c| c.kind() == "comment")?;
+    let t = text(first, src);
+    let detached = first
+        .next_sibling()
+        .is_none_or(|n| n.start_position().row > first.end_position().row + 1);
+    if !t.starts_with("/**") || !detached {
+        return None;
+    }
+    clean_block_comment(t)
+}
+
+/// Resolve an import specifier to a module id in `by_path`.
+fn resolve_specifier<'a>(
+    specifier: &str,
+    from_dir: &Path,
+    config: Option<&TsConfig>,
+    by_path: &HashMap<PathBuf, &'a str>,
+) -> Option<&'a str> {
+    let mut bases = Vec::new();
+    if specifier.starts_with("./") || specifier.starts_with("../") || specifier == "." {
+        bases.push(from_dir.join(specifier));
+    } else if let Some(config) = config {
+        for (pattern, targets) in &config.paths {
+            let Some(star) = match_alias(pattern, specifier) else {
+                continue;
+            };
+            bases.extend(
+                targets
+                    .iter()
+                    .map(|t| config.paths_base.join(t.replacen('*', star, 1))),
+            );
+        }
+        if let Some(base_url) = &config.base_url {
+            bases.push(base_url.join(specifier));
+        }
+    }
+
+    bases.iter().find_map(|base| {
+        let base = normalize_path(base);
+        probe(&base)
+            .into_iter()
+            .find_map(|p| by_path.get(&p).copied())
+    })
+}
+
+/// Candidate files for an extension-less (or `.js`-suffixed) import path.
+fn probe(base: &Path) -> Vec<PathBuf> {
+    let mut out = vec![base.to_path_buf()];
+    let as_string = base.to_string_lossy();
+    let stem = ["js", "jsx", "mjs", "cjs"]
+        .iter()
+        .find_map(|ext| as_string.strip_suffix(&format!(".{ext}")))
+        .unwrap_or(&as_string);
+    for ext in ["ts", "tsx", "mts", "cts"] {
+        out.push(PathBuf::from(format!("{stem}.{ext}")));
+    }
+    for index in ["index.ts", "index.tsx"] {
+        out.push(base.join(index));
+    }
+    out
+}
+
+/// Match `specifier` against a `paths` key (`@app/*`), returning what the
+/// `*` captured (empty for exact keys).
+fn match_alias<'s>(pattern: &str, specifier: &'s str) -> Option<&'s str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier
+            .strip_prefix(prefix)?
+            .strip_suffix(suffix)
+            .filter(|_| specifier.len() >= prefix.len() + suffix.len()),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+/// The slice of `tsconfig.json` module resolution cares about.
+#[derive(Default, Clone)]
+struct TsConfig {
+    base_url: Option<PathBuf>,
+    /// Directory `paths` targets are relative to (`baseUrl`, or the config
+    /// that declared `paths`).
+    paths_base: PathBuf,
+    paths: Vec<(String, Vec<String>)>,
+}
+
+/// Per-directory memo of the nearest `tsconfig.json`.
+#[derive(Default)]
+struct TsConfigCache {
+    by_dir: HashMap<PathBuf, Option<TsConfig>>,
+}
+
+impl TsConfigCache {
+    fn nearest(&mut self, dir: &Path) -> Option<&TsConfig> {
+        if !self.by_dir.contains_key(dir) {
+            let config = dir
+                .ancestors()
+                .map(|a| a.join("tsconfig.json"))
+                .find(|p| p.is_file())
+                .and_then(|p| load_tsconfig(&p, 0));
+            self.by_dir.insert(dir.to_path_buf(), config);
+        }
+        self.by_dir.get(dir).and_then(Option::as_ref)
+    }
+}
+
+/// Load `path`, layering it over any relative `extends` parent.
+fn load_tsconfig(path: &Path, depth: usize) -> Option<TsConfig> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let json: Value = serde_json::from_str(&strip_jsonc(&text)).ok()?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut config = json["extends"]
+        .as_str()
+        .filter(|e| e.starts_with('.') && depth < 8)
+        .and_then(|e| {
+            let parent = dir.join(e);
+            let parent = if parent.extension().is_some() {
+                parent
+            } else {
+                parent.with_extension("json")
+            };
+            load_tsconfig(&parent, depth + 1)
+        })
+        .unwrap_or_else(|| TsConfig {
+            paths_base: dir.to_path_buf(),
+            ..TsConfig::default()
+        });
+
+    let options = &json["compilerOptions"];
+    if let Some(base_url) = options["baseUrl"].as_str() {
+        let base_url = dir.join(base_url);
+        config.paths_base = base_url.clone();
+        config.base_url = Some(base_url);
+    }
+    if let Some(paths) = options["paths"].as_object() {
+        if config.base_url.is_none() {
+            config.paths_base = dir.to_path_buf();
+        }
+        config.paths = paths
+            .iter()
+            .map(|(k, v)| {
+                let targets = v
+                    .as_array()
+                    .map(|a| {
+                        a.iter()
+                            .filter_map(|t| t.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (k.clone(), targets)
+            })
+            .collect();
+    }
+    Some(config)
+}
+
+/// Strip `//` / `/* */` comments and trailing commas so `serde_json` can
+/// read a `tsconfig.json`.
+fn strip_jsonc(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
 }
@@ -0,0 +1,431 @@
+//! Zig driver (scanner-based).
+//!
+//! There is no Zig grammar for the `tree-sitter` 0.20 line the workspace is
+//! pinned to, so this driver runs the shared [`scanner`](super::scanner)
+//! tokenizer and tracks container scopes by brace depth.
+//!
+//! Every `.zig` file is a struct, and becomes one [`Module`] with id
+//! `relative/path/stem`; its `//!` comments are the module doc. `build.zig`
+//! describes the build rather than the code and is skipped.
+//!
+//! Functions, containers bound to a `const` (`struct`, `opaque`, `enum`,
+//! `union` and error sets) and other constants are extracted as symbols;
+//! declarations inside a container are named `Outer.name`. A constant with
+//! a capitalised name (`const Allocator = std.mem.Allocator;`) is a type by
+//! Zig convention and becomes a [`SymbolKind::TypeAlias`]. `@import` of a
+//! relative path becomes an [`EdgeKind::Import`] edge when the file is in
+//! the model, while named imports — `std`, `builtin` and the modules and
+//! dependencies wired up in `build.zig` — are [`ExternalImport`]s.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::common::{
+    EdgeSet, collapse_whitespace, common_root, has_extension, join_doc, normalize_path,
+    relative_module_id, scan_each,
+};
+use super::scanner::{Syntax, Token, TokenKind, matching, tokenize};
+use super::{Driver, FileClaims};
+use crate::ir::{
+    CodeModel, EdgeKind, ExternalImport, ExternalKind, Module, ModuleId, Symbol, SymbolKind,
+    Visibility,
+};
+
+/// Zig driver (detects `build.zig`).
+#[derive(Default)]
+pub struct ZigDriver;
+
+impl Driver for ZigDriver {
+    fn name(&self) -> &'static str {
+        "zig"
+    }
+
+    fn detect(&self, project_root: &Path) -> bool {
+        project_root.join("build.zig").is_file()
+    }
+
+    fn claims(&self) -> FileClaims {
+        FileClaims::extensions(&["zig"])
+    }
+
+    fn parse(&self, paths: &[PathBuf]) -> Result<CodeModel> {
+        let files = scan_each(
+            paths,
+            |p| has_extension(p, &["zig"]) && p.file_name().is_none_or(|n| n != "build.zig"),
+            |path, source| Ok(extract_file(path, source)),
+        )?;
+        let root = common_root(files.iter().map(|f| f.path.as_path()));
+        let ids: Vec<String> = files
+            .iter()
+            .map(|f| relative_module_id(&f.path, &root))
+            .collect();
+        let by_path: HashMap<PathBuf, &str> = files
+            .iter()
+            .zip(&ids)
+            .map(|(f, id)| (normalize_path(&f.path), id.as_str()))
+            .collect();
+
+        let mut model = CodeModel::new(self.name());
+        let mut edges = EdgeSet::default();
+        for (file, id) in files.iter().zip(&ids) {
+            let mut external_imports: Vec<ExternalImport> = Vec::new();
+            for import in &file.imports {
+                if is_path(import) {
+                    let dir = file.path.parent().unwrap_or(Path::new(""));
+                    if let Some(to) = by_path.get(&normalize_path(&dir.join(import))) {
+                        edges.push(id, to, EdgeKind::Import);
+                    }
+                } else if !external_imports.iter().any(|e| &e.path == import) {
+                    external_imports.push(ExternalImport {
+                        path: import.clone(),
+                        kind: classify(import),
+                    });
+                }
+            }
+
+            model.modules.push(Module {
+                id: ModuleId(id.clone()),
+                name: file
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                path: file.path.clone(),
+                symbols: file.symbols.clone(),
+                doc: file.doc.clone(),
+                external_imports,
+                group: None,
+                container: None,
+            });
+        }
+        model.edges = edges.into_edges();
+
+        Ok(model)
+    }
+}
+
+const SYNTAX: Syntax = Syntax {
+    doc_line_comments: &["///", "//!"],
+    line_comments: &["//"],
+    block_comment: None,
+    doc_block_comment: None,
+    nested_block_comments: false,
+    quotes: &['"', '\''],
+    triple_quoted_strings: false,
+    verbatim_strings: false,
+    ident_chars: &[],
+};
+
+/// Keywords that may precede `fn`.
+const FN_MODIFIERS: &[&str] = &["pub", "export", "extern", "inline", "noinline"];
+
+/// Everything extracted from a single `.zig` file.
+struct ParsedFile {
+    path: PathBuf,
+    doc: Option<String>,
+    symbols: Vec<Symbol>,
+    /// `@import` arguments, in source order.
+    imports: Vec<String>,
+}
+
+/// Scopes the scanner tracks.
+enum Block {
+    /// The body of a `struct` / `enum` / `union` / `opaque` bound to a
+    /// `const`, whose declarations are extracted as `Name.member`.
+    Container(String),
+    /// Function bodies, error sets, literals — skipped wholesale.
+    Other,
+}
+
+fn extract_file(path: &Path, src: &str) -> ParsedFile {
+    let masked = mask_multiline_strings(src);
+    let tokens = tokenize(&masked, &SYNTAX);
+    let mut file = ParsedFile {
+        path: path.to_path_buf(),
+        doc: join_doc(
+            tokens
+                .iter()
+                .filter_map(|t| t.text.strip_prefix("//!"))
+                .map(|l| l.trim().to_string())
+                .collect(),
+        ),
+        symbols: Vec::new(),
+        imports: imports(&tokens),
+    };
+    let mut stack: Vec<Block> = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(stack.last(), Some(Block::Other)) {
+            if token.is("{") {
+                stack.push(Block::Other);
+            } else if token.is("}") {
+                stack.pop();
+            }
+            i += 1;
+            continue;
+        }
+        let outer = match stack.last() {
+            Some(Block::Container(name)) => Some(name.as_str()),
+            _ => None,
+        };
+        let next_is_ident = tokens.get(i + 1).is_some_and(|t| t.is_ident());
+
+        match token.text {
+            "{" => stack.push(Block::Other),
+            "}" => {
+                stack.pop();
+            }
+            "fn" if next_is_ident => {
+                let start = modifiers_start(&tokens, i);
+                let end = function_end(&tokens, i + 2);
+                file.symbols.push(Symbol {
+                    name: qualify(outer, tokens[i + 1].text),
+                    kind: SymbolKind::Function,
+                    visibility: visibility(&tokens[start..i]),
+                    line: tokens[start].line,
+                    cell: None,
+                    doc: zig_doc(&tokens, start),
+                    signature: Some(signature(&tokens, start, end, &masked)),
+                    members: Vec::new(),
+                });
+                // The body (or `;` of an extern prototype) is handled by
+                // the next iteration.
+                i = end;
+                continue;
+            }
+            "const" if next_is_ident => {
+                let start = modifiers_start(&tokens, i);
+                let name = qualify(outer, tokens[i + 1].text);
+                let Some(value) = initializer(&tokens, i + 2) else {
+                    i += 2;
+                    continue;
+                };
+                let symbol = |kind: SymbolKind, end: usize| Symbol {
+                    name: name.clone(),
+                    kind,
+                    visibility: visibility(&tokens[start..i]),
+                    line: tokens[start].line,
+                    cell: None,
+                    doc: zig_doc(&tokens, start),
+                    signature: Some(signature(&tokens, start, end, &masked)),
+                    members: Vec::new(),
+                };
+
+                let mut keyword = value;
+                while tokens
+                    .get(keyword)
+                    .is_some_and(|t| t.is("extern") || t.is("packed"))
+                {
+                    keyword += 1;
+                }
+                let kind = match tokens.get(keyword).map(|t| t.text) {
+                    Some("struct" | "opaque") => Some(SymbolKind::Struct),
+                    Some("enum" | "union") => Some(SymbolKind::Enum),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    // `enum(u8)`, `union(enum)`.
+                    let mut open = keyword + 1;
+                    if tokens.get(open).is_some_and(|t| t.is("(")) {
+                        open = matching(&tokens, open) + 1;
+                    }
+                    file.symbols.push(symbol(kind, open));
+                    if tokens.get(open).is_some_and(|t| t.is("{")) {
+                        stack.push(Block::Container(name));
+                        i = open + 1;
+                    } else {
+                        i = open;
+                    }
+                    continue;
+                }
+
+                let end = statement_end(&tokens, value);
+                match (tokens[value].text, tokens.get(value + 1).map(|t| t.text)) {
+                    // Error sets: `error{ OutOfMemory, Overflow }`.
+                    ("error", Some("{")) => file.symbols.push(symbol(SymbolKind::Enum, value + 1)),
+                    // Namespaces and `@This()` are not declarations of
+                    // their own.
+                    ("@", Some("import" | "This")) => {}
+                    _ if tokens[i + 1].text.starts_with(char::is_uppercase) => {
+                        file.symbols.push(symbol(SymbolKind::TypeAlias, end));
+                    }
+                    _ => file.symbols.push(symbol(SymbolKind::Constant, end)),
+                }
+                // Literals in the initializer are skipped as blocks.
+                i = value;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    file
+}
+
+/// Every `@import("...")` argument in the file.
+fn imports(tokens: &[Token<'_>]) -> Vec<String> {
+    tokens
+        .windows(5)
+        .filter(|w| w[0].is("@") && w[1].is("import") && w[2].is("(") && w[4].is(")"))
+        .filter(|w| w[3].kind == TokenKind::Str)
+        .map(|w| w[3].text.trim_matches('"').to_string())
+        .collect()
+}
+
+/// `@import("util/fmt.zig")` names a file; `@import("std")` a module.
+fn is_path(import: &str) -> bool {
+    import.ends_with(".zig")
+}
+
+/// Index of the first token of the initializer of the `const` whose name
+/// ends just before `after_name`, skipping a type annotation.
+fn initializer(tokens: &[Token<'_>], after_name: usize) -> Option<usize> {
+    let mut i = after_name;
+    while let Some(t) = tokens.get(i) {
+        if t.is("=") {
+            return (i + 1 < tokens.len()).then_some(i + 1);
+        }
+        if t.is(";") {
+            return None;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            i = matching(tokens, i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the `{` opening the body of the function whose parameter list
+/// starts at `params`, or of the `;` ending a prototype.
+fn function_end(tokens: &[Token<'_>], params: usize) -> usize {
+    let mut i = params;
+    while let Some(t) = tokens.get(i) {
+        if t.is("{") || t.is(";") {
+            break;
+        }
+        if t.is("(") || t.is("[") {
+            i = matching(tokens, i);
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Index of the `;` ending the statement that starts at `start`.
+fn statement_end(tokens: &[Token<'_>], start: usize) -> usize {
+    let mut i = start;
+    while let Some(t) = tokens.get(i) {
+        if t.is(";") {
+            break;
+        }
+        if t.is("(") || t.is("[") || t.is("{") {
+            i = matching(tokens, i);
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Index of the first modifier in front of the keyword at `keyword`
+/// (`pub`, `export`, `extern "c"`, `inline`).
+fn modifiers_start(tokens: &[Token<'_>], keyword: usize) -> usize {
+    let mut start = keyword;
+    while start > 0 {
+        let prev = tokens[start - 1];
+        let library = prev.kind == TokenKind::Str && start > 1 && tokens[start - 2].is("extern");
+        if library || (prev.is_ident() && FN_MODIFIERS.contains(&prev.text)) {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// Declarations are private to their file unless marked `pub`.
+fn visibility(modifiers: &[Token<'_>]) -> Visibility {
+    if modifiers.iter().any(|t| t.is("pub")) {
+        Visibility::Public
+    } else {
+        Visibility::Private
+    }
+}
+
+fn qualify(outer: Option<&str>, name: &str) -> String {
+    match outer {
+        Some(outer) => format!("{outer}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// The `///` lines directly in front of the declaration at `start`.
+fn zig_doc(tokens: &[Token<'_>], start: usize) -> Option<String> {
+    let first = tokens[..start]
+        .iter()
+        .rposition(|t| t.kind != TokenKind::Doc || !t.text.starts_with("///"))
+        .map_or(0, |p| p + 1);
+    let lines = tokens[first..start]
+        .iter()
+        .map(|t| t.text.trim_start_matches('/').trim().to_string())
+        .collect();
+    join_doc(lines)
+}
+
+/// Source text of `tokens[start..end]`, whitespace collapsed.
+fn signature(tokens: &[Token<'_>], start: usize, end: usize, src: &str) -> String {
+    let last = tokens[end.clamp(start + 1, tokens.len()) - 1];
+    collapse_whitespace(&src[tokens[start].offset..last.offset + last.text.len()])
+}
+
+/// Blank out (keeping newlines and byte offsets) the lines of `\\`
+/// multiline string literals, which the tokenizer would otherwise read as
+/// code.
+fn mask_multiline_strings(src: &str) -> String {
+    let b = src.as_bytes();
+    let mut out = b.to_vec();
+    let line_end = |from: usize| {
+        b[from..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(b.len(), |p| from + p)
+    };
+
+    let mut i = 0;
+    while i < b.len() {
+        match b[i] {
+            b'/' if b.get(i + 1) == Some(&b'/') => i = line_end(i),
+            b'\\' if b.get(i + 1) == Some(&b'\\') => {
+                let end = line_end(i);
+                out[i..end].fill(b' ');
+                i = end;
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < b.len() && b[i] != quote && b[i] != b'\n' {
+                    i += if b[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    // Only ASCII bytes were replaced, so the result is still UTF-8.
+    String::from_utf8(out).unwrap_or_else(|_| src.to_string())
+}
+
+/// `std`, `builtin` and `root` come with the compiler; any other named
+/// module is declared in `build.zig` (a dependency or one of the build's own
+/// modules).
+fn classify(import: &str) -> ExternalKind {
+    if matches!(import, "std" | "builtin" | "root") {
+        ExternalKind::Std
+    } else {
+        ExternalKind::ThirdParty
+    }
+}
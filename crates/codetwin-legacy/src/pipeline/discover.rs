@@ -1,8 +1,9 @@
 //! File discovery (NEW_ROADMAP Phase 1.e).
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use glob::Pattern;
 use ignore::WalkBuilder;
 
 use crate::config::Config;
@@ -12,9 +13,9 @@ use crate::config::Config;
 /// Respects `.gitignore` + nested ignore files via the `ignore` crate, and
 /// applies the `config.exclude_patterns` glob list on top.
 pub fn discover(config: &Config) -> Result<Vec<PathBuf>> {
-    // TODO(Phase 1.e): apply `config.exclude_patterns` as glob filters,
-    //                  handle `source_dirs` that don't exist with a
+    // TODO(Phase 1.e): handle `source_dirs` that don't exist with a
     //                  friendly error (NEW_ROADMAP "zero-config" goal).
+    let excludes = Excludes::new(&config.exclude_patterns)?;
     let mut results = Vec::new();
 
     for dir in &config.source_dirs {
@@ -22,10 +23,12 @@ pub fn discover(config: &Config) -> Result<Vec<PathBuf>> {
             tracing::warn!(path = %dir.display(), "source directory does not exist");
             continue;
         }
+        let excludes = excludes.clone();
         let walker = WalkBuilder::new(dir)
             .hidden(false)
             .git_ignore(true)
             .git_exclude(true)
+            .filter_entry(move |e| !excludes.is_excluded(e.path()))
             .build();
         for entry in walker {
             let entry = entry?;
@@ -35,6 +38,30 @@ pub fn discover(config: &Config) -> Result<Vec<PathBuf>> {
         }
     }
 
+    // Source dirs may nest (`crates` and `crates/core/src`).
     results.sort();
+    results.dedup();
     Ok(results)
 }
+
+/// Compiled `exclude_patterns`.
+#[derive(Clone)]
+pub(super) struct Excludes(Vec<Pattern>);
+
+impl Excludes {
+    pub(super) fn new(patterns: &[String]) -> Result<Self> {
+        patterns
+            .iter()
+            .map(|p| Pattern::new(p).with_context(|| format!("invalid exclude pattern `{p}`")))
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Whether `path` matches a pattern, or is a directory whose contents
+    /// all do (`**/target/**` excludes `target` itself).
+    pub(super) fn is_excluded(&self, path: &Path) -> bool {
+        self.0
+            .iter()
+            .any(|p| p.matches_path(path) || p.matches_path(&path.join("_")))
+    }
+}
@@ -3,16 +3,20 @@
 //! Each stage is a standalone function so it can be unit-tested in isolation:
 //!
 //! ```text
-//! discover → drivers.detect → parse (rayon) → merge → layout.render → write
+//! projects → drivers.detect → discover → parse (rayon) → merge → layout.render → write
 //! ```
 
 mod discover;
 pub mod merge;
+mod projects;
 mod render;
+mod route;
 mod write;
 
 pub use discover::discover;
+pub use projects::{Project, detect_projects, label_model, select_projects};
 pub use render::render;
+pub use route::{route, unclaimed};
 pub use write::write_outputs;
 
 use anyhow::{Context, Result};
@@ -34,31 +38,68 @@ pub struct GenOptions {
 
 /// Run the full pipeline once.
 ///
-/// This is the function invoked by `codetwin gen` and (via the watch loop)
-/// on every filesystem change.
+/// This is the function invoked by `codetwin gen`; the watch loop calls
+/// [`run_with`] on every filesystem change.
 pub fn run(config: &Config, opts: &GenOptions, json: bool) -> Result<()> {
     let project_root = std::env::current_dir()?;
-    let files = discover(config).context("file discovery failed")?;
-    tracing::info!(count = files.len(), "discovered source files");
+    let registry = DriverRegistry::with_plugins(&config.plugins.drivers, &project_root);
+    run_with(&registry, config, opts, json)
+}
 
-    let registry = DriverRegistry::default();
-    let active: Vec<_> = match &config.drivers {
-        Some(names) => names.iter().filter_map(|n| registry.get(n)).collect(),
-        None => registry.detect_all(&project_root),
-    };
+/// [`run`] with a registry built by the caller, so the watch loop builds
+/// its plugin drivers once instead of on every change.
+pub fn run_with(
+    registry: &DriverRegistry,
+    config: &Config,
+    opts: &GenOptions,
+    json: bool,
+) -> Result<()> {
+    let project_root = std::env::current_dir()?;
+    let projects =
+        select_projects(registry, &project_root, config).context("project detection failed")?;
 
-    if active.is_empty() {
+    if projects.iter().all(|p| p.drivers.is_empty()) {
         tracing::warn!("no drivers matched the project; output will be empty");
-    } else {
+    }
+
+    let mut jobs = Vec::new();
+    for project in projects.iter().filter(|p| !p.drivers.is_empty()) {
+        let files =
+            discover(&project.config(config, &project_root)).context("file discovery failed")?;
         tracing::info!(
-            drivers = ?active.iter().map(|d| d.name()).collect::<Vec<_>>(),
-            "active drivers"
+            project = %project.root.display(),
+            count = files.len(),
+            drivers = ?project.drivers.iter().map(|d| d.name()).collect::<Vec<_>>(),
+            "discovered source files"
+        );
+        let stray = unclaimed(&files, registry, &project.drivers);
+        if !stray.is_empty() {
+            tracing::warn!(
+                project = %project.root.display(),
+                count = stray.len(),
+                examples = ?stray.iter().take(3).collect::<Vec<_>>(),
+                "source files no active driver claims"
+            );
+        }
+        let routed = route(&files, &project.drivers);
+        jobs.extend(
+            project
+                .drivers
+                .iter()
+                .zip(routed)
+                .map(|(driver, files)| (project, *driver, files)),
         );
     }
 
-    let models: Vec<CodeModel> = active
+    let models: Vec<CodeModel> = jobs
         .par_iter()
-        .map(|driver| driver.parse(&files))
+        .map(|(project, driver, files)| {
+            let mut model = driver.parse(files)?;
+            if let Some(label) = project.label() {
+                label_model(&mut model, &label);
+            }
+            Ok(model)
+        })
         .collect::<Result<Vec<_>>>()?;
 
     let merged = merge::merge_all(models);
@@ -0,0 +1,281 @@
+//! Sub-project detection for monorepos.
+//!
+//! [`DriverRegistry::detect_all`] only sniffs the manifests of one
+//! directory. A monorepo keeps its projects further down
+//! (`services/api/go.mod`, `web/package.json`), so [`detect_projects`]
+//! walks the tree and runs detection in every directory. Each project is
+//! then discovered and parsed from its own root, and its modules are
+//! labelled with that root (see [`Project::label`]).
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+use ignore::WalkBuilder;
+
+use super::discover::Excludes;
+use crate::config::Config;
+use crate::drivers::{Driver, DriverRegistry};
+use crate::ir::{CodeModel, ModuleId};
+
+/// A directory some drivers detect a project at.
+pub struct Project<'a> {
+    /// Root of the project relative to the top-level project root (empty
+    /// for the top-level project itself).
+    pub root: PathBuf,
+    /// Drivers detecting the project.
+    pub drivers: Vec<&'a dyn Driver>,
+}
+
+impl Project<'_> {
+    /// Group label of a nested project: its `/`-separated root
+    /// (`"services/api"`). `None` for the top-level project.
+    pub fn label(&self) -> Option<String> {
+        let segments: Vec<String> = self
+            .root
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        (!segments.is_empty()).then(|| segments.join("/"))
+    }
+
+    /// `config` with the source dirs of this project.
+    ///
+    /// The top-level project scans `config.source_dirs`; a nested one scans
+    /// those of them that exist below its root, or else the whole root.
+    /// Either way, the drivers' own [`Driver::source_dirs`] are added.
+    pub fn config(&self, config: &Config, project_root: &Path) -> Config {
+        let mut config = config.clone();
+        if self.label().is_some() {
+            let nested: Vec<PathBuf> = config
+                .source_dirs
+                .iter()
+                .filter(|dir| dir.is_relative())
+                .map(|dir| self.root.join(dir))
+                .filter(|dir| project_root.join(dir).is_dir())
+                .collect();
+            config.source_dirs = if nested.is_empty() {
+                vec![self.root.clone()]
+            } else {
+                nested
+            };
+        }
+
+        let root = project_root.join(&self.root);
+        for dir in self.drivers.iter().flat_map(|d| d.source_dirs(&root)) {
+            let dir = self.root.join(dir);
+            if !config.source_dirs.contains(&dir) {
+                config.source_dirs.push(dir);
+            }
+        }
+        config
+    }
+}
+
+/// The projects a run parses: with a `config.drivers` override, one project
+/// at the root using exactly those drivers (unknown names are ignored);
+/// otherwise whatever [`detect_projects`] finds.
+pub fn select_projects<'a>(
+    registry: &'a DriverRegistry,
+    project_root: &Path,
+    config: &Config,
+) -> Result<Vec<Project<'a>>> {
+    match &config.drivers {
+        Some(names) => Ok(vec![Project {
+            root: PathBuf::new(),
+            drivers: names.iter().filter_map(|n| registry.get(n)).collect(),
+        }]),
+        None => detect_projects(registry, project_root, config),
+    }
+}
+
+/// Every project below `project_root`: the top-level one first (even when
+/// no driver detects it), then the nested ones in path order.
+///
+/// The walk respects ignore files and `config.exclude_patterns`. A driver
+/// detecting a directory an enclosing project of the same driver already
+/// scans is not reported again: the directory lies inside that project's
+/// source dirs, or one of the driver's own [`Driver::source_dirs`] lies
+/// inside it (a Cargo workspace member, a Terraform child module). Any
+/// other nested manifest — an npm workspace package below a root
+/// `package.json`, say — is a project of its own. Directories a driver
+/// only detects through loose source files are merged instead (see
+/// [`merge_loose`]).
+pub fn detect_projects<'a>(
+    registry: &'a DriverRegistry,
+    project_root: &Path,
+    config: &Config,
+) -> Result<Vec<Project<'a>>> {
+    let excludes = Excludes::new(&config.exclude_patterns)?;
+    let root = project_root.to_path_buf();
+    let walker = WalkBuilder::new(project_root)
+        .git_ignore(true)
+        .git_exclude(true)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .filter_entry(move |e| {
+            let rel = e.path().strip_prefix(&root).unwrap_or(e.path());
+            rel.as_os_str().is_empty() || !excludes.is_excluded(rel)
+        })
+        .build();
+
+    let mut projects = vec![Project {
+        root: PathBuf::new(),
+        drivers: registry.detect_all(project_root),
+    }];
+    let mut scopes = vec![Scope::of(&projects[0], config, project_root)];
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_some_and(|ft| ft.is_dir()) || entry.depth() == 0 {
+            continue;
+        }
+        let rel = relative(entry.path(), project_root);
+        let drivers: Vec<_> = registry
+            .detect_all(entry.path())
+            .into_iter()
+            .filter(|d| !scopes.iter().any(|s| s.claims(d.name(), &rel)))
+            .collect();
+        if !drivers.is_empty() {
+            let project = Project { root: rel, drivers };
+            scopes.push(Scope::of(&project, config, project_root));
+            projects.push(project);
+        }
+    }
+    merge_loose(&mut projects, project_root);
+    Ok(projects)
+}
+
+/// Merge the nested projects a driver detects through loose source files
+/// alone (see [`Driver::detects_loose_sources`]) into one project at their
+/// common ancestor: `.proto` packages in `proto/acme/orders/v1` and
+/// `proto/acme/shipping/v1` import each other, so they are parsed
+/// together at `proto/acme`. Two directories stay apart when their common
+/// ancestor is the top-level root, or when a project detected by a
+/// manifest lies below it.
+fn merge_loose(projects: &mut Vec<Project<'_>>, project_root: &Path) {
+    let loose = |project: &Project<'_>, driver: &dyn Driver| {
+        driver.detects_loose_sources(&project_root.join(&project.root))
+    };
+    loop {
+        let mut merge = None;
+        'search: for (i, a) in projects.iter().enumerate().skip(1) {
+            for b in &projects[i + 1..] {
+                for &driver in &a.drivers {
+                    if !b.drivers.iter().any(|d| d.name() == driver.name())
+                        || !loose(a, driver)
+                        || !loose(b, driver)
+                    {
+                        continue;
+                    }
+                    let ancestor: PathBuf = a
+                        .root
+                        .components()
+                        .zip(b.root.components())
+                        .take_while(|(x, y)| x == y)
+                        .map(|(x, _)| x)
+                        .collect();
+                    let walled = projects.iter().any(|p| {
+                        p.root.starts_with(&ancestor)
+                            && p.root != ancestor
+                            && p.drivers.iter().any(|&d| !loose(p, d))
+                    });
+                    if !ancestor.as_os_str().is_empty() && !walled {
+                        merge = Some((driver, ancestor));
+                        break 'search;
+                    }
+                }
+            }
+        }
+        let Some((driver, ancestor)) = merge else {
+            return;
+        };
+
+        for project in projects.iter_mut().skip(1) {
+            if project.root.starts_with(&ancestor) && project.root != ancestor {
+                project.drivers.retain(|d| d.name() != driver.name());
+            }
+        }
+        match projects.iter_mut().find(|p| p.root == ancestor) {
+            Some(project) => {
+                if !project.drivers.iter().any(|d| d.name() == driver.name()) {
+                    project.drivers.push(driver);
+                }
+            }
+            None => projects.push(Project {
+                root: ancestor,
+                drivers: vec![driver],
+            }),
+        }
+        projects.retain(|p| p.label().is_none() || !p.drivers.is_empty());
+        projects[1..].sort_by(|a, b| a.root.cmp(&b.root));
+    }
+}
+
+/// The directories a detected project scans, to tell whether a nested
+/// directory belongs to it.
+struct Scope {
+    /// Source dirs of the project, relative to the top-level root.
+    source_dirs: Vec<PathBuf>,
+    /// Each driver's own [`Driver::source_dirs`], likewise relative.
+    drivers: Vec<(&'static str, Vec<PathBuf>)>,
+}
+
+impl Scope {
+    fn of(project: &Project<'_>, config: &Config, project_root: &Path) -> Self {
+        let root = project_root.join(&project.root);
+        Self {
+            source_dirs: project
+                .config(config, project_root)
+                .source_dirs
+                .iter()
+                .map(|dir| relative(dir, project_root))
+                .collect(),
+            drivers: project
+                .drivers
+                .iter()
+                .map(|d| {
+                    let dirs = d.source_dirs(&root);
+                    (
+                        d.name(),
+                        dirs.iter().map(|dir| project.root.join(dir)).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the project's `driver` already covers directory `rel`.
+    fn claims(&self, driver: &str, rel: &Path) -> bool {
+        self.drivers.iter().any(|(name, dirs)| {
+            *name == driver
+                && (dirs.iter().any(|dir| dir.starts_with(rel))
+                    || self.source_dirs.iter().any(|dir| rel.starts_with(dir)))
+        })
+    }
+}
+
+/// `path` relative to `project_root`, without `.` components.
+fn relative(path: &Path, project_root: &Path) -> PathBuf {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+/// Prefix every module id of `model` with `label/` and tag its modules
+/// with the group, so equally named modules of two projects stay apart. A
+/// group the driver set itself (an umbrella app, a SwiftPM target) is kept
+/// below the label: `label/group`.
+pub fn label_model(model: &mut CodeModel, label: &str) {
+    let prefixed = |id: &ModuleId| ModuleId(format!("{label}/{}", id.0));
+    for module in &mut model.modules {
+        module.id = prefixed(&module.id);
+        module.group = Some(match module.group.take() {
+            Some(group) => format!("{label}/{group}"),
+            None => label.to_string(),
+        });
+    }
+    for edge in &mut model.edges {
+        edge.from = prefixed(&edge.from);
+        edge.to = prefixed(&edge.to);
+    }
+}
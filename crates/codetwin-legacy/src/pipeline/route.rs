@@ -0,0 +1,35 @@
+//! Per-file routing of discovered files to drivers.
+
+use std::path::PathBuf;
+
+use crate::drivers::{Driver, DriverRegistry, script_interpreter};
+
+/// The files each of `drivers` gets, in order: those its
+/// [`Driver::claims`] owns or reads.
+pub fn route(files: &[PathBuf], drivers: &[&dyn Driver]) -> Vec<Vec<PathBuf>> {
+    drivers
+        .iter()
+        .map(|driver| {
+            let claims = driver.claims();
+            files.iter().filter(|f| claims.wants(f)).cloned().collect()
+        })
+        .collect()
+}
+
+/// Files that look like source — some driver in `registry` owns them, or
+/// they are `#!` scripts — yet none of `active` owns.
+pub fn unclaimed(
+    files: &[PathBuf],
+    registry: &DriverRegistry,
+    active: &[&dyn Driver],
+) -> Vec<PathBuf> {
+    files
+        .iter()
+        .filter(|f| !active.iter().any(|d| d.claims().owns(f)))
+        .filter(|f| {
+            registry.iter().any(|d| d.claims().owns(f))
+                || (f.extension().is_none() && script_interpreter(f).is_some())
+        })
+        .cloned()
+        .collect()
+}
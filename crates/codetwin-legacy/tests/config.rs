@@ -65,4 +65,39 @@ fn roundtrip_through_toml() {
     assert_eq!(parsed.source_dirs, cfg.source_dirs);
 }
 
+#[test]
+fn plugin_drivers_parse() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("codetwin.toml");
+    std::fs::write(
+        &path,
+        r#"
+[[plugins.drivers]]
+name = "dsl"
+command = "tools/dsl-codetwin"
+args = ["--codetwin"]
+extensions = ["dsl"]
+"#,
+    )
+    .unwrap();
+
+    let cfg = Config::load_or_default_from(&path).unwrap();
+    let [plugin] = cfg.plugins.drivers.as_slice() else {
+        panic!("expected one plugin, got {:?}", cfg.plugins.drivers);
+    };
+    assert_eq!(plugin.name, "dsl");
+    assert_eq!(
+        plugin.command,
+        std::path::PathBuf::from("tools/dsl-codetwin")
+    );
+    assert_eq!(plugin.args, vec!["--codetwin"]);
+    assert_eq!(plugin.extensions, vec!["dsl"]);
+    assert!(plugin.detect.is_empty());
+
+    // An empty `[plugins]` table is not written back.
+    let saved = dir.path().join("saved.toml");
+    Config::default().save_to(&saved).unwrap();
+    assert!(!std::fs::read_to_string(&saved).unwrap().contains("plugins"));
+}
+
 // TODO(Phase 1.e): test that `exclude_patterns` are applied during discovery.
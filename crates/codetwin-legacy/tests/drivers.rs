@@ -1,13 +1,84 @@
-//! Driver registry detection + lookup (NEW_ROADMAP Phase 1.b).
+//! Driver registry detection + lookup, what each built-in driver extracts
+//! from a `TempDir` tree, and the external plugin protocol (NEW_ROADMAP
+//! Phase 1.b).
 
-use codetwin_legacy::drivers::DriverRegistry;
+use std::path::{Path, PathBuf};
+
+use codetwin_legacy::drivers::{
+    CSharpDriver, CppDriver, DockerDriver, Driver, DriverRegistry, ElixirDriver, ExternalDriver,
+    GoDriver, GraphqlDriver, JavaDriver, KotlinDriver, OpenApiDriver, PhpDriver, ProtobufDriver,
+    PythonDriver, RubyDriver, RustDriver, ScalaDriver, SqlDriver, SwiftDriver, TerraformDriver,
+    TypeScriptDriver, ZigDriver,
+};
+use codetwin_legacy::ir::{
+    CodeModel, Container, EdgeKind, ExternalImport, ExternalKind, Member, Module, SymbolKind,
+    Visibility,
+};
+use codetwin_legacy::pipeline::merge::merge_all;
 use pretty_assertions::assert_eq;
 use tempfile::TempDir;
 
+/// Write `files` (relative path → contents) under `root` and return the
+/// sorted list of written paths, mimicking `pipeline::discover`.
+fn write_tree(root: &Path, files: &[(&str, &str)]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for (rel, contents) in files {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        paths.push(path);
+    }
+    paths.sort();
+    paths
+}
+
+fn module<'a>(model: &'a CodeModel, id: &str) -> &'a Module {
+    model
+        .modules
+        .iter()
+        .find(|m| m.id.0 == id)
+        .unwrap_or_else(|| panic!("module {id} missing"))
+}
+
+fn edges(model: &CodeModel, kind: EdgeKind) -> Vec<(String, String)> {
+    let mut out: Vec<_> = model
+        .edges
+        .iter()
+        .filter(|e| e.kind == kind)
+        .map(|e| (e.from.0.clone(), e.to.0.clone()))
+        .collect();
+    out.sort();
+    out
+}
+
 #[test]
 fn default_registry_lists_all_builtins() {
     let names = DriverRegistry::default().names();
-    assert_eq!(names, vec!["rust", "python", "typescript", "go"]);
+    assert_eq!(
+        names,
+        vec![
+            "rust",
+            "python",
+            "typescript",
+            "go",
+            "java",
+            "kotlin",
+            "csharp",
+            "cpp",
+            "ruby",
+            "php",
+            "swift",
+            "elixir",
+            "scala",
+            "zig",
+            "protobuf",
+            "openapi",
+            "sql",
+            "terraform",
+            "docker",
+            "graphql"
+        ]
+    );
 }
 
 #[test]
@@ -60,6 +131,4890 @@ fn get_returns_none_for_unknown_driver() {
     assert!(DriverRegistry::default().get("pascal").is_none());
 }
 
-// TODO(Phase 1.b): integration test parsing a real Rust fixture end-to-end.
-// TODO(Phase 5.a): add a `tsconfig.json` detection test for TypeScript.
-// TODO(Phase 5.b): add a `go.mod` detection test for Go.
+#[test]
+fn rust_driver_follows_the_mod_tree() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("Cargo.toml", "[package]\nname = \"demo-app\"\n"),
+            ("src/lib.rs", "//! Crate docs.\npub mod cli;\nmod util;\n"),
+            ("src/cli/mod.rs", "pub mod gen;\n"),
+            ("src/cli/gen.rs", ""),
+            ("src/util.rs", "mod fs;\n"),
+            ("src/util/fs.rs", ""),
+            ("README.md", "# not rust\n"),
+        ],
+    );
+
+    let model = RustDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "demo_app",
+            "demo_app::cli",
+            "demo_app::cli::gen",
+            "demo_app::util",
+            "demo_app::util::fs"
+        ]
+    );
+    assert_eq!(
+        module(&model, "demo_app").doc.as_deref(),
+        Some("Crate docs.")
+    );
+    assert_eq!(module(&model, "demo_app::util::fs").name, "fs");
+    // A lone package is grouped by its crate just like a workspace member.
+    assert!(
+        model
+            .modules
+            .iter()
+            .all(|m| m.group.as_deref() == Some("demo_app"))
+    );
+}
+
+#[test]
+fn rust_driver_extracts_symbols_with_visibility_docs_and_signatures() {
+    let dir = TempDir::new().unwrap();
+    let source = r#"
+/// Adds one.
+#[inline]
+pub fn add_one(x: u32) -> u32 {
+    x + 1
+}
+
+pub(crate) struct Config {
+    field: u8,
+}
+
+enum Mode { A, B }
+
+pub trait Render {}
+
+pub const LIMIT: usize = 10;
+
+type Alias = Vec<u8>;
+
+pub mod inline {}
+"#;
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("Cargo.toml", "[package]\nname = \"demo\"\n"),
+            ("src/lib.rs", source),
+        ],
+    );
+
+    let model = RustDriver.parse(&paths).unwrap();
+    let symbols = &module(&model, "demo").symbols;
+    let summary: Vec<_> = symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("add_one", SymbolKind::Function, Visibility::Public),
+            ("Config", SymbolKind::Struct, Visibility::Internal),
+            ("Mode", SymbolKind::Enum, Visibility::Private),
+            ("Render", SymbolKind::Trait, Visibility::Public),
+            ("LIMIT", SymbolKind::Constant, Visibility::Public),
+            ("Alias", SymbolKind::TypeAlias, Visibility::Private),
+            ("inline", SymbolKind::Module, Visibility::Public),
+        ]
+    );
+
+    let add_one = &symbols[0];
+    assert_eq!(add_one.line, 4);
+    assert_eq!(add_one.doc.as_deref(), Some("Adds one."));
+    assert_eq!(
+        add_one.signature.as_deref(),
+        Some("pub fn add_one(x: u32) -> u32")
+    );
+    assert_eq!(
+        symbols[4].signature.as_deref(),
+        Some("pub const LIMIT: usize")
+    );
+}
+
+#[test]
+fn rust_driver_resolves_use_declarations_into_import_edges() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("Cargo.toml", "[package]\nname = \"demo\"\n"),
+            (
+                "src/lib.rs",
+                "mod a;\nmod b;\nmod c;\nuse std::fmt;\nuse a::Thing;\n",
+            ),
+            ("src/a.rs", "use crate::{b::{self, Other}, c::*};\n"),
+            (
+                "src/b.rs",
+                "use super::a::Thing as T;\nuse self::inner::X;\n",
+            ),
+            ("src/c.rs", "use crate::c::Y;\n"),
+        ],
+    );
+
+    let model = RustDriver.parse(&paths).unwrap();
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("demo".to_string(), "demo::a".to_string()),
+            ("demo::a".to_string(), "demo::b".to_string()),
+            ("demo::a".to_string(), "demo::c".to_string()),
+            ("demo::b".to_string(), "demo::a".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn rust_driver_models_workspace_members_as_crates() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "Cargo.toml",
+                "[workspace]\n\
+                 members = [\"crates/*\"]\n\
+                 exclude = [\"crates/legacy\"]\n\
+                 \n\
+                 [workspace.dependencies]\n\
+                 acme-core = { path = \"crates/core\" }\n\
+                 serde = \"1\"\n",
+            ),
+            (
+                "crates/core/Cargo.toml",
+                "[package]\nname = \"acme-core\"\n\n[dependencies]\nserde = { workspace = true }\n",
+            ),
+            ("crates/core/src/lib.rs", "pub mod model;\n"),
+            ("crates/core/src/model.rs", "use serde::Serialize;\n"),
+            (
+                "crates/cli/Cargo.toml",
+                "[package]\nname = \"acme-cli\"\n\n[dependencies]\nacme-core = { workspace = true }\n",
+            ),
+            ("crates/cli/src/lib.rs", "pub mod args;\n"),
+            ("crates/cli/src/args.rs", "use acme_core::model::User;\n"),
+            ("crates/cli/src/main.rs", "mod run;\nuse acme_cli::args;\n"),
+            (
+                "crates/cli/src/run.rs",
+                "use crate::run;\nuse super::args;\n",
+            ),
+            (
+                "crates/web/Cargo.toml",
+                "[package]\nname = \"acme-web\"\n\n\
+                 [target.'cfg(unix)'.dev-dependencies]\n\
+                 core = { package = \"acme-core\", path = \"../core\" }\n",
+            ),
+            ("crates/web/src/main.rs", "use core::model;\n"),
+            ("crates/legacy/Cargo.toml", "[package]\nname = \"legacy\"\n"),
+        ],
+    );
+
+    assert_eq!(
+        RustDriver.source_dirs(dir.path()),
+        vec![
+            PathBuf::from("crates/cli/src"),
+            PathBuf::from("crates/core/src"),
+            PathBuf::from("crates/web/src"),
+        ]
+    );
+
+    let model = RustDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "acme_cli::args",
+            "acme_cli",
+            "acme_cli::main",
+            "acme_cli::main::run",
+            "acme_core",
+            "acme_core::model",
+            "acme_web",
+        ]
+    );
+    assert_eq!(module(&model, "acme_cli::main").name, "main");
+    let groups: Vec<_> = model
+        .modules
+        .iter()
+        .map(|m| m.group.as_deref().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            "acme_cli",
+            "acme_cli",
+            "acme_cli",
+            "acme_cli",
+            "acme_core",
+            "acme_core",
+            "acme_web",
+        ]
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("acme_cli".to_string(), "acme_core".to_string()),
+            ("acme_cli::args".to_string(), "acme_core::model".to_string()),
+            ("acme_cli::main".to_string(), "acme_cli::args".to_string()),
+            (
+                "acme_cli::main::run".to_string(),
+                "acme_cli::main".to_string()
+            ),
+            ("acme_web".to_string(), "acme_core".to_string()),
+            ("acme_web".to_string(), "acme_core::model".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn python_driver_derives_module_ids_from_package_layout() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("src/pkg/__init__.py", "\"\"\"The package.\"\"\"\n"),
+            ("src/pkg/core.py", ""),
+            ("src/pkg/sub/__init__.py", ""),
+            ("src/pkg/sub/mod.py", ""),
+            ("src/tools/run.py", ""),
+            ("scripts/build.py", ""),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "pkg",
+            "pkg.core",
+            "pkg.sub",
+            "pkg.sub.mod",
+            "scripts.build",
+            "tools.run"
+        ]
+    );
+    assert_eq!(module(&model, "pkg").doc.as_deref(), Some("The package."));
+}
+
+#[test]
+fn python_driver_extracts_symbols_honouring_dunder_all() {
+    let dir = TempDir::new().unwrap();
+    let source = r#"
+from enum import Enum
+from typing import Protocol, TypeAlias
+
+__all__ = ["Point", "render", "Color"]
+
+MAX_SIZE: int = 10
+PathLike: TypeAlias = str
+
+@dataclass(frozen=True)
+class Point:
+    """A point.
+
+    With two coordinates.
+    """
+
+class Color(Enum):
+    RED = 1
+
+class Renderer(Protocol):
+    pass
+
+async def render(p: Point) -> str:
+    """Render it."""
+
+def _helper():
+    pass
+"#;
+    let paths = write_tree(dir.path(), &[("app.py", source)]);
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let symbols = &module(&model, "app").symbols;
+    let summary: Vec<_> = symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("MAX_SIZE", SymbolKind::Constant, Visibility::Private),
+            ("PathLike", SymbolKind::TypeAlias, Visibility::Private),
+            ("Point", SymbolKind::Struct, Visibility::Public),
+            ("Color", SymbolKind::Enum, Visibility::Public),
+            ("Renderer", SymbolKind::Trait, Visibility::Private),
+            ("render", SymbolKind::Function, Visibility::Public),
+            ("_helper", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+
+    let point = &symbols[2];
+    assert_eq!(point.line, 11);
+    assert_eq!(
+        point.doc.as_deref(),
+        Some("A point.\n\nWith two coordinates.")
+    );
+    assert_eq!(
+        point.signature.as_deref(),
+        Some("@dataclass(frozen=True) class Point")
+    );
+    assert_eq!(
+        symbols[5].signature.as_deref(),
+        Some("async def render(p: Point) -> str")
+    );
+    assert_eq!(symbols[0].signature.as_deref(), Some("MAX_SIZE: int"));
+}
+
+#[test]
+fn python_driver_resolves_imports_and_base_classes() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("pkg/__init__.py", "from .models import Base\n"),
+            ("pkg/models.py", "import os\nclass Base: ...\n"),
+            (
+                "pkg/api/__init__.py",
+                "from ..models import Base\nfrom . import views\n",
+            ),
+            (
+                "pkg/api/views.py",
+                "import pkg.models as m\nfrom pkg import util\n\nclass View(m.Base): ...\n",
+            ),
+            (
+                "pkg/util.py",
+                "def f():\n    from pkg.api.views import *\n\nclass Helper(Base): ...\n",
+            ),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            pair("pkg", "pkg.models"),
+            pair("pkg.api", "pkg.api.views"),
+            pair("pkg.api", "pkg.models"),
+            pair("pkg.api.views", "pkg.models"),
+            pair("pkg.api.views", "pkg.util"),
+            pair("pkg.util", "pkg.api.views"),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![pair("pkg.api.views", "pkg.models")]
+    );
+}
+
+#[test]
+fn python_driver_reads_notebooks_cell_by_cell() {
+    let dir = TempDir::new().unwrap();
+    let notebook = serde_json::json!({
+        "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+        "nbformat": 4,
+        "cells": [
+            {"cell_type": "markdown", "source": ["# Training\n"]},
+            {
+                "cell_type": "code",
+                "source": ["%matplotlib inline\n", "!pip install torch\n", "from pkg.models import Net\n", "import pkg.data as data"]
+            },
+            {"cell_type": "code", "source": "%%bash\nclass NotPython: ...\n"},
+            {
+                "cell_type": "code",
+                "source": ["%%time\n", "files = !ls\n", "\n", "def train(net: Net):\n", "    return net\n"]
+            },
+            {"cell_type": "code", "source": ["class Model(Net):\n", "    ...\n"]}
+        ]
+    });
+    let r_notebook = serde_json::json!({
+        "metadata": {"kernelspec": {"language": "R", "name": "ir"}},
+        "cells": [{"cell_type": "code", "source": "library(ggplot2)\n"}]
+    });
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("pkg/__init__.py", ""),
+            ("pkg/models.py", "class Net: ...\n"),
+            ("pkg/data.py", ""),
+            ("notebooks/train.ipynb", &notebook.to_string()),
+            ("notebooks/plots.ipynb", &r_notebook.to_string()),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    assert!(model.modules.iter().all(|m| m.id.0 != "notebooks.plots"));
+    let train = module(&model, "notebooks.train");
+    let located: Vec<_> = train
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.cell, s.line))
+        .collect();
+    assert_eq!(located, vec![("train", Some(4), 4), ("Model", Some(5), 1)]);
+    assert_eq!(module(&model, "pkg.models").symbols[0].cell, None);
+
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            pair("notebooks.train", "pkg.data"),
+            pair("notebooks.train", "pkg.models")
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![pair("notebooks.train", "pkg.models")]
+    );
+}
+
+#[test]
+fn detects_typescript_by_tsconfig_or_package_json() {
+    let names = |dir: &TempDir| -> Vec<&'static str> {
+        DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect()
+    };
+
+    let tsconfig = TempDir::new().unwrap();
+    std::fs::write(tsconfig.path().join("tsconfig.json"), "{}").unwrap();
+    assert_eq!(names(&tsconfig), vec!["typescript"]);
+
+    let package = TempDir::new().unwrap();
+    std::fs::write(
+        package.path().join("package.json"),
+        r#"{ "devDependencies": { "typescript": "^5.4.0" } }"#,
+    )
+    .unwrap();
+    assert_eq!(names(&package), vec!["typescript"]);
+
+    let plain_js = TempDir::new().unwrap();
+    std::fs::write(
+        plain_js.path().join("package.json"),
+        r#"{ "dependencies": { "react": "^18.0.0" } }"#,
+    )
+    .unwrap();
+    assert!(names(&plain_js).is_empty());
+}
+
+#[test]
+fn typescript_driver_extracts_declarations_and_exports() {
+    let dir = TempDir::new().unwrap();
+    let source = r#"/** Shapes module. */
+
+/** A shape. */
+export interface Shape { area(): number }
+
+export abstract class Base {}
+
+enum Color { Red }
+
+export type Id = string;
+
+export const origin = { x: 0 };
+export const area = (s: Shape): number => s.area();
+
+function helper(x: number): number { return x; }
+
+export { Color };
+"#;
+    let paths = write_tree(dir.path(), &[("src/shapes.ts", source)]);
+
+    let model = TypeScriptDriver.parse(&paths).unwrap();
+    let shapes = module(&model, "shapes");
+    assert_eq!(shapes.doc.as_deref(), Some("Shapes module."));
+    let summary: Vec<_> = shapes
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Shape", SymbolKind::Trait, Visibility::Public),
+            ("Base", SymbolKind::Struct, Visibility::Public),
+            ("Color", SymbolKind::Enum, Visibility::Public),
+            ("Id", SymbolKind::TypeAlias, Visibility::Public),
+            ("origin", SymbolKind::Constant, Visibility::Public),
+            ("area", SymbolKind::Function, Visibility::Public),
+            ("helper", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    assert_eq!(shapes.symbols[0].doc.as_deref(), Some("A shape."));
+    assert_eq!(shapes.symbols[0].line, 4);
+    assert_eq!(
+        shapes.symbols[6].signature.as_deref(),
+        Some("function helper(x: number): number")
+    );
+}
+
+#[test]
+fn typescript_driver_resolves_aliases_barrels_and_heritage() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "tsconfig.json",
+                r#"{
+  // Comments and trailing commas are allowed here.
+  "compilerOptions": {
+    "baseUrl": ".",
+    "paths": { "@core/*": ["src/core/*"], },
+  },
+}"#,
+            ),
+            ("src/core/index.ts", "export * from './model';\n"),
+            (
+                "src/core/model.ts",
+                "export interface Entity {}\nexport class Model {}\n",
+            ),
+            (
+                "src/ui/view.tsx",
+                "import React from 'react';\nimport { Model, Entity } from '@core/model';\n\
+                 import * as core from '../core';\n\
+                 export class View extends Model implements Entity {}\n\
+                 export const lazy = () => import('./widget.js');\n",
+            ),
+            (
+                "src/ui/widget.tsx",
+                "import { Entity } from 'src/core/model';\n",
+            ),
+        ],
+    );
+    let sources: Vec<_> = paths
+        .into_iter()
+        .filter(|p| p.extension().is_some_and(|e| e != "json"))
+        .collect();
+
+    let model = TypeScriptDriver.parse(&sources).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            pair("core/index", "core/model"),
+            pair("ui/view", "core/index"),
+            pair("ui/view", "core/model"),
+            pair("ui/view", "ui/widget"),
+            pair("ui/widget", "core/model"),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![pair("ui/view", "core/model")]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![pair("ui/view", "core/model")]
+    );
+}
+
+#[test]
+fn detects_go_by_go_mod() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("go.mod"), "module example.com/app\n").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["go"]);
+}
+
+#[test]
+fn go_driver_models_packages_with_import_paths() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("go.mod", "module example.com/app\n\ngo 1.22\n"),
+            (
+                "main.go",
+                "package main\n\nimport (\n\t\"fmt\"\n\t\"example.com/app/internal/store\"\n)\n\nfunc main() { fmt.Println(store.New()) }\n",
+            ),
+            (
+                "internal/store/doc.go",
+                "// Package store persists things.\npackage store\n",
+            ),
+            (
+                "internal/store/store.go",
+                r#"package store
+
+import (
+	"net/http"
+	"github.com/google/uuid"
+	"example.com/app/internal/missing"
+)
+
+// Store keeps items.
+type Store struct{ items []string }
+
+type Reader interface{ Read() string }
+
+type ID string
+
+const (
+	MaxItems int = 10
+	prefix       = "s-"
+)
+
+// New builds a Store.
+func New() *Store { return &Store{} }
+
+// Add appends an item.
+func (s *Store) Add(item string) error { return nil }
+
+func (s *Store) reset() {}
+"#,
+            ),
+            ("internal/store/store_test.go", "package store\n"),
+        ],
+    );
+
+    let model = GoDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec!["example.com/app", "example.com/app/internal/store"]
+    );
+
+    let store = module(&model, "example.com/app/internal/store");
+    assert_eq!(store.name, "store");
+    assert_eq!(store.doc.as_deref(), Some("Package store persists things."));
+    let summary: Vec<_> = store
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Store", SymbolKind::Struct, Visibility::Public),
+            ("Reader", SymbolKind::Trait, Visibility::Public),
+            ("ID", SymbolKind::TypeAlias, Visibility::Public),
+            ("MaxItems", SymbolKind::Constant, Visibility::Public),
+            ("prefix", SymbolKind::Constant, Visibility::Private),
+            ("New", SymbolKind::Function, Visibility::Public),
+            ("Store.Add", SymbolKind::Function, Visibility::Public),
+            ("Store.reset", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    let add = &store.symbols[6];
+    assert_eq!(add.doc.as_deref(), Some("Add appends an item."));
+    assert_eq!(
+        add.signature.as_deref(),
+        Some("func (s *Store) Add(item string) error")
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![(
+            "example.com/app".to_string(),
+            "example.com/app/internal/store".to_string()
+        )]
+    );
+    assert_eq!(
+        store.external_imports,
+        vec![
+            ExternalImport {
+                path: "net/http".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "github.com/google/uuid".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+}
+
+#[test]
+fn go_driver_records_package_level_vars() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("go.mod", "module example.com/app\n"),
+            (
+                "config/config.go",
+                r#"package config
+
+import "errors"
+
+// ErrMissing reports an unset key.
+var ErrMissing = errors.New("missing")
+
+var (
+	// Timeout bounds every request.
+	Timeout int = 30
+	host, port  = "localhost", 8080
+)
+
+var _ error = ErrMissing
+
+func load() { var local = 1; _ = local }
+"#,
+            ),
+        ],
+    );
+
+    let model = GoDriver.parse(&paths).unwrap();
+    let config = module(&model, "example.com/app/config");
+    let summary: Vec<_> = config
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("ErrMissing", SymbolKind::Constant, Visibility::Public),
+            ("Timeout", SymbolKind::Constant, Visibility::Public),
+            ("host", SymbolKind::Constant, Visibility::Private),
+            ("port", SymbolKind::Constant, Visibility::Private),
+            ("load", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    let err = &config.symbols[0];
+    assert_eq!(err.doc.as_deref(), Some("ErrMissing reports an unset key."));
+    assert_eq!(err.signature.as_deref(), Some("var ErrMissing"));
+    assert_eq!(err.line, 6);
+    let timeout = &config.symbols[1];
+    assert_eq!(
+        timeout.doc.as_deref(),
+        Some("Timeout bounds every request.")
+    );
+    assert_eq!(timeout.signature.as_deref(), Some("var Timeout int"));
+}
+
+#[test]
+fn go_driver_attaches_methods_to_their_receiver_types() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("go.mod", "module example.com/app\n"),
+            (
+                "list/list.go",
+                "package list\n\n// List is a generic list.\ntype List[T any] struct{ items []T }\n\ntype counter int\n",
+            ),
+            (
+                "list/methods.go",
+                r#"package list
+
+// Push appends v.
+func (l *List[T]) Push(v T) { l.items = append(l.items, v) }
+
+func (l List[T]) Len() int { return len(l.items) }
+
+func (c counter) inc() counter { return c + 1 }
+
+func New[T any]() *List[T] { return &List[T]{} }
+"#,
+            ),
+        ],
+    );
+
+    let model = GoDriver.parse(&paths).unwrap();
+    let list = module(&model, "example.com/app/list");
+    let summary: Vec<_> = list
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("List", SymbolKind::Struct, Visibility::Public),
+            ("counter", SymbolKind::TypeAlias, Visibility::Private),
+            ("List.Push", SymbolKind::Function, Visibility::Public),
+            ("List.Len", SymbolKind::Function, Visibility::Public),
+            ("counter.inc", SymbolKind::Function, Visibility::Private),
+            ("New", SymbolKind::Function, Visibility::Public),
+        ]
+    );
+    let push = &list.symbols[2];
+    assert_eq!(push.doc.as_deref(), Some("Push appends v."));
+    assert_eq!(
+        push.signature.as_deref(),
+        Some("func (l *List[T]) Push(v T)")
+    );
+}
+
+#[test]
+fn go_driver_links_embedded_structs_and_interfaces() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("go.mod", "module example.com/app\n"),
+            (
+                "base/base.go",
+                "package base\n\ntype Base struct{ ID string }\n\ntype Reader interface{ Read() string }\n\ntype ID int\n",
+            ),
+            ("log/log.go", "package log\n\ntype Logger struct{}\n"),
+            (
+                "api/handler.go",
+                r#"package api
+
+import (
+	"io"
+
+	"example.com/app/base"
+	applog "example.com/app/log"
+)
+
+type Handler struct {
+	*base.Base
+	applog.Logger
+	io.Writer
+	name string
+}
+"#,
+            ),
+            (
+                "stream/stream.go",
+                r#"package stream
+
+import (
+	"io"
+
+	"example.com/app/base"
+)
+
+type Source interface {
+	base.Reader
+	io.Closer
+	Open() error
+}
+"#,
+            ),
+            (
+                "num/num.go",
+                r#"package num
+
+import "example.com/app/base"
+
+type Number interface {
+	~int | base.ID
+}
+
+type Pair struct {
+	Left base.ID
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = GoDriver.parse(&paths).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![
+            pair("example.com/app/api", "example.com/app/base"),
+            pair("example.com/app/api", "example.com/app/log"),
+            pair("example.com/app/stream", "example.com/app/base"),
+        ]
+    );
+}
+
+#[test]
+fn go_driver_skips_test_files_and_links_internal_packages() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("go.mod", "module example.com/app\n"),
+            (
+                "internal/store/store.go",
+                "package store\n\nfunc Open() {}\n",
+            ),
+            (
+                "internal/store/store_test.go",
+                "package store_test\n\nimport \"testing\"\n\nfunc TestOpen(t *testing.T) {}\n",
+            ),
+            (
+                "internal/fixtures/fixtures_test.go",
+                "package fixtures\n\nfunc Load() {}\n",
+            ),
+            (
+                "cmd/server/main.go",
+                r#"package main
+
+import (
+	"example.com/app/internal/fixtures"
+	"example.com/app/internal/store"
+)
+
+func main() {}
+"#,
+            ),
+            ("tools/go.mod", "module example.com/tools\n"),
+            (
+                "tools/gen/gen.go",
+                "package gen\n\nimport \"example.com/app/internal/store\"\n",
+            ),
+        ],
+    );
+
+    let model = GoDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "example.com/app/cmd/server",
+            "example.com/app/internal/store",
+            "example.com/tools/gen",
+        ]
+    );
+    let store = module(&model, "example.com/app/internal/store");
+    let names: Vec<_> = store.symbols.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Open"]);
+    // The test-only package is part of the module, not a dependency.
+    assert!(
+        module(&model, "example.com/app/cmd/server")
+            .external_imports
+            .is_empty()
+    );
+
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            pair(
+                "example.com/app/cmd/server",
+                "example.com/app/internal/store"
+            ),
+            pair("example.com/tools/gen", "example.com/app/internal/store"),
+        ]
+    );
+}
+
+#[test]
+fn detects_java_by_maven_or_gradle_build() {
+    for manifest in ["pom.xml", "build.gradle", "build.gradle.kts"] {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(manifest), "").unwrap();
+
+        let active: Vec<_> = DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect();
+        assert_eq!(active, vec!["java"], "{manifest}");
+    }
+}
+
+#[test]
+fn java_driver_extracts_types_with_access_modifiers() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "src/main/java/com/acme/billing/Invoice.java",
+            r#"package com.acme.billing;
+
+/**
+ * A bill sent to a customer.
+ */
+@Entity
+public final class Invoice<T> extends Document {
+    protected enum Status { OPEN, PAID }
+
+    private record Line(String sku, int qty) {}
+
+    static class Builder {}
+}
+
+interface Payable {
+    class Receipt {}
+}
+
+@interface Audited {}
+"#,
+        )],
+    );
+
+    let model = JavaDriver.parse(&paths).unwrap();
+    let invoice = module(&model, "com.acme.billing.Invoice");
+    assert_eq!(invoice.name, "Invoice");
+    let summary: Vec<_> = invoice
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Invoice", SymbolKind::Struct, Visibility::Public),
+            ("Invoice.Status", SymbolKind::Enum, Visibility::Protected),
+            ("Invoice.Line", SymbolKind::Struct, Visibility::Private),
+            ("Invoice.Builder", SymbolKind::Struct, Visibility::Internal),
+            ("Payable", SymbolKind::Trait, Visibility::Internal),
+            ("Payable.Receipt", SymbolKind::Struct, Visibility::Public),
+            ("Audited", SymbolKind::Trait, Visibility::Internal),
+        ]
+    );
+    let class = &invoice.symbols[0];
+    assert_eq!(class.line, 6);
+    assert_eq!(class.doc.as_deref(), Some("A bill sent to a customer."));
+    assert_eq!(
+        class.signature.as_deref(),
+        Some("@Entity public final class Invoice<T> extends Document")
+    );
+}
+
+#[test]
+fn java_driver_resolves_imports_and_supertypes() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "com/acme/core/Entity.java",
+                "package com.acme.core;\npublic abstract class Entity {}\n",
+            ),
+            (
+                "com/acme/core/Named.java",
+                "package com.acme.core;\npublic interface Named { interface Tag {} }\n",
+            ),
+            (
+                "com/acme/model/Auditable.java",
+                "package com.acme.model;\ninterface Auditable extends com.acme.core.Named.Tag {}\n",
+            ),
+            (
+                "com/acme/model/User.java",
+                r#"package com.acme.model;
+
+import java.util.List;
+import com.google.common.base.Preconditions;
+import com.acme.core.*;
+import static com.acme.core.Named.Tag;
+
+public class User extends Entity implements Named, Auditable, Comparable<User> {}
+"#,
+            ),
+        ],
+    );
+
+    let model = JavaDriver.parse(&paths).unwrap();
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![
+            (
+                "com.acme.model.Auditable".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.model.Auditable".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        module(&model, "com.acme.model.User").external_imports,
+        vec![
+            ExternalImport {
+                path: "java.util.List".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "com.google.common.base.Preconditions".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_kotlin_alongside_java_in_a_gradle_build() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("build.gradle.kts"),
+        "plugins {\n    kotlin(\"jvm\") version \"2.0.0\"\n}\n",
+    )
+    .unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["java", "kotlin"]);
+
+    // A Kotlin build script alone does not make a Kotlin project.
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("build.gradle.kts"), "plugins { java }\n").unwrap();
+    assert!(
+        !DriverRegistry::default()
+            .detect_all(dir.path())
+            .iter()
+            .any(|d| d.name() == "kotlin")
+    );
+}
+
+#[test]
+fn kotlin_driver_extracts_declarations_with_visibility() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "src/main/kotlin/com/acme/billing/Invoice.kt",
+            r#"package com.acme.billing
+
+import java.time.Instant
+
+/**
+ * A bill sent to a customer.
+ */
+@Serializable
+data class Invoice(val id: Long, val issued: Instant) {
+    enum class Status { OPEN, PAID }
+
+    private object Defaults
+
+    companion object {
+        fun empty() = Invoice(0, Instant.EPOCH)
+    }
+
+    fun total(): Long = 0
+}
+
+sealed class Result<out T> {
+    class Ok<T>(val value: T) : Result<T>()
+}
+
+internal interface Payable
+
+fun interface Handler {
+    fun handle(invoice: Invoice)
+}
+
+typealias Ledger = Map<Long, Invoice>
+
+const val MAX_LINES = 100
+
+private fun audit(invoice: Invoice) {}
+
+fun <T> List<T>.second(): T = this[1]
+"#,
+        )],
+    );
+
+    let model = KotlinDriver.parse(&paths).unwrap();
+    let invoice = module(&model, "com.acme.billing.Invoice");
+    assert_eq!(invoice.name, "Invoice");
+    let summary: Vec<_> = invoice
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Invoice", SymbolKind::Struct, Visibility::Public),
+            ("Invoice.Status", SymbolKind::Enum, Visibility::Public),
+            ("Invoice.Defaults", SymbolKind::Struct, Visibility::Private),
+            ("Result", SymbolKind::Struct, Visibility::Public),
+            ("Result.Ok", SymbolKind::Struct, Visibility::Public),
+            ("Payable", SymbolKind::Trait, Visibility::Internal),
+            ("Handler", SymbolKind::Trait, Visibility::Public),
+            ("Ledger", SymbolKind::TypeAlias, Visibility::Public),
+            ("MAX_LINES", SymbolKind::Constant, Visibility::Public),
+            ("audit", SymbolKind::Function, Visibility::Private),
+            ("List.second", SymbolKind::Function, Visibility::Public),
+        ]
+    );
+    let class = &invoice.symbols[0];
+    assert_eq!(class.line, 8);
+    assert_eq!(class.doc.as_deref(), Some("A bill sent to a customer."));
+    assert_eq!(
+        class.signature.as_deref(),
+        Some("@Serializable data class Invoice(val id: Long, val issued: Instant)")
+    );
+    let second = invoice.symbols.last().unwrap();
+    assert_eq!(
+        second.signature.as_deref(),
+        Some("fun <T> List<T>.second(): T")
+    );
+    assert_eq!(
+        invoice.external_imports,
+        vec![ExternalImport {
+            path: "java.time.Instant".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn kotlin_and_java_modules_link_across_languages() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "src/main/java/com/acme/core/Entity.java",
+                "package com.acme.core;\npublic abstract class Entity {}\n",
+            ),
+            (
+                "src/main/java/com/acme/core/Named.java",
+                "package com.acme.core;\npublic interface Named {}\n",
+            ),
+            (
+                "src/main/kotlin/com/acme/model/User.kt",
+                r#"package com.acme.model
+
+import com.acme.core.Entity
+import com.acme.core.Named as HasName
+import kotlinx.coroutines.flow.Flow
+
+class User : Entity(), HasName, Auditable
+
+interface Auditable : HasName
+"#,
+            ),
+            (
+                "src/main/java/com/acme/api/UserView.java",
+                r#"package com.acme.api;
+
+import com.acme.model.User;
+
+public class UserView extends User implements com.acme.model.Auditable {}
+"#,
+            ),
+        ],
+    );
+
+    let models = vec![
+        JavaDriver.parse(&paths).unwrap(),
+        KotlinDriver.parse(&paths).unwrap(),
+    ];
+    let model = merge_all(models);
+    assert_eq!(model.language, "polyglot");
+    assert_eq!(model.modules.len(), 4);
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            (
+                "com.acme.api.UserView".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![
+            (
+                "com.acme.api.UserView".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            (
+                "com.acme.api.UserView".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn detects_csharp_by_solution_or_project_file() {
+    for manifest in ["Acme.sln", "Acme.csproj"] {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(manifest), "").unwrap();
+
+        let active: Vec<_> = DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect();
+        assert_eq!(active, vec!["csharp"], "{manifest}");
+    }
+}
+
+#[test]
+fn csharp_driver_groups_types_by_namespace() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "Billing/Invoice.cs",
+                r#"using System;
+
+namespace Acme.Billing
+{
+    /// <summary>
+    /// A bill sent to a <see cref="T:Acme.Billing.Customer"/>.
+    /// </summary>
+    [Serializable]
+    public sealed class Invoice<T> : IDisposable where T : class
+    {
+        private string note = "} not a brace {";
+
+        protected enum Status { Open, Paid }
+
+        class Line { }
+
+        public void Dispose() { if (note == @"C:\") { } }
+    }
+
+    interface IPayable
+    {
+        record Receipt(decimal Amount);
+    }
+}
+"#,
+            ),
+            (
+                "Billing/Customer.cs",
+                "namespace Acme.Billing;\n\ninternal record struct Customer(string Name);\npublic delegate void Paid(Invoice<object> invoice);\n",
+            ),
+        ],
+    );
+
+    let model = CSharpDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["Acme.Billing"]);
+
+    let billing = module(&model, "Acme.Billing");
+    assert_eq!(billing.name, "Billing");
+    let summary: Vec<_> = billing
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Customer", SymbolKind::Struct, Visibility::Internal),
+            ("Paid", SymbolKind::TypeAlias, Visibility::Public),
+            ("Invoice", SymbolKind::Struct, Visibility::Public),
+            ("Invoice.Status", SymbolKind::Enum, Visibility::Protected),
+            ("Invoice.Line", SymbolKind::Struct, Visibility::Private),
+            ("IPayable", SymbolKind::Trait, Visibility::Internal),
+            ("IPayable.Receipt", SymbolKind::Struct, Visibility::Public),
+        ]
+    );
+    let invoice = &billing.symbols[2];
+    assert_eq!(invoice.line, 9);
+    assert_eq!(
+        invoice.doc.as_deref(),
+        Some("A bill sent to a Acme.Billing.Customer.")
+    );
+    assert_eq!(
+        invoice.signature.as_deref(),
+        Some("public sealed class Invoice<T> : IDisposable where T : class")
+    );
+    assert_eq!(
+        billing.external_imports,
+        vec![ExternalImport {
+            path: "System".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn csharp_driver_links_usings_bases_and_project_references() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "src/Core/Core.csproj",
+                "<Project Sdk=\"Microsoft.NET.Sdk\">\n  <PropertyGroup>\n    <RootNamespace>Acme.Core</RootNamespace>\n  </PropertyGroup>\n</Project>\n",
+            ),
+            (
+                "src/Core/Entity.cs",
+                "namespace Acme.Core { public abstract class Entity { } public interface INamed { } }\n",
+            ),
+            (
+                "src/Core/Storage/Repo.cs",
+                "namespace Acme.Core.Storage { public interface IRepo<T> : Acme.Core.INamed { } }\n",
+            ),
+            (
+                "src/App/App.csproj",
+                "<Project Sdk=\"Microsoft.NET.Sdk.Web\">\n  <ItemGroup>\n    <ProjectReference Include=\"..\\Core\\Core.csproj\" />\n    <PackageReference Include=\"Newtonsoft.Json\" Version=\"13.0.3\" />\n  </ItemGroup>\n</Project>\n",
+            ),
+            (
+                "src/App/User.cs",
+                r#"using Store = Acme.Core.Storage;
+using Newtonsoft.Json;
+
+namespace App.Models
+{
+    public class User : Acme.Core.Entity, global::Acme.Core.INamed, Store.IRepo<User> { }
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = CSharpDriver.parse(&paths).unwrap();
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            // `<ProjectReference>` from App to Core.
+            ("App.Models".to_string(), "Acme.Core".to_string()),
+            // `using Store = Acme.Core.Storage;`
+            ("App.Models".to_string(), "Acme.Core.Storage".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![
+            ("Acme.Core.Storage".to_string(), "Acme.Core".to_string()),
+            ("App.Models".to_string(), "Acme.Core".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            ("App.Models".to_string(), "Acme.Core".to_string()),
+            ("App.Models".to_string(), "Acme.Core.Storage".to_string()),
+        ]
+    );
+    assert_eq!(
+        module(&model, "App.Models").external_imports,
+        vec![ExternalImport {
+            path: "Newtonsoft.Json".to_string(),
+            kind: ExternalKind::ThirdParty,
+        }]
+    );
+}
+
+#[test]
+fn detects_cpp_by_build_files() {
+    for manifest in [
+        "CMakeLists.txt",
+        "Makefile",
+        "meson.build",
+        "compile_commands.json",
+    ] {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(manifest), "").unwrap();
+
+        let active: Vec<_> = DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect();
+        assert_eq!(active, vec!["cpp"], "{manifest}");
+    }
+}
+
+#[test]
+fn cpp_driver_pairs_headers_with_sources() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "include/gpio.h",
+                r#"#ifndef GPIO_H
+#define GPIO_H
+#include <stdint.h>
+
+/** A GPIO pin. */
+typedef struct {
+    uint8_t port;
+} gpio_pin;
+
+typedef enum { GPIO_LOW, GPIO_HIGH } gpio_level;
+
+typedef uint32_t gpio_mask;
+
+/// Drive a pin.
+void gpio_write(gpio_pin pin, gpio_level level);
+#endif
+"#,
+            ),
+            (
+                "src/gpio.c",
+                r#"#include "gpio.h"
+#include "hal/regs.h"
+
+struct state { int ticks; };
+
+static void tick(void) {}
+
+void gpio_write(gpio_pin pin, gpio_level level) { tick(); }
+"#,
+            ),
+            ("src/hal/regs.h", "int regs_read(void);\n"),
+        ],
+    );
+
+    let model = CppDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["src/gpio", "src/hal/regs"]);
+
+    let gpio = module(&model, "src/gpio");
+    assert_eq!(gpio.name, "gpio");
+    let summary: Vec<_> = gpio
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("gpio_pin", SymbolKind::Struct, Visibility::Public),
+            ("gpio_level", SymbolKind::Enum, Visibility::Public),
+            ("gpio_mask", SymbolKind::TypeAlias, Visibility::Public),
+            ("gpio_write", SymbolKind::Function, Visibility::Public),
+            ("state", SymbolKind::Struct, Visibility::Private),
+            ("tick", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    assert_eq!(gpio.symbols[0].doc.as_deref(), Some("A GPIO pin."));
+    let write = &gpio.symbols[3];
+    assert_eq!(write.line, 15);
+    assert_eq!(write.doc.as_deref(), Some("Drive a pin."));
+    assert_eq!(
+        write.signature.as_deref(),
+        Some("void gpio_write(gpio_pin pin, gpio_level level)")
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![("src/gpio".to_string(), "src/hal/regs".to_string())]
+    );
+    assert_eq!(
+        gpio.external_imports,
+        vec![ExternalImport {
+            path: "stdint.h".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn cpp_driver_resolves_includes_through_compile_commands() {
+    let dir = TempDir::new().unwrap();
+    let compile_commands = format!(
+        r#"[{{"directory": "{}", "file": "../app/main.cpp", "arguments": ["c++", "-I", "../lib/include", "-c", "../app/main.cpp"]}}]"#,
+        dir.path().join("build").display()
+    );
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("compile_commands.json", &compile_commands),
+            (
+                "lib/include/net/socket.hpp",
+                r#"#pragma once
+#include <vector>
+#include <boost/asio.hpp>
+
+namespace net {
+/// A TCP socket.
+class Socket : public Stream {
+public:
+    void send();
+};
+
+template <typename T>
+using Buffer = std::vector<T>;
+
+enum class Status : int { Ok, Closed };
+}
+"#,
+            ),
+            // Same include path, but not on main.cpp's `-I` list.
+            ("vendor/net/socket.hpp", "struct Socket;\n"),
+            (
+                "app/main.cpp",
+                r#"#include "net/socket.hpp"
+#include <vector>
+
+namespace {
+int helper() { return 0; }
+}
+
+void net::Socket::send() {}
+
+int main() { return helper(); }
+"#,
+            ),
+        ],
+    );
+
+    let model = CppDriver.parse(&paths).unwrap();
+    let socket = module(&model, "lib/include/net/socket");
+    let summary: Vec<_> = socket
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.signature.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("net", SymbolKind::Module, Some("namespace net")),
+            (
+                "net::Socket",
+                SymbolKind::Struct,
+                Some("class Socket : public Stream")
+            ),
+            (
+                "net::Buffer",
+                SymbolKind::TypeAlias,
+                Some("template <typename T> using Buffer = std::vector<T>")
+            ),
+            (
+                "net::Status",
+                SymbolKind::Enum,
+                Some("enum class Status : int")
+            ),
+        ]
+    );
+    assert_eq!(socket.symbols[1].doc.as_deref(), Some("A TCP socket."));
+
+    let main = module(&model, "app/main");
+    let visibility: Vec<_> = main
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.visibility))
+        .collect();
+    assert_eq!(
+        visibility,
+        vec![
+            ("helper", Visibility::Private),
+            ("main", Visibility::Public)
+        ]
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![("app/main".to_string(), "lib/include/net/socket".to_string())]
+    );
+    assert_eq!(
+        socket.external_imports,
+        vec![
+            ExternalImport {
+                path: "vector".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "boost/asio.hpp".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_ruby_by_gemfile_or_gemspec() {
+    for manifest in ["Gemfile", "acme.gemspec"] {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(manifest), "").unwrap();
+
+        let active: Vec<_> = DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect();
+        assert_eq!(active, vec!["ruby"], "{manifest}");
+    }
+}
+
+#[test]
+fn ruby_driver_extracts_classes_methods_and_visibility_sections() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "app/models/user.rb",
+            r#"# frozen_string_literal: true
+
+# A registered account.
+class User < ApplicationRecord
+  ROLES = %w[admin end member].freeze
+
+  # Look a user up by e-mail.
+  def self.find_by_email(email)
+    where(email: email).first
+  end
+
+  def display_name
+    return name if name.present?
+    query = <<~SQL
+      select * from users where end = 1
+    SQL
+    email.split("@").first
+  end
+
+  def admin? = role == "admin"
+
+  protected
+
+  def token
+    @token ||= SecureRandom.hex
+  end
+
+  private
+
+  def normalize!
+    self.email = email.downcase if email
+  end
+
+  class << self
+    def build_guest; end
+
+    private
+
+    def counter; end
+  end
+
+  public def touch_login; end
+
+  def legacy; end
+  private :legacy
+end
+
+def helper; end
+"#,
+        )],
+    );
+
+    let model = RubyDriver.parse(&paths).unwrap();
+    let user = module(&model, "user");
+    let summary: Vec<_> = user
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("User", SymbolKind::Struct, Visibility::Public),
+            ("User::ROLES", SymbolKind::Constant, Visibility::Public),
+            (
+                "User.find_by_email",
+                SymbolKind::Function,
+                Visibility::Public
+            ),
+            (
+                "User#display_name",
+                SymbolKind::Function,
+                Visibility::Public
+            ),
+            ("User#admin?", SymbolKind::Function, Visibility::Public),
+            ("User#token", SymbolKind::Function, Visibility::Protected),
+            ("User#normalize!", SymbolKind::Function, Visibility::Private),
+            ("User.build_guest", SymbolKind::Function, Visibility::Public),
+            ("User.counter", SymbolKind::Function, Visibility::Private),
+            ("User#touch_login", SymbolKind::Function, Visibility::Public),
+            ("User#legacy", SymbolKind::Function, Visibility::Private),
+            ("helper", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    let class = &user.symbols[0];
+    assert_eq!(class.line, 4);
+    assert_eq!(class.doc.as_deref(), Some("A registered account."));
+    assert_eq!(
+        class.signature.as_deref(),
+        Some("class User < ApplicationRecord")
+    );
+    let finder = &user.symbols[2];
+    assert_eq!(finder.doc.as_deref(), Some("Look a user up by e-mail."));
+    assert_eq!(
+        finder.signature.as_deref(),
+        Some("def self.find_by_email(email)")
+    );
+}
+
+#[test]
+fn ruby_driver_resolves_requires_mixins_and_autoloaded_constants() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "app/models/application_record.rb",
+                "class ApplicationRecord < ActiveRecord::Base\n  self.abstract_class = true\nend\n",
+            ),
+            (
+                "app/models/concerns/trackable.rb",
+                "module Trackable\n  extend ActiveSupport::Concern\nend\n",
+            ),
+            (
+                "app/models/billing/invoice.rb",
+                r#"module Billing
+  class Invoice < ApplicationRecord
+    include Trackable, Comparable
+    belongs_to :user
+
+    def notify
+      InvoiceMailer.with(invoice: self).deliver_later
+      Totals.new(self).sum
+    end
+  end
+end
+"#,
+            ),
+            (
+                "app/models/billing/totals.rb",
+                "module Billing\n  class Totals\n  end\nend\n",
+            ),
+            (
+                "app/mailers/invoice_mailer.rb",
+                "class InvoiceMailer < ApplicationMailer\nend\n",
+            ),
+            (
+                "lib/acme/pdf.rb",
+                r#"require "json"
+require "prawn"
+require "net/http"
+require_relative "pdf/layout"
+
+module Acme
+  module Pdf
+  end
+end
+"#,
+            ),
+            (
+                "lib/acme/pdf/layout.rb",
+                "module Acme\n  module Pdf\n    class Layout; end\n  end\nend\n",
+            ),
+            (
+                "script/render.rb",
+                "require \"acme/pdf\"\nAcme::Pdf::Layout.new\n",
+            ),
+        ],
+    );
+
+    let model = RubyDriver.parse(&paths).unwrap();
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            (
+                "app/models/billing/invoice".to_string(),
+                "app/mailers/invoice_mailer".to_string()
+            ),
+            (
+                "app/models/billing/invoice".to_string(),
+                "app/models/billing/totals".to_string()
+            ),
+            (
+                "lib/acme/pdf".to_string(),
+                "lib/acme/pdf/layout".to_string()
+            ),
+            ("script/render".to_string(), "lib/acme/pdf".to_string()),
+            (
+                "script/render".to_string(),
+                "lib/acme/pdf/layout".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![(
+            "app/models/billing/invoice".to_string(),
+            "app/models/application_record".to_string()
+        )]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![(
+            "app/models/billing/invoice".to_string(),
+            "app/models/concerns/trackable".to_string()
+        )]
+    );
+    assert_eq!(
+        module(&model, "lib/acme/pdf").external_imports,
+        vec![
+            ExternalImport {
+                path: "json".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "prawn".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+            ExternalImport {
+                path: "net/http".to_string(),
+                kind: ExternalKind::Std,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_php_by_composer_json() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("composer.json"), "{}").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["php"]);
+}
+
+#[test]
+fn php_driver_extracts_namespaced_declarations() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "app/Models/User.php",
+                r#"<?php
+
+declare(strict_types=1);
+
+namespace App\Models;
+
+use Illuminate\Database\Eloquent\Model;
+
+/**
+ * A registered account.
+ *
+ * @property string $email
+ */
+#[\Attribute]
+final class User extends Model
+{
+    use HasRoles;
+
+    public const TABLE = 'users';
+
+    public function scopeActive($query)
+    {
+        $label = Role::class;
+        $mapper = new class {
+            public function map() {}
+        };
+        $body = <<<EOT
+            class Fake {}
+            EOT;
+        return $query->where('active', true);
+    }
+}
+
+interface HasEmail
+{
+    public function email(): string;
+}
+
+trait HasRoles
+{
+    public function roles() {}
+}
+
+enum Role: string implements HasLabel
+{
+    case Admin = 'admin';
+}
+"#,
+            ),
+            (
+                "app/helpers.php",
+                r#"<?php
+
+namespace App;
+
+const VERSION = '1.0', CODENAME = "oak";
+
+/** Format a money amount. */
+function money(int $cents): string
+{
+    $fmt = function ($v) use ($cents) {
+        return $v;
+    };
+    return $fmt($cents);
+}
+"#,
+            ),
+            (
+                "resources/views/welcome.blade.php",
+                "<?php class NotAView {} ?>",
+            ),
+            (
+                "public/index.php",
+                "<html><?php function render() {} ?><p>class Inline {}</p></html>\n",
+            ),
+        ],
+    );
+
+    let model = PhpDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["App", "App\\Models", "global"]);
+
+    let models = module(&model, "App\\Models");
+    assert_eq!(models.name, "Models");
+    let summary: Vec<_> = models
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("User", SymbolKind::Struct, Visibility::Public),
+            ("HasEmail", SymbolKind::Trait, Visibility::Public),
+            ("HasRoles", SymbolKind::Trait, Visibility::Public),
+            ("Role", SymbolKind::Enum, Visibility::Public),
+        ]
+    );
+    let user = &models.symbols[0];
+    assert_eq!(user.line, 15);
+    assert_eq!(user.doc.as_deref(), Some("A registered account."));
+    assert_eq!(
+        user.signature.as_deref(),
+        Some("final class User extends Model")
+    );
+
+    let app = module(&model, "App");
+    let summary: Vec<_> = app
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("VERSION", SymbolKind::Constant),
+            ("CODENAME", SymbolKind::Constant),
+            ("money", SymbolKind::Function),
+        ]
+    );
+    assert_eq!(
+        app.symbols[2].doc.as_deref(),
+        Some("Format a money amount.")
+    );
+    assert_eq!(
+        app.symbols[2].signature.as_deref(),
+        Some("function money(int $cents): string")
+    );
+
+    let names: Vec<_> = module(&model, "global")
+        .symbols
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["render"]);
+}
+
+#[test]
+fn php_driver_maps_psr4_namespaces_and_resolves_uses() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "composer.json",
+                r#"{
+    "autoload": { "psr-4": { "App\\": "app/", "Database\\Factories\\": "database/factories/" } },
+    "autoload-dev": { "psr-4": { "Tests\\": "tests/" } }
+}"#,
+            ),
+            (
+                "app/Http/Controllers/Controller.php",
+                "<?php\nnamespace App\\Http\\Controllers;\n\nabstract class Controller {}\n",
+            ),
+            (
+                "app/Http/Controllers/InvoiceController.php",
+                r#"<?php
+namespace App\Http\Controllers;
+
+use App\Models\{Invoice, User as Customer};
+use App\Contracts\Billable;
+use App\Support\Missing;
+use Illuminate\Http\Request;
+use function App\money;
+use Exception;
+
+class InvoiceController extends Controller implements Billable, \JsonSerializable
+{
+    use \App\Concerns\Loggable;
+}
+"#,
+            ),
+            (
+                "app/Models/Invoice.php",
+                "<?php\nnamespace App\\Models;\n\nclass Invoice {}\n",
+            ),
+            (
+                "app/Models/User.php",
+                "<?php\nnamespace App\\Models;\n\nclass User {}\n",
+            ),
+            (
+                "app/Contracts/Billable.php",
+                "<?php\nnamespace App\\Contracts;\n\ninterface Billable extends \\App\\Support\\Countable {}\n",
+            ),
+            (
+                "app/Support/Countable.php",
+                "<?php\nnamespace App\\Support;\n\ninterface Countable {}\n",
+            ),
+            (
+                "app/Concerns/Loggable.php",
+                "<?php\nnamespace App\\Concerns;\n\ntrait Loggable {}\n",
+            ),
+            (
+                "app/helpers.php",
+                "<?php\nnamespace App;\n\nfunction money() {}\n",
+            ),
+            // No `namespace` line: PSR-4 supplies `Tests\Unit`.
+            (
+                "tests/Unit/InvoiceTest.php",
+                "<?php\n\nclass InvoiceTest {}\n",
+            ),
+        ],
+    );
+
+    let model = PhpDriver.parse(&paths).unwrap();
+    let tests = module(&model, "Tests\\Unit");
+    assert_eq!(tests.path, dir.path().join("tests/Unit"));
+    assert_eq!(
+        module(&model, "App\\Http\\Controllers").path,
+        dir.path().join("app/Http/Controllers")
+    );
+
+    let controllers = "App\\Http\\Controllers".to_string();
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            (controllers.clone(), "App".to_string()),
+            (controllers.clone(), "App\\Contracts".to_string()),
+            (controllers.clone(), "App\\Models".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![("App\\Contracts".to_string(), "App\\Support".to_string())]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            (controllers.clone(), "App\\Concerns".to_string()),
+            (controllers.clone(), "App\\Contracts".to_string()),
+        ]
+    );
+    assert_eq!(
+        module(&model, "App\\Http\\Controllers").external_imports,
+        vec![
+            ExternalImport {
+                path: "Illuminate\\Http\\Request".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+            ExternalImport {
+                path: "Exception".to_string(),
+                kind: ExternalKind::Std,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_swift_by_package_swift() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Package.swift"), "").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["swift"]);
+}
+
+#[test]
+fn swift_driver_extracts_declarations_with_access_levels() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "Package.swift",
+                r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "Shop",
+    targets: [.target(name: "Catalog")]
+)
+"#,
+            ),
+            (
+                "Sources/Catalog/Models/Product.swift",
+                r#"import Foundation
+
+/// An item for sale.
+///
+/// - Parameter id: Stable identifier.
+@MainActor
+public final class Product: Identifiable {
+    public let id: UUID
+    private(set) var price: Decimal
+
+    public init(id: UUID) { self.id = id; price = 0 }
+
+    public func discounted(by rate: Decimal) -> Decimal { price * (1 - rate) }
+
+    public enum Category: String {
+        case food, toys
+    }
+}
+
+struct Draft {}
+
+fileprivate actor Cache {}
+
+public protocol Pricing {
+    func price(for product: Product) -> Decimal
+}
+
+public typealias ProductID = UUID
+
+let defaultCurrency = "EUR"
+
+/// Formats a price.
+public func format(_ amount: Decimal) async throws -> String {
+    "\(amount)"
+}
+
+private func log(_ message: String) {}
+
+public extension Product {
+    func isFree() -> Bool { price == 0 }
+    private func audit() {}
+}
+
+extension Draft {
+    static func == (lhs: Draft, rhs: Draft) -> Bool { true }
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = SwiftDriver.parse(&paths).unwrap();
+    let product = module(&model, "Catalog/Models/Product");
+    assert_eq!(product.group.as_deref(), Some("Catalog"));
+    assert_eq!(product.name, "Product");
+    let summary: Vec<_> = product
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Product", SymbolKind::Struct, Visibility::Public),
+            ("Product.Category", SymbolKind::Enum, Visibility::Public),
+            ("Draft", SymbolKind::Struct, Visibility::Internal),
+            ("Cache", SymbolKind::Struct, Visibility::Private),
+            ("Pricing", SymbolKind::Trait, Visibility::Public),
+            ("ProductID", SymbolKind::TypeAlias, Visibility::Public),
+            (
+                "defaultCurrency",
+                SymbolKind::Constant,
+                Visibility::Internal
+            ),
+            ("format", SymbolKind::Function, Visibility::Public),
+            ("log", SymbolKind::Function, Visibility::Private),
+            ("Product.isFree", SymbolKind::Function, Visibility::Public),
+            ("Product.audit", SymbolKind::Function, Visibility::Private),
+            ("Draft.==", SymbolKind::Function, Visibility::Internal),
+        ]
+    );
+    let class = &product.symbols[0];
+    assert_eq!(class.line, 6);
+    assert_eq!(class.doc.as_deref(), Some("An item for sale."));
+    assert_eq!(
+        class.signature.as_deref(),
+        Some("@MainActor public final class Product: Identifiable")
+    );
+    let format = &product.symbols[7];
+    assert_eq!(format.doc.as_deref(), Some("Formats a price."));
+    assert_eq!(
+        format.signature.as_deref(),
+        Some("public func format(_ amount: Decimal) async throws -> String")
+    );
+    assert_eq!(
+        product.symbols[6].signature.as_deref(),
+        Some("let defaultCurrency")
+    );
+    assert_eq!(
+        product.external_imports,
+        vec![ExternalImport {
+            path: "Foundation".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn swift_driver_links_targets_and_protocol_conformances() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "Package.swift",
+                r#"// swift-tools-version:5.9
+import PackageDescription
+
+let package = Package(
+    name: "Shop",
+    dependencies: [
+        .package(url: "https://github.com/apple/swift-log", from: "1.5.0"),
+    ],
+    targets: [
+        .target(name: "Core", path: "Sources/CoreKit"),
+        .target(
+            name: "Checkout",
+            dependencies: ["Core", .product(name: "Logging", package: "swift-log")]
+        ),
+        .executableTarget(name: "shop", dependencies: ["Checkout"]),
+        .testTarget(name: "CheckoutTests", dependencies: ["Checkout"]),
+    ]
+)
+"#,
+            ),
+            ("Sources/CoreKit/Utils.swift", "// Helpers.\n"),
+            ("Sources/Checkout/Utils.swift", "// Helpers.\n"),
+            (
+                "Sources/CoreKit/Money.swift",
+                "public struct Money: Equatable {}\n",
+            ),
+            (
+                "Sources/CoreKit/Repository.swift",
+                "public protocol Repository {}\nopen class BaseService {}\n",
+            ),
+            (
+                "Sources/Checkout/Cart.swift",
+                r#"import Core
+import Logging
+
+public final class Cart: BaseService, Repository {
+    var total = Money()
+}
+
+extension Cart: Discountable {}
+"#,
+            ),
+            (
+                "Sources/Checkout/Discountable.swift",
+                "protocol Discountable {}\n",
+            ),
+            (
+                "Sources/shop/main.swift",
+                "import Checkout\n\nprint(\"hello\")\n",
+            ),
+            (
+                "Tests/CheckoutTests/CartTests.swift",
+                "import XCTest\n@testable import Checkout\n\nfinal class CartTests: XCTestCase {\n    func testDiscount() { _ = Discountable.self }\n}\n",
+            ),
+        ],
+    );
+
+    let model = SwiftDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model
+        .modules
+        .iter()
+        .map(|m| (m.group.as_deref().unwrap_or_default(), m.id.0.as_str()))
+        .collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            ("Checkout", "Checkout/Cart"),
+            ("Checkout", "Checkout/Discountable"),
+            ("Checkout", "Checkout/Utils"),
+            ("CheckoutTests", "CheckoutTests/CartTests"),
+            ("Core", "Core/Money"),
+            ("Core", "Core/Repository"),
+            ("Core", "Core/Utils"),
+            ("shop", "shop/main"),
+        ]
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("Checkout/Cart".to_string(), "Core/Money".to_string()),
+            ("Checkout/Cart".to_string(), "Core/Repository".to_string()),
+            (
+                "CheckoutTests/CartTests".to_string(),
+                "Checkout/Discountable".to_string()
+            ),
+            ("shop/main".to_string(), "Checkout/Cart".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![("Checkout/Cart".to_string(), "Core/Repository".to_string())]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            (
+                "Checkout/Cart".to_string(),
+                "Checkout/Discountable".to_string()
+            ),
+            ("Checkout/Cart".to_string(), "Core/Repository".to_string()),
+        ]
+    );
+    assert_eq!(
+        module(&model, "Checkout/Cart").external_imports,
+        vec![ExternalImport {
+            path: "Logging".to_string(),
+            kind: ExternalKind::ThirdParty,
+        }]
+    );
+}
+
+#[test]
+fn detects_elixir_by_mix_exs() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("mix.exs"), "").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["elixir"]);
+}
+
+#[test]
+fn elixir_driver_extracts_otp_symbols() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "mix.exs",
+                "defmodule Shop.MixProject do\n  use Mix.Project\nend\n",
+            ),
+            (
+                "lib/shop/cart.ex",
+                r#"defmodule Shop.Cart do
+  @moduledoc """
+  A shopping cart.
+  """
+  use GenServer
+
+  @type t :: %__MODULE__{items: list()}
+  @typep state :: map()
+
+  defstruct items: [], owner: nil
+
+  @doc "Starts the cart process."
+  def start_link(opts \\ []) do
+    GenServer.start_link(__MODULE__, opts, name: __MODULE__)
+  end
+
+  def total(%__MODULE__{items: items}), do: Enum.sum(items)
+  def total(_), do: 0
+
+  @impl true
+  def handle_call(:items, _from, state) do
+    pattern = ~r/end|do/
+    words = ~w(def end fn)
+    char = ?(
+    fun = fn x -> x end
+    {:reply, {pattern, words, char, fun}, state}
+  end
+
+  defp valid?(item) when is_map(item), do: true
+
+  defmacro __using__(_opts) do
+    quote do
+      def injected, do: :ok
+    end
+  end
+
+  defmodule Line do
+    defstruct [:sku, :qty]
+  end
+end
+"#,
+            ),
+            (
+                "lib/shop/pricing.ex",
+                r#"defmodule Shop.Pricing do
+  @callback price(item :: map(), currency :: atom()) :: integer()
+  @callback currency() :: atom()
+end
+
+defprotocol Shop.Describable do
+  def describe(value)
+end
+"#,
+            ),
+        ],
+    );
+
+    let model = ElixirDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "Shop.Cart",
+            "Shop.Cart.Line",
+            "Shop.Describable",
+            "Shop.Pricing"
+        ]
+    );
+
+    let cart = module(&model, "Shop.Cart");
+    assert_eq!(cart.doc.as_deref(), Some("A shopping cart."));
+    let summary: Vec<_> = cart
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("t", SymbolKind::TypeAlias, Visibility::Public),
+            ("state", SymbolKind::TypeAlias, Visibility::Private),
+            ("Cart", SymbolKind::Struct, Visibility::Public),
+            ("start_link/1", SymbolKind::Function, Visibility::Public),
+            ("total/1", SymbolKind::Function, Visibility::Public),
+            ("handle_call/3", SymbolKind::Function, Visibility::Public),
+            ("valid?/1", SymbolKind::Function, Visibility::Private),
+            ("__using__/1", SymbolKind::Function, Visibility::Public),
+        ]
+    );
+    let start_link = &cart.symbols[3];
+    assert_eq!(start_link.line, 13);
+    assert_eq!(start_link.doc.as_deref(), Some("Starts the cart process."));
+    assert_eq!(
+        start_link.signature.as_deref(),
+        Some(r"def start_link(opts \\ [])")
+    );
+    assert_eq!(
+        cart.external_imports,
+        vec![ExternalImport {
+            path: "GenServer".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+
+    let line: Vec<_> = module(&model, "Shop.Cart.Line")
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone()))
+        .collect();
+    assert_eq!(line, vec![("Line", SymbolKind::Struct)]);
+
+    let pricing: Vec<_> = module(&model, "Shop.Pricing")
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone()))
+        .collect();
+    assert_eq!(
+        pricing,
+        vec![
+            ("Pricing", SymbolKind::Trait),
+            ("price/2", SymbolKind::Function),
+            ("currency/0", SymbolKind::Function),
+        ]
+    );
+    let describable: Vec<_> = module(&model, "Shop.Describable")
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone()))
+        .collect();
+    assert_eq!(
+        describable,
+        vec![
+            ("Describable", SymbolKind::Trait),
+            ("describe/1", SymbolKind::Function),
+        ]
+    );
+}
+
+#[test]
+fn elixir_driver_groups_umbrella_apps_and_links_modules() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("mix.exs", "defmodule Platform.MixProject do\nend\n"),
+            (
+                "apps/accounts/mix.exs",
+                "defmodule Accounts.MixProject do\nend\n",
+            ),
+            (
+                "apps/accounts/lib/accounts/user.ex",
+                r#"defmodule Accounts.User do
+  use Ecto.Schema
+  alias Accounts.{Repo, Notifier}
+
+  def register(attrs) do
+    Repo.insert(attrs)
+    Notifier.welcome(attrs)
+  end
+end
+"#,
+            ),
+            (
+                "apps/accounts/lib/accounts/repo.ex",
+                "defmodule Accounts.Repo do\n  @behaviour Accounts.Store\nend\n",
+            ),
+            (
+                "apps/accounts/lib/accounts/store.ex",
+                "defmodule Accounts.Store do\n  @callback insert(map()) :: :ok\nend\n",
+            ),
+            (
+                "apps/accounts/lib/accounts/notifier.ex",
+                "defmodule Accounts.Notifier do\n  require Logger\n  import :crypto, only: [hash: 2]\nend\n",
+            ),
+            ("apps/web/mix.exs", "defmodule Web.MixProject do\nend\n"),
+            (
+                "apps/web/lib/web/user_view.ex",
+                r#"defmodule Web.UserView do
+  alias Accounts.User, as: Account
+  import Web.Helpers
+
+  defimpl Web.Renderable, for: Account do
+    def render(user), do: user
+  end
+
+  defimpl Web.Renderable, for: Integer do
+    def render(n), do: n
+  end
+end
+"#,
+            ),
+            (
+                "apps/web/lib/web/renderable.ex",
+                "defprotocol Web.Renderable do\n  def render(value)\nend\n",
+            ),
+            (
+                "apps/web/lib/web/helpers.ex",
+                "defmodule Web.Helpers do\nend\n",
+            ),
+        ],
+    );
+
+    let model = ElixirDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model
+        .modules
+        .iter()
+        .map(|m| (m.group.as_deref().unwrap_or_default(), m.id.0.as_str()))
+        .collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            ("accounts", "Accounts.Notifier"),
+            ("accounts", "Accounts.Repo"),
+            ("accounts", "Accounts.Store"),
+            ("accounts", "Accounts.User"),
+            ("web", "Web.Helpers"),
+            ("web", "Web.Renderable"),
+            ("web", "Web.Renderable.Accounts.User"),
+            ("web", "Web.Renderable.Integer"),
+            ("web", "Web.UserView"),
+        ]
+    );
+
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("Accounts.User".to_string(), "Accounts.Notifier".to_string()),
+            ("Accounts.User".to_string(), "Accounts.Repo".to_string()),
+            ("Web.UserView".to_string(), "Accounts.User".to_string()),
+            ("Web.UserView".to_string(), "Web.Helpers".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            ("Accounts.Repo".to_string(), "Accounts.Store".to_string()),
+            ("Accounts.User".to_string(), "Web.Renderable".to_string()),
+            (
+                "Web.Renderable.Integer".to_string(),
+                "Web.Renderable".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        module(&model, "Accounts.Notifier").external_imports,
+        vec![
+            ExternalImport {
+                path: "Logger".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: ":crypto".to_string(),
+                kind: ExternalKind::Std,
+            },
+        ]
+    );
+    assert_eq!(
+        module(&model, "Accounts.User").external_imports,
+        vec![ExternalImport {
+            path: "Ecto.Schema".to_string(),
+            kind: ExternalKind::ThirdParty,
+        }]
+    );
+}
+
+#[test]
+fn detects_scala_by_build_sbt() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("build.sbt"), "").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["scala"]);
+}
+
+#[test]
+fn scala_driver_extracts_declarations_with_qualified_access() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "src/main/scala/com/acme/billing/Invoice.scala",
+            r#"package com.acme
+package billing
+
+import scala.collection.mutable
+
+/** A bill sent to a customer. */
+@SerialVersionUID(1L)
+final case class Invoice(id: Long, lines: List[Line]) {
+  def total: BigDecimal = lines.map(_.amount).sum
+
+  private[billing] def audit(): Unit = {
+    def helper(x: Int) = x
+    helper(1)
+  }
+
+  private def secret = "}"
+}
+
+object Invoice {
+  val Empty = Invoice(0, Nil)
+
+  def apply(id: Long): Invoice =
+    Invoice(id, Nil)
+
+  protected[this] def cache = mutable.Map.empty[Long, Invoice]
+}
+
+sealed trait Status
+case object Open extends Status
+
+protected[acme] class Ledger
+
+type Lines = List[Line]
+
+enum Color:
+  case Red, Green
+
+  def hex: String =
+    "white"
+
+class Line(val amount: BigDecimal):
+  def +(that: Line): Line = Line(amount + that.amount)
+end Line
+
+def parse(s: String): Option[Invoice] = None
+"#,
+        )],
+    );
+
+    let model = ScalaDriver.parse(&paths).unwrap();
+    let invoice = module(&model, "com.acme.billing.Invoice");
+    assert_eq!(invoice.name, "Invoice");
+    let summary: Vec<_> = invoice
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Invoice", SymbolKind::Struct, Visibility::Public),
+            ("Invoice.total", SymbolKind::Function, Visibility::Public),
+            ("Invoice.audit", SymbolKind::Function, Visibility::Internal),
+            ("Invoice.secret", SymbolKind::Function, Visibility::Private),
+            ("Invoice", SymbolKind::Struct, Visibility::Public),
+            ("Invoice.apply", SymbolKind::Function, Visibility::Public),
+            ("Invoice.cache", SymbolKind::Function, Visibility::Protected),
+            ("Status", SymbolKind::Trait, Visibility::Public),
+            ("Open", SymbolKind::Struct, Visibility::Public),
+            ("Ledger", SymbolKind::Struct, Visibility::Internal),
+            ("Lines", SymbolKind::TypeAlias, Visibility::Public),
+            ("Color", SymbolKind::Enum, Visibility::Public),
+            ("Color.hex", SymbolKind::Function, Visibility::Public),
+            ("Line", SymbolKind::Struct, Visibility::Public),
+            ("Line.+", SymbolKind::Function, Visibility::Public),
+            ("parse", SymbolKind::Function, Visibility::Public),
+        ]
+    );
+    let class = &invoice.symbols[0];
+    assert_eq!(class.line, 7);
+    assert_eq!(class.doc.as_deref(), Some("A bill sent to a customer."));
+    assert_eq!(
+        class.signature.as_deref(),
+        Some("@SerialVersionUID(1L) final case class Invoice(id: Long, lines: List[Line])")
+    );
+    assert_eq!(
+        invoice.symbols[1].signature.as_deref(),
+        Some("def total: BigDecimal")
+    );
+    assert_eq!(
+        invoice.external_imports,
+        vec![ExternalImport {
+            path: "scala.collection.mutable".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn scala_driver_resolves_imports_and_mixin_chains() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "src/main/java/com/acme/core/Entity.java",
+                "package com.acme.core;\npublic abstract class Entity {}\n",
+            ),
+            (
+                "src/main/scala/com/acme/core/Named.scala",
+                "package com.acme.core\n\ntrait Named\n\ntrait Versioned extends Named\n",
+            ),
+            (
+                "src/main/scala/com/acme/core/Audit.scala",
+                "package com.acme.core\n\ntrait Auditable\n",
+            ),
+            (
+                "src/main/scala/com/acme/model/User.scala",
+                r#"package com.acme.model
+
+import com.acme.core.{Entity, Named => HasName, Audit => _}
+import com.acme.core.Auditable as Tracked
+import cats.effect._
+
+class User(name: String) extends Entity with HasName with Tracked
+
+object User extends Tracked
+"#,
+            ),
+            (
+                "src/main/scala/com/acme/api/Views.scala",
+                r#"package com.acme.api
+
+import com.acme.model._
+
+class Admin extends User("root"), com.acme.core.Versioned
+"#,
+            ),
+            (
+                "src/main/java/com/acme/api/UserView.java",
+                r#"package com.acme.api;
+
+import com.acme.model.User;
+
+public class UserView extends User {}
+"#,
+            ),
+        ],
+    );
+
+    let models = vec![
+        JavaDriver.parse(&paths).unwrap(),
+        ScalaDriver.parse(&paths).unwrap(),
+    ];
+    let model = merge_all(models);
+    assert_eq!(model.language, "polyglot");
+    assert_eq!(model.modules.len(), 6);
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            (
+                "com.acme.api.UserView".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.api.Views".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Audit".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![
+            (
+                "com.acme.api.UserView".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.api.Views".to_string(),
+                "com.acme.model.User".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Entity".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            (
+                "com.acme.api.Views".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Audit".to_string()
+            ),
+            (
+                "com.acme.model.User".to_string(),
+                "com.acme.core.Named".to_string()
+            ),
+        ]
+    );
+    let user = module(&model, "com.acme.model.User");
+    assert_eq!(
+        user.external_imports,
+        vec![ExternalImport {
+            path: "cats.effect".to_string(),
+            kind: ExternalKind::ThirdParty,
+        }]
+    );
+}
+
+#[test]
+fn detects_zig_by_build_zig() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("build.zig"), "").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["zig"]);
+}
+
+#[test]
+fn zig_driver_extracts_containers_and_pub_declarations() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "src/geometry.zig",
+            r#"//! Shapes and the maths on them.
+
+const std = @import("std");
+pub const Allocator = std.mem.Allocator;
+
+pub const max_points = 64;
+
+/// A point in the plane.
+pub const Point = extern struct {
+    x: f32,
+    y: f32,
+
+    const Self = @This();
+
+    /// Distance to the origin.
+    pub fn length(self: Self) f32 {
+        const help = struct {
+            fn square(v: f32) f32 {
+                return v * v;
+            }
+        };
+        return @sqrt(help.square(self.x) + help.square(self.y));
+    }
+
+    fn scale(self: *Self, by: f32) void {
+        self.x *= by;
+    }
+};
+
+pub const Shape = union(enum) {
+    circle: f32,
+    square: f32,
+
+    pub const Kind = enum(u8) { round, angular };
+};
+
+pub const ParseError = error{ Empty, Overflow };
+
+const banner =
+    \\ pub fn fake() void {
+    \\ }
+;
+
+pub extern "c" fn printf(format: [*:0]const u8, ...) c_int;
+
+fn helper() void {}
+
+test "length" {
+    const p = Point{ .x = 3, .y = 4 };
+    try std.testing.expectEqual(@as(f32, 5), p.length());
+}
+"#,
+        )],
+    );
+
+    let model = ZigDriver.parse(&paths).unwrap();
+    let geometry = module(&model, "geometry");
+    assert_eq!(geometry.name, "geometry");
+    assert_eq!(
+        geometry.doc.as_deref(),
+        Some("Shapes and the maths on them.")
+    );
+    let summary: Vec<_> = geometry
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Allocator", SymbolKind::TypeAlias, Visibility::Public),
+            ("max_points", SymbolKind::Constant, Visibility::Public),
+            ("Point", SymbolKind::Struct, Visibility::Public),
+            ("Point.length", SymbolKind::Function, Visibility::Public),
+            ("Point.scale", SymbolKind::Function, Visibility::Private),
+            ("Shape", SymbolKind::Enum, Visibility::Public),
+            ("Shape.Kind", SymbolKind::Enum, Visibility::Public),
+            ("ParseError", SymbolKind::Enum, Visibility::Public),
+            ("banner", SymbolKind::Constant, Visibility::Private),
+            ("printf", SymbolKind::Function, Visibility::Public),
+            ("helper", SymbolKind::Function, Visibility::Private),
+        ]
+    );
+    let point = &geometry.symbols[2];
+    assert_eq!(point.line, 9);
+    assert_eq!(point.doc.as_deref(), Some("A point in the plane."));
+    assert_eq!(
+        point.signature.as_deref(),
+        Some("pub const Point = extern struct")
+    );
+    assert_eq!(
+        geometry.symbols[3].signature.as_deref(),
+        Some("pub fn length(self: Self) f32")
+    );
+    assert_eq!(
+        geometry.symbols[9].signature.as_deref(),
+        Some("pub extern \"c\" fn printf(format: [*:0]const u8, ...) c_int")
+    );
+    assert_eq!(
+        geometry.external_imports,
+        vec![ExternalImport {
+            path: "std".to_string(),
+            kind: ExternalKind::Std,
+        }]
+    );
+}
+
+#[test]
+fn zig_driver_resolves_relative_imports() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "build.zig",
+                "const std = @import(\"std\");\npub fn build(b: *std.Build) void {}\n",
+            ),
+            (
+                "src/main.zig",
+                r#"const std = @import("std");
+const builtin = @import("builtin");
+const clap = @import("clap");
+const server = @import("net/server.zig");
+const missing = @import("gen/missing.zig");
+
+pub fn main() !void {
+    try server.run(@import("config.zig").port);
+}
+"#,
+            ),
+            ("src/config.zig", "pub const port: u16 = 8080;\n"),
+            (
+                "src/net/server.zig",
+                "const config = @import(\"../config.zig\");\nconst std = @import(\"std\");\n",
+            ),
+        ],
+    );
+
+    let model = ZigDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["config", "main", "net/server"]);
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("main".to_string(), "config".to_string()),
+            ("main".to_string(), "net/server".to_string()),
+            ("net/server".to_string(), "config".to_string()),
+        ]
+    );
+    assert_eq!(
+        module(&model, "main").external_imports,
+        vec![
+            ExternalImport {
+                path: "std".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "builtin".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "clap".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+    assert_eq!(
+        module(&model, "config").symbols[0].kind,
+        SymbolKind::Constant
+    );
+}
+
+#[test]
+fn detects_protobuf_by_buf_yaml_or_proto_files() {
+    let buf = TempDir::new().unwrap();
+    std::fs::write(buf.path().join("buf.yaml"), "version: v2\n").unwrap();
+    let nested = TempDir::new().unwrap();
+    std::fs::create_dir(nested.path().join("proto")).unwrap();
+    std::fs::write(nested.path().join("proto/api.proto"), "").unwrap();
+
+    for dir in [&buf, &nested] {
+        let active: Vec<_> = DriverRegistry::default()
+            .detect_all(dir.path())
+            .into_iter()
+            .map(|d| d.name())
+            .collect();
+        assert_eq!(active, vec!["protobuf"]);
+    }
+}
+
+#[test]
+fn protobuf_driver_models_packages_messages_and_services() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "proto/acme/billing/v1/invoice.proto",
+                r#"syntax = "proto3";
+
+package acme.billing.v1;
+
+option go_package = "acme/billing/v1;billingv1";
+
+// An invoice sent to a customer.
+message Invoice {
+  string id = 1; // trailing comment, not a doc
+  repeated Line lines = 2;
+
+  message Line {
+    int64 amount = 1;
+  }
+
+  enum Status {
+    STATUS_UNSPECIFIED = 0;
+    STATUS_PAID = 1;
+  }
+}
+"#,
+            ),
+            (
+                "proto/acme/billing/v1/service.proto",
+                r#"syntax = "proto3";
+
+package acme.billing.v1;
+
+/* Issues and lists invoices. */
+service BillingService {
+  // Creates an invoice.
+  rpc CreateInvoice(CreateInvoiceRequest) returns (Invoice);
+  rpc WatchInvoices(WatchRequest) returns (stream Invoice) {
+    option idempotency_level = NO_SIDE_EFFECTS;
+  }
+}
+
+message CreateInvoiceRequest {}
+message WatchRequest {}
+"#,
+            ),
+        ],
+    );
+
+    let model = ProtobufDriver.parse(&paths).unwrap();
+    assert_eq!(model.modules.len(), 1);
+    let billing = module(&model, "acme.billing.v1");
+    assert_eq!(billing.name, "billing");
+    assert!(billing.path.ends_with("proto/acme/billing/v1"));
+    let summary: Vec<_> = billing
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Invoice", SymbolKind::Struct),
+            ("Invoice.Line", SymbolKind::Struct),
+            ("Invoice.Status", SymbolKind::Enum),
+            ("BillingService", SymbolKind::Trait),
+            ("BillingService.CreateInvoice", SymbolKind::Function),
+            ("BillingService.WatchInvoices", SymbolKind::Function),
+            ("CreateInvoiceRequest", SymbolKind::Struct),
+            ("WatchRequest", SymbolKind::Struct),
+        ]
+    );
+    let invoice = &billing.symbols[0];
+    assert_eq!(invoice.line, 8);
+    assert_eq!(
+        invoice.doc.as_deref(),
+        Some("An invoice sent to a customer.")
+    );
+    assert_eq!(billing.symbols[1].doc, None);
+    assert_eq!(
+        billing.symbols[3].doc.as_deref(),
+        Some("Issues and lists invoices.")
+    );
+    let create = &billing.symbols[4];
+    assert_eq!(create.doc.as_deref(), Some("Creates an invoice."));
+    assert_eq!(
+        create.signature.as_deref(),
+        Some("rpc CreateInvoice(CreateInvoiceRequest) returns (Invoice)")
+    );
+    assert_eq!(
+        billing.symbols[5].signature.as_deref(),
+        Some("rpc WatchInvoices(WatchRequest) returns (stream Invoice)")
+    );
+}
+
+#[test]
+fn protobuf_driver_links_imports_and_field_types() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "proto/acme/common/v1/money.proto",
+                "syntax = \"proto3\";\npackage acme.common.v1;\n\nmessage Money {\n  string currency = 1;\n  int64 units = 2;\n}\n\nenum Region { REGION_UNSPECIFIED = 0; }\n",
+            ),
+            (
+                "proto/acme/orders/v1/order.proto",
+                r#"syntax = "proto3";
+
+package acme.orders.v1;
+
+import "acme/common/v1/money.proto";
+import "google/protobuf/timestamp.proto";
+import public "validate/validate.proto";
+
+message Order {
+  acme.common.v1.Money total = 1;
+  google.protobuf.Timestamp placed_at = 2;
+  map<string, .acme.common.v1.Region> regions = 3;
+  oneof payment {
+    Card card = 4;
+  }
+  message Card {
+    string number = 1;
+  }
+}
+"#,
+            ),
+            (
+                "proto/acme/shipping/v1/shipping.proto",
+                r#"syntax = "proto3";
+
+package acme.shipping.v1;
+
+import "acme/orders/v1/order.proto";
+
+service Shipping {
+  rpc Ship(acme.orders.v1.Order) returns (acme.orders.v1.Order.Card);
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = ProtobufDriver.parse(&paths).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            pair("acme.orders.v1", "acme.common.v1"),
+            pair("acme.shipping.v1", "acme.orders.v1"),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![
+            pair("acme.orders.v1", "acme.common.v1"),
+            pair("acme.shipping.v1", "acme.orders.v1"),
+        ]
+    );
+    assert_eq!(
+        module(&model, "acme.orders.v1").external_imports,
+        vec![
+            ExternalImport {
+                path: "google/protobuf/timestamp.proto".to_string(),
+                kind: ExternalKind::Std,
+            },
+            ExternalImport {
+                path: "validate/validate.proto".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_openapi_by_spec_file_name() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("openapi.yaml"), "openapi: 3.1.0\n").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["openapi"]);
+}
+
+#[test]
+fn openapi_driver_groups_operations_by_tag_and_path_prefix() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "api/openapi.yaml",
+                r##"openapi: 3.1.0
+info:
+  title: Billing API
+tags:
+  - name: invoices
+    description: Invoice lifecycle.
+paths:
+  /api/v1/invoices:
+    get:
+      operationId: listInvoices
+      tags: [invoices]
+      summary: List invoices.
+      responses:
+        "200":
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Invoice"
+  /api/v1/health:
+    get:
+      responses:
+        "200":
+          description: OK
+components:
+  schemas:
+    Invoice:
+      type: object
+      description: A bill.
+      properties:
+        status:
+          $ref: "#/components/schemas/Status"
+    Status:
+      type: string
+      enum: [open, paid]
+"##,
+            ),
+            ("api/notes.yaml", "title: not a spec\n"),
+        ],
+    );
+
+    let model = OpenApiDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec!["openapi/invoices", "openapi/health", "openapi/schemas"]
+    );
+
+    let invoices = module(&model, "openapi/invoices");
+    assert_eq!(invoices.doc.as_deref(), Some("Invoice lifecycle."));
+    let list = &invoices.symbols[0];
+    assert_eq!(list.name, "listInvoices");
+    assert_eq!(list.kind, SymbolKind::Function);
+    assert_eq!(list.line, 9);
+    assert_eq!(list.doc.as_deref(), Some("List invoices."));
+    assert_eq!(list.signature.as_deref(), Some("GET /api/v1/invoices"));
+    assert_eq!(
+        module(&model, "openapi/health").symbols[0].name,
+        "GET /api/v1/health"
+    );
+
+    let schemas = module(&model, "openapi/schemas");
+    assert_eq!(schemas.doc.as_deref(), Some("Billing API"));
+    let summary: Vec<_> = schemas
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.line))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Invoice", SymbolKind::Struct, 28),
+            ("Status", SymbolKind::Enum, 34),
+        ]
+    );
+    assert_eq!(
+        schemas.symbols[0].signature.as_deref(),
+        Some("Invoice: object")
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![(
+            "openapi/invoices".to_string(),
+            "openapi/schemas".to_string()
+        )]
+    );
+}
+
+#[test]
+fn openapi_driver_follows_refs_across_components_and_specs() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "common/openapi.yaml",
+                "openapi: 3.0.3\ninfo: {title: Common}\npaths: {}\ncomponents:\n  schemas:\n    Money:\n      type: object\n",
+            ),
+            (
+                "orders/swagger.json",
+                r##"{
+  "swagger": "2.0",
+  "paths": {
+    "/orders/{id}": {
+      "parameters": [{"$ref": "#/parameters/OrderId"}],
+      "get": {
+        "operationId": "getOrder",
+        "responses": {"200": {"$ref": "#/responses/OrderFound"}}
+      }
+    }
+  },
+  "parameters": {
+    "OrderId": {"name": "id", "in": "path", "type": "string"}
+  },
+  "responses": {
+    "OrderFound": {"schema": {"$ref": "#/definitions/Order"}}
+  },
+  "definitions": {
+    "Order": {
+      "type": "object",
+      "properties": {
+        "total": {"$ref": "../common/openapi.yaml#/components/schemas/Money"}
+      }
+    }
+  }
+}
+"##,
+            ),
+        ],
+    );
+
+    let model = OpenApiDriver.parse(&paths).unwrap();
+    let order = &module(&model, "orders/swagger/schemas").symbols[0];
+    assert_eq!(order.name, "Order");
+    assert_eq!(order.line, 19);
+    assert_eq!(module(&model, "orders/swagger/orders").symbols[0].line, 6);
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![
+            pair("orders/swagger/orders", "orders/swagger/schemas"),
+            pair("orders/swagger/schemas", "common/openapi/schemas"),
+        ]
+    );
+}
+
+#[test]
+fn detects_sql_in_migration_directories() {
+    let dir = TempDir::new().unwrap();
+    write_tree(
+        dir.path(),
+        &[("migrations/2024-01-01-000000_init/up.sql", "SELECT 1;\n")],
+    );
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["sql"]);
+}
+
+#[test]
+fn sql_driver_replays_migrations_into_final_schema() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "migrations/V1__init.sql",
+                "-- Registered accounts.\n\
+                 CREATE TABLE Users (\n\
+                 \x20   id SERIAL PRIMARY KEY,\n\
+                 \x20   mail TEXT\n\
+                 );\n\
+                 CREATE TABLE scratch (id INT);\n",
+            ),
+            (
+                "migrations/V2__email.sql",
+                "ALTER TABLE users RENAME COLUMN mail TO email;\n\
+                 ALTER TABLE users ALTER COLUMN email SET NOT NULL;\n\
+                 DROP TABLE scratch;\n",
+            ),
+            (
+                "migrations/V10__audit.sql",
+                "ALTER TABLE users ADD COLUMN created_at TIMESTAMP DEFAULT now();\n\
+                 COMMENT ON COLUMN users.email IS 'Login address.';\n\
+                 CREATE FUNCTION touch() RETURNS trigger AS $$ BEGIN RETURN NEW; END; $$ LANGUAGE plpgsql;\n",
+            ),
+            ("migrations/U2__email.sql", "DROP TABLE users;\n"),
+        ],
+    );
+
+    let model = SqlDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["users"]);
+
+    let users = module(&model, "users");
+    assert_eq!(users.name, "Users");
+    assert_eq!(users.doc.as_deref(), Some("Registered accounts."));
+    let table = &users.symbols[0];
+    assert_eq!(table.kind, SymbolKind::Struct);
+    assert_eq!(table.line, 2);
+    assert_eq!(table.signature.as_deref(), Some("CREATE TABLE Users"));
+    assert_eq!(
+        table.members,
+        vec![
+            Member {
+                name: "id".to_string(),
+                ty: Some("SERIAL".to_string()),
+                modifiers: vec!["PRIMARY KEY".to_string()],
+                doc: None,
+            },
+            Member {
+                name: "email".to_string(),
+                ty: Some("TEXT".to_string()),
+                modifiers: vec!["NOT NULL".to_string()],
+                doc: Some("Login address.".to_string()),
+            },
+            Member {
+                name: "created_at".to_string(),
+                ty: Some("TIMESTAMP".to_string()),
+                modifiers: vec!["DEFAULT now()".to_string()],
+                doc: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn sql_driver_turns_foreign_keys_into_uses_edges() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "db/migrations/001_accounts.sql",
+                "-- migrate:up\n\
+                 CREATE TABLE auth.accounts (id INT PRIMARY KEY);\n\
+                 CREATE TABLE orders (\n\
+                 \x20   id INT PRIMARY KEY,\n\
+                 \x20   account_id INT REFERENCES accounts (id),\n\
+                 \x20   coupon_id INT\n\
+                 );\n\
+                 -- migrate:down\n\
+                 DROP TABLE orders;\n",
+            ),
+            (
+                "db/migrations/002_coupons.sql",
+                "CREATE TABLE coupons (id INT PRIMARY KEY);\n\
+                 ALTER TABLE orders ADD CONSTRAINT fk_coupon\n\
+                 \x20   FOREIGN KEY (coupon_id) REFERENCES coupons (id) ON DELETE CASCADE;\n\
+                 CREATE TABLE lines (order_id INT, FOREIGN KEY (order_id) REFERENCES orders (id));\n\
+                 ALTER TABLE lines DROP COLUMN order_id;\n",
+            ),
+            (
+                "db/migrations/002_coupons.down.sql",
+                "DROP TABLE coupons;\n",
+            ),
+        ],
+    );
+
+    let model = SqlDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["auth.accounts", "orders", "coupons", "lines"]);
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![
+            ("orders".to_string(), "auth.accounts".to_string()),
+            ("orders".to_string(), "coupons".to_string()),
+        ]
+    );
+
+    // No doc from the dbmate section marker.
+    assert_eq!(module(&model, "auth.accounts").doc, None);
+    let coupon = &module(&model, "orders").symbols[0].members[2];
+    assert_eq!(
+        coupon.modifiers,
+        vec!["REFERENCES coupons (id) ON DELETE CASCADE".to_string()]
+    );
+}
+
+#[test]
+fn detects_terraform_in_infra_directory() {
+    let dir = TempDir::new().unwrap();
+    write_tree(dir.path(), &[("infra/main.tf", "terraform {}\n")]);
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["terraform"]);
+}
+
+#[test]
+fn terraform_driver_models_blocks_as_symbols() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "infra/main.tf",
+                r#"# Access logs.
+resource "aws_s3_bucket" "logs" {
+  bucket = "${var.prefix}-logs"
+}
+
+data "aws_iam_policy_document" "read" {
+  statement {
+    resources = [aws_s3_bucket.logs.arn]
+  }
+}
+"#,
+            ),
+            (
+                "infra/variables.tf",
+                r#"variable "prefix" {
+  type        = string
+  description = "Name prefix."
+}
+
+output "bucket_arn" {
+  value = aws_s3_bucket.logs.arn
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = TerraformDriver.parse(&paths).unwrap();
+    assert_eq!(model.modules.len(), 1);
+    let infra = module(&model, "infra");
+    let summary: Vec<_> = infra
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.visibility, s.line))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "aws_s3_bucket.logs",
+                SymbolKind::Struct,
+                Visibility::Private,
+                2
+            ),
+            (
+                "data.aws_iam_policy_document.read",
+                SymbolKind::Struct,
+                Visibility::Private,
+                6
+            ),
+            ("var.prefix", SymbolKind::Constant, Visibility::Public, 1),
+            (
+                "output.bucket_arn",
+                SymbolKind::Constant,
+                Visibility::Public,
+                6
+            ),
+        ]
+    );
+    assert_eq!(infra.symbols[0].doc.as_deref(), Some("Access logs."));
+    assert_eq!(
+        infra.symbols[0].signature.as_deref(),
+        Some(r#"resource "aws_s3_bucket" "logs""#)
+    );
+    assert_eq!(infra.symbols[2].doc.as_deref(), Some("Name prefix."));
+    assert_eq!(infra.symbols[3].doc, None);
+    assert!(model.edges.is_empty());
+}
+
+#[test]
+fn terraform_driver_links_module_calls_and_references() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "terraform/main.tf",
+                r#"terraform {
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+  }
+}
+
+module "network" {
+  source = "./modules/network"
+}
+
+module "app" {
+  source    = "./modules/app"
+  subnet_id = module.network.subnet_id
+}
+
+module "dns" {
+  source = "terraform-aws-modules/route53/aws"
+}
+"#,
+            ),
+            (
+                "terraform/modules/network/main.tf",
+                "resource \"aws_subnet\" \"main\" {}\n",
+            ),
+            (
+                "terraform/modules/app/main.tf",
+                "variable \"subnet_id\" {}\n",
+            ),
+        ],
+    );
+
+    let model = TerraformDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["terraform", "modules/app", "modules/network"]);
+    assert_eq!(module(&model, "modules/network").name, "network");
+    assert_eq!(
+        module(&model, "terraform").symbols[0].kind,
+        SymbolKind::Module
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![
+            ("terraform".to_string(), "modules/app".to_string()),
+            ("terraform".to_string(), "modules/network".to_string()),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![("terraform".to_string(), "modules/network".to_string())]
+    );
+    assert_eq!(
+        module(&model, "terraform").external_imports,
+        vec![
+            ExternalImport {
+                path: "terraform-aws-modules/route53/aws".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+            ExternalImport {
+                path: "hashicorp/aws".to_string(),
+                kind: ExternalKind::ThirdParty,
+            },
+        ]
+    );
+}
+
+#[test]
+fn detects_docker_by_compose_file() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("compose.yaml"), "services: {}\n").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["docker"]);
+}
+
+#[test]
+fn docker_driver_models_compose_services_as_containers() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "docker-compose.yml",
+                r#"services:
+  api:
+    build:
+      context: ./api
+      dockerfile: Dockerfile
+    ports:
+      - "8080:80"
+    depends_on:
+      db:
+        condition: service_healthy
+    networks: [backend]
+  db:
+    image: postgres:16
+    ports:
+      - target: 5432
+        published: 5433
+    networks: [backend]
+  web:
+    build: ./web
+    links:
+      - api:backend
+"#,
+            ),
+            (
+                "docker-compose.override.yml",
+                "services:\n  db:\n    networks: [admin]\n",
+            ),
+            (
+                "api/Dockerfile",
+                "FROM rust:1.80 AS build\n\
+                 EXPOSE 9000\n\
+                 FROM build AS test\n\
+                 FROM debian:bookworm-slim\n\
+                 COPY --from=build /app /app\n",
+            ),
+            ("api/src/main.rs", "fn main() {}\n"),
+            ("api/src/routes/mod.rs", "\n"),
+            (
+                "web/Dockerfile",
+                "# syntax=docker/dockerfile:1\n\
+                 FROM --platform=linux/amd64 node:20 \\\n\
+                 \x20   AS base\n\
+                 EXPOSE 3000 3001\n",
+            ),
+        ],
+    );
+
+    let model = DockerDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["api", "db", "web"]);
+
+    let root = dir.path();
+    assert_eq!(
+        module(&model, "api").container,
+        Some(Container {
+            image: Some("debian:bookworm-slim".to_string()),
+            ports: vec!["8080:80".to_string()],
+            networks: vec!["backend".to_string()],
+            build_context: Some(root.join("api")),
+            sources: vec![root.join("api/src"), root.join("api/src/routes")],
+        })
+    );
+    let db = module(&model, "db").container.clone().unwrap();
+    assert_eq!(db.image.as_deref(), Some("postgres:16"));
+    assert_eq!(db.ports, vec!["5433:5432"]);
+    assert_eq!(db.networks, vec!["backend", "admin"]);
+    assert_eq!(db.build_context, None);
+    let web = module(&model, "web").container.clone().unwrap();
+    assert_eq!(web.image.as_deref(), Some("node:20"));
+    assert_eq!(web.ports, vec!["3000", "3001"]);
+
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![
+            ("api".to_string(), "db".to_string()),
+            ("web".to_string(), "api".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn docker_driver_links_services_sharing_a_network() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "compose.yaml",
+                r#"services:
+  api:
+    image: api
+    depends_on: [cache]
+    networks: [backend]
+  cache:
+    image: redis
+    networks: [backend, jobs]
+  worker:
+    image: worker
+    networks:
+      jobs:
+        aliases: [w]
+  web:
+    image: web
+  proxy:
+    image: nginx
+"#,
+            ),
+            (
+                "tools/compose.yaml",
+                "services:\n  admin:\n    image: admin\n    networks: [backend]\n",
+            ),
+        ],
+    );
+
+    let model = DockerDriver.parse(&paths).unwrap();
+    // `api` keeps its `depends_on` direction; `web` and `proxy` only share
+    // the default network; `tools/admin` belongs to another project.
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![
+            ("api".to_string(), "cache".to_string()),
+            ("cache".to_string(), "worker".to_string()),
+            ("worker".to_string(), "cache".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn docker_driver_keeps_standalone_dockerfiles() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "deploy/compose.yaml",
+                "services:\n  cache:\n    image: redis:7\n",
+            ),
+            (
+                "services/worker/Dockerfile.jobs",
+                "FROM python:3.12\nEXPOSE 8000\n",
+            ),
+            ("services/worker/app.py", "print()\n"),
+        ],
+    );
+
+    let model = DockerDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["deploy/cache", "services/worker/jobs"]);
+    let jobs = module(&model, "services/worker/jobs");
+    assert_eq!(jobs.name, "jobs");
+    assert_eq!(
+        jobs.container,
+        Some(Container {
+            image: Some("python:3.12".to_string()),
+            ports: vec!["8000".to_string()],
+            networks: Vec::new(),
+            build_context: Some(dir.path().join("services/worker")),
+            sources: vec![dir.path().join("services/worker")],
+        })
+    );
+    assert!(module(&model, "deploy/cache").symbols.is_empty());
+}
+
+#[test]
+fn detects_graphql_by_schema_directory() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("schema")).unwrap();
+    std::fs::write(dir.path().join("schema/schema.graphql"), "type Query\n").unwrap();
+
+    let active: Vec<_> = DriverRegistry::default()
+        .detect_all(dir.path())
+        .into_iter()
+        .map(|d| d.name())
+        .collect();
+    assert_eq!(active, vec!["graphql"]);
+}
+
+#[test]
+fn graphql_driver_models_types_and_root_fields() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "schema.graphql",
+                r#"schema {
+  query: RootQuery
+}
+
+"""Anything with a global id."""
+interface Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  "Display name."
+  name: String @deprecated(reason: "use handle")
+  role: Role
+}
+
+enum Role {
+  ADMIN
+  MEMBER
+}
+
+union SearchResult = User
+
+input UserFilter {
+  role: Role = MEMBER
+}
+
+scalar DateTime
+
+type RootQuery {
+  "Looks a user up."
+  user(id: ID!): User
+  search(filter: UserFilter, first: Int = 10): [SearchResult!]!
+}
+"#,
+            ),
+            ("queries/me.graphql", "query Me { user(id: 1) { name } }\n"),
+        ],
+    );
+
+    let model = GraphqlDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["schema"]);
+
+    let schema = module(&model, "schema");
+    let symbols: Vec<_> = schema
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.kind.clone(), s.line))
+        .collect();
+    assert_eq!(
+        symbols,
+        vec![
+            ("Node", SymbolKind::Trait, 6),
+            ("User", SymbolKind::Struct, 10),
+            ("Role", SymbolKind::Enum, 17),
+            ("SearchResult", SymbolKind::Enum, 22),
+            ("UserFilter", SymbolKind::Struct, 24),
+            ("DateTime", SymbolKind::TypeAlias, 28),
+            ("RootQuery.user", SymbolKind::Function, 31),
+            ("RootQuery.search", SymbolKind::Function, 33),
+        ]
+    );
+    assert!(
+        schema
+            .symbols
+            .iter()
+            .all(|s| s.visibility == Visibility::Public)
+    );
+    assert_eq!(
+        schema.symbols[0].doc.as_deref(),
+        Some("Anything with a global id.")
+    );
+
+    let user = &schema.symbols[1];
+    assert_eq!(user.signature.as_deref(), Some("type User implements Node"));
+    assert_eq!(
+        user.members[1],
+        Member {
+            name: "name".to_string(),
+            ty: Some("String".to_string()),
+            modifiers: vec![r#"@deprecated(reason: "use handle")"#.to_string()],
+            doc: Some("Display name.".to_string()),
+        }
+    );
+    let filter = &schema.symbols[4];
+    assert_eq!(filter.members[0].modifiers, vec!["= MEMBER"]);
+    assert_eq!(
+        schema.symbols[3].signature.as_deref(),
+        Some("union SearchResult = User")
+    );
+
+    let search = &schema.symbols[7];
+    assert_eq!(
+        search.signature.as_deref(),
+        Some("search(filter: UserFilter, first: Int = 10): [SearchResult!]!")
+    );
+    assert_eq!(schema.symbols[6].doc.as_deref(), Some("Looks a user up."));
+}
+
+#[test]
+fn graphql_driver_keeps_federated_subgraphs_apart() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "subgraphs/accounts/schema.graphql",
+                r#"interface Node {
+  id: ID!
+}
+
+type User implements Node @key(fields: "id") {
+  id: ID!
+  email: String
+}
+
+type Query {
+  me: User
+}
+"#,
+            ),
+            (
+                "subgraphs/reviews/schema.graphql",
+                r#"type Review implements Node {
+  id: ID!
+  author: User
+}
+
+extend type User @key(fields: "id") {
+  reviews: [Review!]!
+}
+
+type Query {
+  reviews(first: Int): [Review]
+}
+"#,
+            ),
+        ],
+    );
+
+    let model = GraphqlDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["accounts/schema", "reviews/schema"]);
+    let reviews = module(&model, "reviews/schema");
+    assert_eq!(reviews.name, "reviews");
+
+    let names: Vec<_> = reviews.symbols.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Review", "User", "Query.reviews"]);
+    let extension = &reviews.symbols[1];
+    assert_eq!(extension.signature.as_deref(), Some("extend type User"));
+    assert_eq!(extension.members[0].name, "reviews");
+
+    let link = |kind| edges(&model, kind);
+    assert_eq!(
+        link(EdgeKind::Implements),
+        vec![("reviews/schema".to_string(), "accounts/schema".to_string())]
+    );
+    assert_eq!(
+        link(EdgeKind::Uses),
+        vec![("reviews/schema".to_string(), "accounts/schema".to_string())]
+    );
+    assert_eq!(
+        link(EdgeKind::Extends),
+        vec![("reviews/schema".to_string(), "accounts/schema".to_string())]
+    );
+}
+
+#[test]
+fn drivers_claim_files_by_extension_name_and_shebang() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("bin/manage", "#!/usr/bin/env python3.12\nprint('hi')\n"),
+            ("bin/deploy", "#!/usr/bin/ruby -w\nputs 'hi'\n"),
+            ("bin/tool", "# -*- mode: python -*-\nx = 1\n"),
+            ("bin/notes", "just text\n"),
+            ("src/App.kt", ""),
+            ("src/Main.java", ""),
+            ("Dockerfile.prod", "FROM scratch\n"),
+        ],
+    );
+    let path = |rel: &str| paths.iter().find(|p| p.ends_with(rel)).unwrap();
+
+    let python = PythonDriver.claims();
+    assert!(python.owns(path("bin/manage")));
+    assert!(python.owns(path("bin/tool")));
+    assert!(!python.owns(path("bin/deploy")));
+    assert!(!python.owns(path("bin/notes")));
+    assert!(RubyDriver.claims().owns(path("bin/deploy")));
+
+    let java = JavaDriver.claims();
+    assert!(java.owns(path("src/Main.java")));
+    assert!(!java.owns(path("src/App.kt")));
+    assert!(java.wants(path("src/App.kt")));
+
+    assert!(DockerDriver.claims().owns(path("Dockerfile.prod")));
+    assert!(DockerDriver.claims().wants(path("src/App.kt")));
+    assert!(!RustDriver.claims().wants(path("Dockerfile.prod")));
+}
+
+#[test]
+fn python_driver_parses_extensionless_scripts() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("app/__init__.py", ""),
+            (
+                "bin/manage",
+                "#!/usr/bin/env python3\nfrom app import run\n\ndef main():\n    run()\n",
+            ),
+            ("bin/deploy", "#!/bin/sh\necho hi\n"),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["app", "bin.manage"]);
+    assert!(
+        module(&model, "bin.manage")
+            .symbols
+            .iter()
+            .any(|s| s.name == "main")
+    );
+}
+
+/// A `[[plugins.drivers]]` entry running `script` with `sh`.
+fn sh_plugin(name: &str, script: &str) -> codetwin_legacy::config::PluginDriverSpec {
+    codetwin_legacy::config::PluginDriverSpec {
+        name: name.to_string(),
+        command: PathBuf::from("sh"),
+        args: vec![script.to_string()],
+        extensions: vec!["dsl".to_string()],
+        detect: vec!["dsl.yaml".to_string()],
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn external_driver_speaks_the_plugin_protocol() {
+    let dir = TempDir::new().unwrap();
+    let model_json = r#"{
+        "language": "",
+        "modules": [
+            {"id": "orders", "name": "orders", "path": "src/orders.dsl", "symbols": [], "doc": "Orders."},
+            {"id": "billing", "name": "billing", "path": "src/billing.dsl", "symbols": [], "doc": null}
+        ],
+        "edges": [{"from": "orders", "to": "billing", "kind": "uses"}]
+    }"#;
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("dsl.yaml", ""),
+            ("plugin.sh", "cat > request.json\ncat model.json\n"),
+            ("model.json", model_json),
+            ("src/orders.dsl", ""),
+            ("src/billing.dsl", ""),
+            ("src/notes.md", ""),
+        ],
+    );
+
+    let registry = DriverRegistry::with_plugins(&[sh_plugin("dsl", "plugin.sh")], dir.path());
+    let driver = registry.get("dsl").unwrap();
+    assert!(driver.detect(dir.path()));
+    assert!(!driver.detect(&dir.path().join("src")));
+
+    let model = driver.parse(&paths).unwrap();
+    assert_eq!(model.language, "dsl");
+    assert_eq!(module(&model, "orders").doc.as_deref(), Some("Orders."));
+    assert_eq!(
+        edges(&model, EdgeKind::Uses),
+        vec![("orders".to_string(), "billing".to_string())]
+    );
+
+    let request: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("request.json")).unwrap())
+            .unwrap();
+    assert_eq!(request["protocol"], 1);
+    assert_eq!(request["driver"], "dsl");
+    assert_eq!(
+        request["files"],
+        serde_json::json!([
+            dir.path().join("src/billing.dsl"),
+            dir.path().join("src/orders.dsl"),
+        ])
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn external_driver_rejects_failures_and_invalid_models() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("fails.sh", "echo 'grammar not found' >&2\nexit 3\n"),
+            ("garbage.sh", "echo 'not json'\n"),
+            (
+                "dangling.sh",
+                r#"echo '{"language": "dsl", "modules": [], "edges": [{"from": "a", "to": "b", "kind": "import"}]}'"#,
+            ),
+            ("src/a.dsl", ""),
+        ],
+    );
+    let parse = |script: &str| {
+        let driver = ExternalDriver::new(&sh_plugin("dsl", script), dir.path());
+        format!("{:#}", driver.parse(&paths).unwrap_err())
+    };
+
+    let err = parse("fails.sh");
+    assert!(err.contains("grammar not found"), "{err}");
+    let err = parse("garbage.sh");
+    assert!(err.contains("invalid code model"), "{err}");
+    let err = parse("dangling.sh");
+    assert!(err.contains("undeclared module `a`"), "{err}");
+}
+
+#[test]
+#[cfg(unix)]
+fn external_driver_rejects_unknown_kinds_and_visibilities() {
+    let dir = TempDir::new().unwrap();
+    let symbol = |kind: &str, visibility: &str| {
+        format!(
+            r#"{{"name": "Order", "kind": "{kind}", "visibility": "{visibility}", "line": 1, "doc": null, "signature": null}}"#
+        )
+    };
+    let model = |symbol: &str, edge: &str| {
+        format!(
+            r#"{{"language": "dsl", "modules": [{{"id": "a", "name": "a", "path": "src/a.dsl", "symbols": [{symbol}], "doc": null}}], "edges": [{{"from": "a", "to": "a", "kind": "{edge}"}}]}}"#
+        )
+    };
+    let valid = symbol("struct", "public");
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("symbol.json", &model(&symbol("widget", "public"), "uses")),
+            (
+                "visibility.json",
+                &model(&symbol("struct", "friend"), "uses"),
+            ),
+            ("edge.json", &model(&valid, "depends_on")),
+            ("src/a.dsl", ""),
+        ],
+    );
+    let parse = |json: &str| {
+        let spec = codetwin_legacy::config::PluginDriverSpec {
+            args: vec!["-c".to_string(), format!("cat {json}")],
+            ..sh_plugin("dsl", "")
+        };
+        let driver = ExternalDriver::new(&spec, dir.path());
+        format!("{:#}", driver.parse(&paths).unwrap_err())
+    };
+
+    for (json, bad) in [
+        ("symbol.json", "widget"),
+        ("visibility.json", "friend"),
+        ("edge.json", "depends_on"),
+    ] {
+        let err = parse(json);
+        assert!(err.contains("invalid code model"), "{err}");
+        assert!(err.contains(&format!("unknown variant `{bad}`")), "{err}");
+    }
+}
+
+#[test]
+fn plugin_names_cannot_shadow_builtin_drivers() {
+    let dir = TempDir::new().unwrap();
+    let registry = DriverRegistry::with_plugins(
+        &[
+            sh_plugin("rust", "plugin.sh"),
+            sh_plugin("dsl", "plugin.sh"),
+        ],
+        dir.path(),
+    );
+    let names = registry.names();
+    assert_eq!(names.iter().filter(|n| **n == "rust").count(), 1);
+    assert_eq!(names.last(), Some(&"dsl"));
+}
+
+#[test]
+fn empty_doc_blocks_yield_no_doc() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("src/A.java", "/**/\npublic class A {}\n"),
+            ("src/b.ts", "/**/\nexport class B {}\n"),
+            ("src/C.cs", "namespace N;\n/**/\npublic class C {}\n"),
+            ("src/d.hpp", "/**/\nclass D {};\n"),
+        ],
+    );
+
+    let models = [
+        JavaDriver.parse(&paths).unwrap(),
+        TypeScriptDriver.parse(&paths).unwrap(),
+        CSharpDriver.parse(&paths).unwrap(),
+        CppDriver.parse(&paths).unwrap(),
+    ];
+    for (model, name) in models.iter().zip(["A", "B", "C", "D"]) {
+        let symbol = model
+            .modules
+            .iter()
+            .flat_map(|m| &m.symbols)
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("{name} not found"));
+        assert_eq!(symbol.doc, None, "{name}");
+    }
+}
+
+#[test]
+fn rust_driver_tolerates_empty_block_doc_comments() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[(
+            "src/lib.rs",
+            "/*!*/\n/**/\nfn a() {}\n/** Documented. */\nfn b() {}\n",
+        )],
+    );
+
+    let model = RustDriver.parse(&paths).unwrap();
+    let lib = &model.modules[0];
+    assert_eq!(lib.doc, None);
+    let doc = |name: &str| {
+        let symbol = lib.symbols.iter().find(|s| s.name == name).unwrap();
+        symbol.doc.clone()
+    };
+    assert_eq!(doc("a"), None);
+    assert_eq!(doc("b").as_deref(), Some("Documented."));
+}
+
+#[test]
+fn elixir_driver_tolerates_truncated_function_heads() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("lib/a.ex", "defmodule A do\n  def f("),
+            ("lib/b.ex", "defmodule B do\n  @callback g("),
+            (
+                "lib/c.ex",
+                "defmodule C do\n  def h(a, b)) do\n  end\nend\n",
+            ),
+        ],
+    );
+
+    let model = ElixirDriver.parse(&paths).unwrap();
+    let names: Vec<&str> = model
+        .modules
+        .iter()
+        .flat_map(|m| &m.symbols)
+        .map(|s| s.name.as_str())
+        .collect();
+    assert!(names.contains(&"f/0"), "{names:?}");
+    assert!(names.contains(&"h/2"), "{names:?}");
+}
+
+#[test]
+fn scala_driver_tolerates_truncated_imports() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("src/main/scala/a/A.scala", "package a\n\nclass A\n"),
+            (
+                "src/main/scala/b/B.scala",
+                "package b\n\nclass B\n\nimport a.{",
+            ),
+        ],
+    );
+
+    let model = ScalaDriver.parse(&paths).unwrap();
+    assert!(model.modules.iter().any(|m| m.id.0 == "b.B"));
+}
+
+#[test]
+fn protobuf_driver_tolerates_truncated_imports() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "proto/acme/a/v1/a.proto",
+                "syntax = \"proto3\";\npackage acme.a.v1;\n\nmessage A {}\n",
+            ),
+            (
+                "proto/acme/b/v1/b.proto",
+                "syntax = 'proto3';\npackage acme.b.v1;\n\nimport 'acme/a/v1/a.proto';\nimport \"",
+            ),
+        ],
+    );
+
+    let model = ProtobufDriver.parse(&paths).unwrap();
+    assert!(module(&model, "acme.b.v1").external_imports.is_empty());
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![("acme.b.v1".to_string(), "acme.a.v1".to_string())]
+    );
+}
+
+#[test]
+fn kotlin_driver_implements_fully_qualified_interfaces() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "src/main/kotlin/com/acme/Repo.kt",
+                "package com.acme\n\ninterface Repo\n\nopen class Base\n",
+            ),
+            (
+                "src/main/kotlin/com/acme/app/A.kt",
+                "package com.acme.app\n\nimport com.acme.Repo\n\nclass A : Repo\n",
+            ),
+            (
+                "src/main/kotlin/com/acme/web/B.kt",
+                "package com.acme.web\n\nclass B : com.acme.Base(), com.acme.Repo\n",
+            ),
+        ],
+    );
+
+    let model = KotlinDriver.parse(&paths).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![
+            pair("com.acme.app.A", "com.acme.Repo"),
+            pair("com.acme.web.B", "com.acme.Repo"),
+        ]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![pair("com.acme.web.B", "com.acme.Repo")]
+    );
+}
+
+#[test]
+fn scala_driver_mixes_in_fully_qualified_traits() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "src/main/scala/com/acme/Mix.scala",
+                "package com.acme\n\ntrait Mix\n\nclass Base\n",
+            ),
+            (
+                "src/main/scala/com/acme/app/C.scala",
+                "package com.acme.app\n\nclass C extends com.acme.Mix\n",
+            ),
+            (
+                "src/main/scala/com/acme/web/D.scala",
+                "package com.acme.web\n\nclass D extends com.acme.Base\n",
+            ),
+        ],
+    );
+
+    let model = ScalaDriver.parse(&paths).unwrap();
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        edges(&model, EdgeKind::Implements),
+        vec![pair("com.acme.app.C", "com.acme.Mix")]
+    );
+    assert_eq!(
+        edges(&model, EdgeKind::Extends),
+        vec![pair("com.acme.web.D", "com.acme.Mix")]
+    );
+}
+
+#[test]
+fn python_driver_tolerates_malformed_notebooks() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("pkg/__init__.py", ""),
+            ("pkg/models.py", "class Net: ...\n"),
+            (
+                "notebooks/broken.ipynb",
+                "{\"cells\": [{\"cell_type\": \"code\",",
+            ),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    assert!(module(&model, "notebooks.broken").symbols.is_empty());
+    assert_eq!(module(&model, "pkg.models").symbols.len(), 1);
+}
+
+#[test]
+fn openapi_driver_skips_malformed_specs() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "billing/openapi.yaml",
+                "openapi: 3.0.3\npaths:\n  /invoices:\n    get:\n      operationId: listInvoices\n",
+            ),
+            (
+                "orders/openapi.yaml",
+                "openapi: 3.0.3\npaths: {/orders: [\n",
+            ),
+        ],
+    );
+
+    let model = OpenApiDriver.parse(&paths).unwrap();
+    let ids: Vec<&str> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["openapi/invoices"]);
+}
+
+#[test]
+fn sql_driver_skips_files_that_do_not_tokenize() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "migrations/001_users.sql",
+                "CREATE TABLE users (id INT PRIMARY KEY);\n",
+            ),
+            (
+                "migrations/002_broken.sql",
+                "CREATE TABLE posts (id INT);\nCOMMENT ON TABLE posts IS 'unterminated;\n",
+            ),
+        ],
+    );
+
+    let model = SqlDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["users"]);
+}
+
+#[test]
+fn terraform_driver_tolerates_malformed_files() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "infra/main.tf",
+                "resource \"aws_s3_bucket\" \"logs\" {\n  bucket = \"logs\"\n}\n",
+            ),
+            (
+                "infra/broken.tf",
+                "resource \"aws_s3_bucket\" \"data\" {\n  bucket =\n",
+            ),
+        ],
+    );
+
+    let model = TerraformDriver.parse(&paths).unwrap();
+    let names: Vec<_> = module(&model, "infra")
+        .symbols
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["aws_s3_bucket.logs"]);
+}
+
+#[test]
+fn docker_driver_tolerates_malformed_compose_files() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            (
+                "deploy/compose.yaml",
+                "services:\n  cache:\n    image: redis:7\n",
+            ),
+            (
+                "ops/docker-compose.yml",
+                "services:\n  db: [image: postgres\n",
+            ),
+        ],
+    );
+
+    let model = DockerDriver.parse(&paths).unwrap();
+    let ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    assert_eq!(ids, vec!["deploy/cache"]);
+}
+
+#[test]
+fn graphql_driver_tolerates_malformed_schema_files() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("schema/users.graphql", "type User {\n  id: ID!\n}\n"),
+            ("schema/orders.graphql", "type Order {\n  buyer: User\n"),
+        ],
+    );
+
+    let model = GraphqlDriver.parse(&paths).unwrap();
+    assert!(module(&model, "orders").symbols.is_empty());
+    assert_eq!(module(&model, "users").symbols[0].name, "User");
+}
+
+#[test]
+fn python_driver_reads_non_utf8_sources_lossily() {
+    let dir = TempDir::new().unwrap();
+    let mut paths = write_tree(dir.path(), &[("pkg/__init__.py", "")]);
+    // Latin-1 `©` in a comment.
+    let latin1 = dir.path().join("pkg/legal.py");
+    std::fs::write(&latin1, b"# \xa9 2024 Acme\n\ndef notice():\n    pass\n").unwrap();
+    paths.push(latin1);
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let legal = module(&model, "pkg.legal");
+    assert_eq!(legal.symbols[0].name, "notice");
+    assert_eq!(legal.symbols[0].line, 3);
+}
+
+#[test]
+fn cpp_driver_reads_latin1_comments() {
+    let dir = TempDir::new().unwrap();
+    let mut paths = write_tree(dir.path(), &[("src/hal/regs.h", "int regs_read(void);\n")]);
+    // CP1252 `©` and `µ` in comments, as firmware vendors ship them.
+    let sources: [(&str, &[u8]); 2] = [
+        (
+            "include/uart.h",
+            b"/* \xa9 2019 Acme GmbH */\n/// Baud rate in \xb5s ticks.\nvoid uart_init(int baud);\n",
+        ),
+        (
+            "src/uart.c",
+            b"#include \"uart.h\"\n#include \"hal/regs.h\"\n// \xa9 Acme\nvoid uart_init(int baud) { regs_read(); }\n",
+        ),
+    ];
+    for (rel, bytes) in sources {
+        let path = dir.path().join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+        paths.push(path);
+    }
+    paths.sort();
+
+    let model = CppDriver.parse(&paths).unwrap();
+    let init = &module(&model, "src/uart").symbols[0];
+    assert_eq!(init.name, "uart_init");
+    assert_eq!(init.line, 3);
+    assert_eq!(init.doc.as_deref(), Some("Baud rate in \u{fffd}s ticks."));
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![("src/uart".to_string(), "src/hal/regs".to_string())]
+    );
+}
+
+#[test]
+fn python_driver_qualifies_scripts_with_their_directory() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_tree(
+        dir.path(),
+        &[
+            ("util.py", "def root_helper(): ...\n"),
+            ("scripts/util.py", "def helper(): ...\n"),
+            ("scripts/main.py", "import util\nfrom util import helper\n"),
+            ("tests/conftest.py", "def fixture(): ...\n"),
+            ("tests/unit/conftest.py", "import conftest\n"),
+        ],
+    );
+
+    let model = PythonDriver.parse(&paths).unwrap();
+    let mut ids: Vec<_> = model.modules.iter().map(|m| m.id.0.as_str()).collect();
+    ids.sort();
+    assert_eq!(
+        ids,
+        vec![
+            "scripts.main",
+            "scripts.util",
+            "tests.conftest",
+            "tests.unit.conftest",
+            "util"
+        ]
+    );
+    assert_eq!(module(&model, "scripts.util").symbols[0].name, "helper");
+    assert_eq!(module(&model, "util").symbols[0].name, "root_helper");
+    // Bare imports look next to the script first.
+    assert_eq!(
+        edges(&model, EdgeKind::Import),
+        vec![("scripts.main".to_string(), "scripts.util".to_string())]
+    );
+}
@@ -1,6 +1,10 @@
 //! End-to-end pipeline tests in a `TempDir`.
 
+use std::path::PathBuf;
+
 use codetwin_legacy::config::Config;
+use codetwin_legacy::drivers::DriverRegistry;
+use codetwin_legacy::ir::{CodeModel, EdgeKind, Module, ModuleId};
 use codetwin_legacy::pipeline;
 use tempfile::TempDir;
 
@@ -37,6 +41,270 @@ fn discover_tolerates_missing_source_dir() {
     assert!(files.is_empty());
 }
 
+#[test]
+fn discover_honours_exclude_patterns() {
+    let dir = TempDir::new().unwrap();
+    let src = dir.path().join("src");
+    std::fs::create_dir_all(src.join("target/debug")).unwrap();
+    std::fs::write(src.join("lib.rs"), "").unwrap();
+    std::fs::write(src.join("lib.snap"), "").unwrap();
+    std::fs::write(src.join("target/debug/build.rs"), "").unwrap();
+
+    let mut config = Config {
+        source_dirs: vec![src.clone()],
+        ..Config::default()
+    };
+    config.exclude_patterns.push("**/*.snap".to_string());
+
+    let files = pipeline::discover(&config).unwrap();
+    assert_eq!(files, vec![src.join("lib.rs")]);
+}
+
+#[test]
+fn detect_projects_finds_nested_manifests() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    for (rel, contents) in [
+        (".gitignore", "generated/\n"),
+        ("services/api/go.mod", "module example.com/api\n"),
+        (
+            "web/package.json",
+            r#"{"devDependencies": {"typescript": "^5"}}"#,
+        ),
+        ("web/node_modules/left-pad/package.json", "{}\n"),
+        ("ml/pyproject.toml", "[project]\nname = \"ml\"\n"),
+        ("ml/src/ml/__init__.py", ""),
+        ("generated/client/package.json", "{}\n"),
+        ("infra/main.tf", ""),
+        ("infra/modules/net/main.tf", ""),
+    ] {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    // `.gitignore` only applies inside a repository.
+    std::fs::create_dir(root.join(".git")).unwrap();
+
+    let registry = DriverRegistry::default();
+    let config = Config::default();
+    let projects = pipeline::detect_projects(&registry, root, &config).unwrap();
+    let found: Vec<_> = projects
+        .iter()
+        .map(|p| {
+            let drivers: Vec<_> = p.drivers.iter().map(|d| d.name()).collect();
+            (p.label(), drivers)
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (None, vec!["terraform"]),
+            (Some("ml".to_string()), vec!["python"]),
+            (Some("services/api".to_string()), vec!["go"]),
+            (Some("web".to_string()), vec!["typescript"]),
+        ]
+    );
+
+    // Nested projects scan their own `src`, or else their whole root; the
+    // drivers add the conventional directories they detected.
+    assert_eq!(
+        projects[0].config(&config, root).source_dirs,
+        vec![PathBuf::from("src"), PathBuf::from("infra")]
+    );
+    assert_eq!(
+        projects[1].config(&config, root).source_dirs,
+        vec![PathBuf::from("ml/src")]
+    );
+    assert_eq!(
+        projects[2].config(&config, root).source_dirs,
+        vec![PathBuf::from("services/api")]
+    );
+}
+
+#[test]
+fn detect_projects_keeps_same_language_nested_manifests() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    for (rel, contents) in [
+        (
+            "package.json",
+            r#"{"devDependencies": {"typescript": "^5"}}"#,
+        ),
+        ("pyproject.toml", "[tool.ruff]\n"),
+        ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+        ("crates/core/Cargo.toml", "[package]\nname = \"core\"\n"),
+        ("crates/core/src/lib.rs", ""),
+        (
+            "packages/web/package.json",
+            r#"{"devDependencies": {"typescript": "^5"}}"#,
+        ),
+        ("packages/web/tsconfig.json", "{}\n"),
+        ("packages/web/src/a.ts", "export const a = 1;\n"),
+        ("ml/pyproject.toml", "[project]\nname = \"ml\"\n"),
+        ("ml/src/ml/__init__.py", ""),
+    ] {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    let registry = DriverRegistry::default();
+    let projects = pipeline::detect_projects(&registry, root, &Config::default()).unwrap();
+    let found: Vec<_> = projects
+        .iter()
+        .map(|p| {
+            let drivers: Vec<_> = p.drivers.iter().map(|d| d.name()).collect();
+            (p.label(), drivers)
+        })
+        .collect();
+    // The workspace member belongs to the root Rust project; the nested
+    // TypeScript and Python manifests lie outside the root's `src`.
+    assert_eq!(
+        found,
+        vec![
+            (None, vec!["rust", "python", "typescript"]),
+            (Some("ml".to_string()), vec!["python"]),
+            (Some("packages/web".to_string()), vec!["typescript"]),
+        ]
+    );
+}
+
+#[test]
+fn select_projects_honours_the_drivers_override() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    for (rel, contents) in [
+        ("Cargo.toml", "[package]\nname = \"app\"\n"),
+        ("src/lib.rs", ""),
+        (
+            "web/package.json",
+            r#"{"devDependencies": {"typescript": "^5"}}"#,
+        ),
+    ] {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    let registry = DriverRegistry::default();
+    let summary = |config: &Config| -> Vec<(Option<String>, Vec<&str>)> {
+        pipeline::select_projects(&registry, root, config)
+            .unwrap()
+            .iter()
+            .map(|p| (p.label(), p.drivers.iter().map(|d| d.name()).collect()))
+            .collect()
+    };
+
+    assert_eq!(
+        summary(&Config::default()),
+        vec![
+            (None, vec!["rust"]),
+            (Some("web".to_string()), vec!["typescript"]),
+        ]
+    );
+    let config = Config {
+        drivers: Some(vec!["python".to_string(), "nope".to_string()]),
+        ..Config::default()
+    };
+    assert_eq!(summary(&config), vec![(None, vec!["python"])]);
+}
+
+#[test]
+fn detect_projects_merges_loose_source_directories() {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    for (rel, contents) in [
+        (
+            "proto/acme/orders/v1/order.proto",
+            "syntax = \"proto3\";\npackage acme.orders.v1;\n\nimport \"acme/shipping/v1/shipping.proto\";\n\nmessage Order {\n  acme.shipping.v1.Parcel parcel = 1;\n}\n",
+        ),
+        (
+            "proto/acme/shipping/v1/shipping.proto",
+            "syntax = \"proto3\";\npackage acme.shipping.v1;\n\nimport \"acme/orders/v1/order.proto\";\n\nmessage Parcel {\n  acme.orders.v1.Order order = 1;\n}\n",
+        ),
+        ("services/api/go.mod", "module example.com/api\n"),
+        ("services/api/api.proto", "syntax = \"proto3\";\n"),
+        ("services/web/web.proto", "syntax = \"proto3\";\n"),
+    ] {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    let registry = DriverRegistry::default();
+    let config = Config::default();
+    let projects = pipeline::detect_projects(&registry, root, &config).unwrap();
+    let found: Vec<_> = projects
+        .iter()
+        .map(|p| {
+            let drivers: Vec<_> = p.drivers.iter().map(|d| d.name()).collect();
+            (p.label(), drivers)
+        })
+        .collect();
+    // The Go module keeps its `.proto` files to itself.
+    assert_eq!(
+        found,
+        vec![
+            (None, vec![]),
+            (Some("proto/acme".to_string()), vec!["protobuf"]),
+            (Some("services/api".to_string()), vec!["go", "protobuf"]),
+            (Some("services/web".to_string()), vec!["protobuf"]),
+        ]
+    );
+
+    let mut config = projects[1].config(&config, root);
+    config.source_dirs = config.source_dirs.iter().map(|d| root.join(d)).collect();
+    let files = pipeline::discover(&config).unwrap();
+    let model = projects[1].drivers[0].parse(&files).unwrap();
+    let mut imports: Vec<_> = model
+        .edges
+        .iter()
+        .filter(|e| e.kind == EdgeKind::Import)
+        .map(|e| (e.from.0.as_str(), e.to.0.as_str()))
+        .collect();
+    imports.sort();
+    assert_eq!(
+        imports,
+        vec![
+            ("acme.orders.v1", "acme.shipping.v1"),
+            ("acme.shipping.v1", "acme.orders.v1"),
+        ]
+    );
+}
+
+#[test]
+fn label_model_nests_driver_groups_under_the_project() {
+    let module = |id: &str, group: Option<&str>| Module {
+        id: ModuleId(id.to_string()),
+        name: id.to_string(),
+        path: PathBuf::from(format!("{id}.ex")),
+        symbols: Vec::new(),
+        doc: None,
+        external_imports: Vec::new(),
+        group: group.map(String::from),
+        container: None,
+    };
+    let mut model = CodeModel::new("elixir");
+    model.modules = vec![
+        module("Shop.Accounts", Some("accounts")),
+        module("Shop", None),
+    ];
+
+    pipeline::label_model(&mut model, "apps/shop");
+    let labelled: Vec<_> = model
+        .modules
+        .iter()
+        .map(|m| (m.id.0.as_str(), m.group.as_deref()))
+        .collect();
+    assert_eq!(
+        labelled,
+        vec![
+            ("apps/shop/Shop.Accounts", Some("apps/shop/accounts")),
+            ("apps/shop/Shop", Some("apps/shop")),
+        ]
+    );
+}
+
 #[test]
 #[ignore = "touches the real filesystem; run with --include-ignored"]
 fn gen_end_to_end_produces_output_file() {
@@ -64,5 +332,35 @@ fn gen_end_to_end_produces_output_file() {
     assert!(out.exists(), "output file must be written");
 }
 
-// TODO(Phase 1.e): exclude_patterns should be honoured by discover.
 // TODO(Phase 1.d): merge de-duplication test once the real merge lands.
+
+#[test]
+fn route_hands_each_driver_its_claimed_files() {
+    let dir = TempDir::new().unwrap();
+    let files: Vec<PathBuf> = ["main.rs", "app.py", "run", "README.md", "query.sql"]
+        .iter()
+        .map(|name| dir.path().join(name))
+        .collect();
+    std::fs::write(&files[0], "fn main() {}\n").unwrap();
+    std::fs::write(&files[1], "").unwrap();
+    std::fs::write(&files[2], "#!/bin/sh\n").unwrap();
+    std::fs::write(&files[3], "").unwrap();
+    std::fs::write(&files[4], "").unwrap();
+
+    let registry = DriverRegistry::default();
+    let active = vec![
+        registry.get("rust").unwrap(),
+        registry.get("docker").unwrap(),
+    ];
+    let routed = pipeline::route(&files, &active);
+    assert_eq!(routed[0], vec![files[0].clone()]);
+    assert_eq!(routed[1], files, "docker reads every file");
+
+    // `.py` and `.sql` belong to inactive drivers, `run` is a script; the
+    // README looks like no source at all.
+    let unclaimed = pipeline::unclaimed(&files, &registry, &active);
+    assert_eq!(
+        unclaimed,
+        vec![files[1].clone(), files[2].clone(), files[4].clone()]
+    );
+}
@@ -17,6 +17,9 @@ fn save_then_load_returns_the_same_model() {
         path: "src/lib.rs".into(),
         symbols: Vec::new(),
         doc: None,
+        external_imports: Vec::new(),
+        group: None,
+        container: None,
     });
 
     let path = store.save("abc1234", &model).unwrap();